@@ -4,7 +4,9 @@ use quote::quote;
 use syn::{parse_macro_input, ItemFn};
 
 // Import shared utilities from cachelito-macro-utils
-use cachelito_macro_utils::{generate_key_expr, parse_async_attributes, AsyncCacheAttributes};
+use cachelito_macro_utils::{
+    generate_key_expr, is_arc_type, is_result_type, parse_async_attributes, AsyncCacheAttributes,
+};
 
 /// Parse macro attributes from the attribute token stream
 fn parse_attributes(attr: TokenStream) -> AsyncCacheAttributes {
@@ -19,18 +21,85 @@ fn parse_attributes(attr: TokenStream) -> AsyncCacheAttributes {
     }
 }
 /// Generate the appropriate insert call based on max_memory configuration
-fn generate_insert_call(max_memory_expr: &TokenStream2) -> TokenStream2 {
+///
+/// With `shared`, the value is stored in an `Arc` (`arc = true`).
+fn generate_insert_call(max_memory_expr: &TokenStream2, shared: bool) -> TokenStream2 {
     let max_memory_str = max_memory_expr.to_string();
     let has_max_memory = !max_memory_str.contains("None");
+    let value = if shared {
+        quote! { ::std::sync::Arc::new(__result.clone()) }
+    } else {
+        quote! { __result.clone() }
+    };
 
     if has_max_memory {
-        quote! { __cache.insert_with_memory(&__key, __result.clone()); }
+        quote! { __cache.insert_with_memory(&__key, #value); }
     } else {
-        quote! { __cache.insert(&__key, __result.clone()); }
+        quote! { __cache.insert(&__key, #value); }
+    }
+}
+
+/// Generate the `AsyncGlobalCache` constructor expression, registering the
+/// `on_evict` removal listener and the custom eviction strategy when specified
+#[allow(clippy::too_many_arguments)]
+fn generate_cache_constructor(
+    cache_ident: &syn::Ident,
+    order_ident: &syn::Ident,
+    stats_ident: &syn::Ident,
+    limit_expr: &TokenStream2,
+    max_memory_expr: &TokenStream2,
+    policy_expr: &TokenStream2,
+    ttl_expr: &TokenStream2,
+    frequency_weight_expr: &TokenStream2,
+    on_evict: &Option<syn::Path>,
+    strategy: &Option<syn::Path>,
+    accesses: Option<&syn::Ident>,
+    timers: Option<&syn::Ident>,
+    memory: Option<&syn::Ident>,
+    eviction_state: Option<&syn::Ident>,
+) -> TokenStream2 {
+    let removal_listener = if let Some(listener_fn) = on_evict {
+        quote! { .with_removal_listener(&#listener_fn) }
+    } else {
+        quote! {}
+    };
+    let eviction_strategy = match strategy {
+        Some(strategy) => quote! { .with_eviction_strategy(&#strategy) },
+        None => quote! {},
+    };
+    let access_buffer = match accesses {
+        Some(accesses_ident) => quote! { .with_access_buffer(&*#accesses_ident) },
+        None => quote! {},
+    };
+    let timer_wheel = match timers {
+        Some(timers_ident) => quote! { .with_timer_wheel(&*#timers_ident) },
+        None => quote! {},
+    };
+    let memory_usage = match memory {
+        Some(memory_ident) => quote! { .with_memory_usage(&#memory_ident) },
+        None => quote! {},
+    };
+    let eviction_state = match eviction_state {
+        Some(state_ident) => quote! { .with_eviction_state(&*#state_ident) },
+        None => quote! {},
+    };
+
+    quote! {
+        cachelito_core::AsyncGlobalCache::new(
+            &*#cache_ident,
+            &*#order_ident,
+            #limit_expr,
+            #max_memory_expr,
+            #policy_expr,
+            #ttl_expr,
+            #frequency_weight_expr,
+            &*#stats_ident,
+        )#removal_listener #eviction_strategy #access_buffer #timer_wheel #memory_usage #eviction_state
     }
 }
 
 /// Generate common cache lookup and execution logic
+#[allow(clippy::too_many_arguments)]
 fn generate_cache_logic_block(
     key_expr: &TokenStream2,
     cache_ident: &syn::Ident,
@@ -41,36 +110,54 @@ fn generate_cache_logic_block(
     policy_expr: &TokenStream2,
     ttl_expr: &TokenStream2,
     frequency_weight_expr: &TokenStream2,
+    on_evict: &Option<syn::Path>,
+    strategy: &Option<syn::Path>,
+    accesses: Option<&syn::Ident>,
+    timers: Option<&syn::Ident>,
+    memory: Option<&syn::Ident>,
+    eviction_state: Option<&syn::Ident>,
     invalidation_check: &TokenStream2,
     block: &syn::Block,
     cache_insert: &TokenStream2,
 ) -> TokenStream2 {
+    let cache_constructor = generate_cache_constructor(
+        cache_ident,
+        order_ident,
+        stats_ident,
+        limit_expr,
+        max_memory_expr,
+        policy_expr,
+        ttl_expr,
+        frequency_weight_expr,
+        on_evict,
+        strategy,
+        accesses,
+        timers,
+        memory,
+        eviction_state,
+    );
+
     quote! {
         // Generate cache key
         let __key = #key_expr;
 
         // Create AsyncGlobalCache wrapper
-        let __cache = cachelito_core::AsyncGlobalCache::new(
-            &*#cache_ident,
-            &*#order_ident,
-            #limit_expr,
-            #max_memory_expr,
-            #policy_expr,
-            #ttl_expr,
-            #frequency_weight_expr,
-            &*#stats_ident,
-        );
+        let __cache = #cache_constructor;
 
         // Try to get from cache
         if let Some(__cached) = __cache.get(&__key) {
             #invalidation_check
         }
 
-        // Execute original async function (cache miss or expired)
+        // Execute original async function (cache miss or expired), timing it for
+        // cost-aware policies
+        let __started = ::std::time::Instant::now();
         let __result = (async #block).await;
 
         // Cache the result (conditional based on cache_if predicate or default behavior)
-        #cache_insert
+        ::cachelito_core::with_compute_cost(__started.elapsed(), || {
+            #cache_insert
+        });
 
         __result
     }
@@ -101,6 +188,12 @@ fn generate_cache_logic_block(
 ///   - `"arc"` - Adaptive Replacement Cache
 ///   - `"random"` - Random Replacement
 ///   - `"tlru"` - Time-aware Least Recently Used (combines recency, frequency, and age)
+///   - `"gdsf"` - Greedy-Dual-Size-Frequency (keeps small, popular entries that were slow
+///     to compute)
+///   - `"sieve"` - SIEVE (hits only set a visited bit, a hand evicts unvisited entries)
+///   - `"s3fifo"` - S3-FIFO (a small queue filters out entries accessed at most once)
+///   - `path::to::Strategy` - A custom `EvictionStrategy` of the cached type: a unit struct or
+///     a `static`, e.g. `policy = ByPriority`
 /// - `ttl` (optional): Time-to-live in seconds. Entries older than this will be
 ///   automatically removed when accessed. Default: None (no expiration).
 /// - `frequency_weight` (optional): Weight factor for frequency in TLRU policy.
@@ -122,6 +215,16 @@ fn generate_cache_logic_block(
 /// - `cache_if` (optional): Function that determines if a result should be cached.
 ///   Signature: `fn(key: &String, value: &T) -> bool`. Return `true` to cache the result.
 ///   When not specified, all results are cached (default behavior).
+/// - `on_evict` (optional): Function called whenever an entry leaves the cache.
+///   Signature: `fn(key: &str, value: &T, cause: RemovalCause)`. The cause tells whether the
+///   entry was evicted by capacity or memory limits, expired, invalidated, replaced or removed
+///   explicitly. It is never called while the cache is locked.
+/// - `buffered_access` (optional): Record LRU, ARC and TLRU hits in a lossy buffer and
+///   move them in the eviction order in batches, instead of locking the order on every
+///   hit. Recency becomes sampled. Default: `false`.
+/// - `arc` (optional): Store values in an `Arc`, so that hits and evictions only bump a
+///   reference count; the function still returns its value, cloned outside the cache
+///   locks. Functions returning `Arc<R>` already share their values. Default: `false`.
 ///
 /// # Cache Behavior
 ///
@@ -230,11 +333,18 @@ pub fn cache_async(attr: TokenStream, item: TokenStream) -> TokenStream {
     let fn_name_string = fn_name.to_string();
     let fn_name_str = attrs.custom_name.as_ref().unwrap_or(&fn_name_string);
 
-    // Extract return type
+    // Extract return type; with `arc = true` the cache stores it in an `Arc`, unless
+    // the function already returns one
     let ret_type = match &sig.output {
         syn::ReturnType::Default => quote! { () },
         syn::ReturnType::Type(_, ty) => quote! { #ty },
     };
+    let shared = attrs.arc && !is_arc_type(&sig.output);
+    let ret_type = if shared {
+        quote! { ::std::sync::Arc<#ret_type> }
+    } else {
+        ret_type
+    };
 
     // Collect function arguments for key generation
     let mut has_self = false;
@@ -265,22 +375,29 @@ pub fn cache_async(attr: TokenStream, item: TokenStream) -> TokenStream {
         &format!("__STATS_{}", fn_name.to_string().to_uppercase()),
         fn_name.span(),
     );
+    let accesses_ident = syn::Ident::new(
+        &format!("__ACCESSES_{}", fn_name.to_string().to_uppercase()),
+        fn_name.span(),
+    );
+    let accesses = attrs.buffered_access.then_some(&accesses_ident);
+    let timers_ident = syn::Ident::new(
+        &format!("__TIMERS_{}", fn_name.to_string().to_uppercase()),
+        fn_name.span(),
+    );
+    let memory_ident = syn::Ident::new(
+        &format!("__MEMORY_{}", fn_name.to_string().to_uppercase()),
+        fn_name.span(),
+    );
+    let state_ident = syn::Ident::new(
+        &format!("__EVICTION_STATE_{}", fn_name.to_string().to_uppercase()),
+        fn_name.span(),
+    );
 
     // Generate cache key expression
     let key_expr = generate_key_expr(has_self, &arg_pats);
 
-    // Detect Result type and extract inner type if needed
-    let (is_result, _cache_value_type) = {
-        let s = quote!(#ret_type).to_string().replace(' ', "");
-        if s.starts_with("Result<") || s.starts_with("std::result::Result<") {
-            // Extract the Ok type from Result<T, E>
-            // For simplicity, we'll use the full return type and let the compiler infer
-            // But we need to specify that the cache stores the inner T, not Result<T, E>
-            (true, ret_type.clone())
-        } else {
-            (false, ret_type.clone())
-        }
-    };
+    // Detect Result types (including aliases such as `io::Result<T>`), unless `result` is explicit
+    let is_result = attrs.result.unwrap_or_else(|| is_result_type(&sig.output));
 
     let limit_expr = &attrs.limit;
     let policy_str = &attrs.policy;
@@ -293,9 +410,17 @@ pub fn cache_async(attr: TokenStream, item: TokenStream) -> TokenStream {
         cachelito_core::EvictionPolicy::from(#policy_str)
     };
 
+    // Shared values are cloned out of their `Arc` on a hit, outside the cache locks
+    let unshare = if shared {
+        quote! { let __cached = ::std::clone::Clone::clone(&*__cached); }
+    } else {
+        quote! {}
+    };
+
     // Generate invalidation check expression
     let invalidation_check = if let Some(pred_fn) = &attrs.invalidate_on {
         quote! {
+            #unshare
             // Validate cached value with invalidation check
             // If function returns true, entry is stale - don't use it, re-execute
             if !#pred_fn(&__key, &__cached) {
@@ -306,13 +431,14 @@ pub fn cache_async(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     } else {
         quote! {
+            #unshare
             return __cached;
         }
     };
 
     // Generate cache logic based on Result or regular return
     let cache_logic = {
-        let insert_call = generate_insert_call(max_memory_expr);
+        let insert_call = generate_insert_call(max_memory_expr, shared);
 
         // Generate conditional caching logic
         let cache_insert = if let Some(pred_fn) = &attrs.cache_if {
@@ -323,9 +449,9 @@ pub fn cache_async(attr: TokenStream, item: TokenStream) -> TokenStream {
                 }
             }
         } else if is_result {
-            // Default behavior for Result types: only cache Ok values
+            // Result types (and `result = true`) only cache values accepted by `CacheableOutcome`
             quote! {
-                if __result.is_ok() {
+                if ::cachelito_core::CacheableOutcome::is_cacheable(&__result) {
                     #insert_call
                 }
             }
@@ -345,12 +471,36 @@ pub fn cache_async(attr: TokenStream, item: TokenStream) -> TokenStream {
             &policy_expr,
             ttl_expr,
             frequency_weight_expr,
+            &attrs.on_evict,
+            &attrs.strategy,
+            accesses,
+            Some(&timers_ident),
+            Some(&memory_ident),
+            Some(&state_ident),
             &invalidation_check,
             block,
             &cache_insert,
         )
     };
 
+    // Constructor used by the invalidation callbacks so that removals reach the listener
+    let cache_constructor = generate_cache_constructor(
+        &cache_ident,
+        &order_ident,
+        &stats_ident,
+        limit_expr,
+        max_memory_expr,
+        &policy_expr,
+        ttl_expr,
+        frequency_weight_expr,
+        &attrs.on_evict,
+        &attrs.strategy,
+        accesses,
+        Some(&timers_ident),
+        Some(&memory_ident),
+        Some(&state_ident),
+    );
+
     // Generate invalidation registration code
     let invalidation_registration = if !attrs.tags.is_empty()
         || !attrs.events.is_empty()
@@ -375,8 +525,8 @@ pub fn cache_async(attr: TokenStream, item: TokenStream) -> TokenStream {
                 cachelito_core::InvalidationRegistry::global().register_callback(
                     #fn_name_str,
                     move || {
-                        #cache_ident.clear();
-                        #order_ident.lock().clear();
+                        let __cache = #cache_constructor;
+                        __cache.invalidate_all();
                     }
                 );
             });
@@ -393,38 +543,80 @@ pub fn cache_async(attr: TokenStream, item: TokenStream) -> TokenStream {
             cachelito_core::InvalidationRegistry::global().register_invalidation_callback(
                 #fn_name_str,
                 move |invalidation_check: &dyn Fn(&str) -> bool| {
-                    // Collect keys to remove based on invalidation check function
-                    let keys_to_remove: Vec<String> = #cache_ident
-                        .iter()
-                        .filter(|entry| invalidation_check(entry.key().as_str()))
-                        .map(|entry| entry.key().clone())
-                        .collect();
-
-                    // Remove matched keys
-                    let mut order_write = #order_ident.lock();
-                    for key in &keys_to_remove {
-                        #cache_ident.remove(key);
-                        if let Some(pos) = order_write.iter().position(|k| k == key) {
-                            order_write.remove(pos);
-                        }
-                    }
+                    // Remove the keys matched by the invalidation check function
+                    let __cache = #cache_constructor;
+                    __cache.invalidate_where(invalidation_check);
+                }
+            );
+        });
+    };
+
+    // Register the cache for `purge_expired` and the expiration sweeper
+    let purge_registration = quote! {
+        static PURGE_REGISTERED: once_cell::sync::OnceCell<()> = once_cell::sync::OnceCell::new();
+        PURGE_REGISTERED.get_or_init(|| {
+            cachelito_core::InvalidationRegistry::global().register_purge_callback(
+                #fn_name_str,
+                move || {
+                    let __cache = #cache_constructor;
+                    __cache.purge_expired()
+                }
+            );
+        });
+
+        // Register the cache for `shrink_all_caches` and the memory pressure monitor
+        static SHRINK_REGISTERED: once_cell::sync::OnceCell<()> = once_cell::sync::OnceCell::new();
+        SHRINK_REGISTERED.get_or_init(|| {
+            cachelito_core::InvalidationRegistry::global().register_shrink_callback(
+                #fn_name_str,
+                move |fraction: f64| {
+                    let __cache = #cache_constructor;
+                    __cache.shrink(fraction)
                 }
             );
         });
     };
 
+    // Memory-aware caches count against the global memory budget
+    let budget_registration = if max_memory_expr.to_string().contains("None") {
+        quote! {}
+    } else {
+        quote! {
+            static BUDGET_REGISTERED: once_cell::sync::OnceCell<()> = once_cell::sync::OnceCell::new();
+            BUDGET_REGISTERED.get_or_init(|| {
+                cachelito_core::MemoryBudget::global().register(
+                    #fn_name_str,
+                    move || {
+                        let __cache = #cache_constructor;
+                        __cache.memory_usage()
+                    },
+                    move || {
+                        let __cache = #cache_constructor;
+                        __cache.evict()
+                    },
+                );
+            });
+        }
+    };
+
     // Generate final expanded code
     let expanded = quote! {
         #vis #sig {
             use std::collections::VecDeque;
 
-            // DashMap stores: (value, timestamp, frequency)
-            static #cache_ident: once_cell::sync::Lazy<dashmap::DashMap<String, (#ret_type, u64, u64)>> =
+            static #cache_ident: once_cell::sync::Lazy<dashmap::DashMap<String, cachelito_core::CacheEntry<#ret_type>>> =
                 once_cell::sync::Lazy::new(|| dashmap::DashMap::new());
             static #order_ident: once_cell::sync::Lazy<parking_lot::Mutex<VecDeque<String>>> =
                 once_cell::sync::Lazy::new(|| parking_lot::Mutex::new(VecDeque::new()));
             static #stats_ident: once_cell::sync::Lazy<cachelito_core::CacheStats> =
                 once_cell::sync::Lazy::new(|| cachelito_core::CacheStats::new());
+            static #accesses_ident: once_cell::sync::Lazy<cachelito_core::AccessBuffer> =
+                once_cell::sync::Lazy::new(cachelito_core::AccessBuffer::new);
+            static #timers_ident: once_cell::sync::Lazy<cachelito_core::TimerWheel> =
+                once_cell::sync::Lazy::new(cachelito_core::TimerWheel::new);
+            static #memory_ident: cachelito_core::MemoryUsage = cachelito_core::MemoryUsage::new();
+            static #state_ident: once_cell::sync::Lazy<cachelito_core::EvictionState> =
+                once_cell::sync::Lazy::new(cachelito_core::EvictionState::new);
 
             // Register stats in the registry (happens once on first access)
             static STATS_REGISTERED: once_cell::sync::OnceCell<()> = once_cell::sync::OnceCell::new();
@@ -434,6 +626,8 @@ pub fn cache_async(attr: TokenStream, item: TokenStream) -> TokenStream {
 
             #invalidation_registration
             #invalidation_callback_registration
+            #purge_registration
+            #budget_registration
 
             #cache_logic
         }
@@ -450,7 +644,7 @@ mod tests {
     #[test]
     fn test_generate_insert_call_without_max_memory() {
         let max_memory_expr = quote! { None };
-        let result = generate_insert_call(&max_memory_expr);
+        let result = generate_insert_call(&max_memory_expr, false);
         let result_str = result.to_string();
 
         assert!(result_str.contains("__cache") && result_str.contains("insert"));
@@ -460,12 +654,21 @@ mod tests {
     #[test]
     fn test_generate_insert_call_with_max_memory() {
         let max_memory_expr = quote! { Some(1024 * 1024) };
-        let result = generate_insert_call(&max_memory_expr);
+        let result = generate_insert_call(&max_memory_expr, false);
         let result_str = result.to_string();
 
         assert!(result_str.contains("insert_with_memory"));
     }
 
+    #[test]
+    fn test_generate_insert_call_shared() {
+        let max_memory_expr = quote! { None };
+        let result = generate_insert_call(&max_memory_expr, true);
+        let result_str = result.to_string();
+
+        assert!(result_str.contains("Arc :: new (__result . clone ())"));
+    }
+
     #[test]
     fn test_generate_cache_logic_block_structure() {
         let key_expr = quote! { format!("{:?}", arg1) };
@@ -490,6 +693,12 @@ mod tests {
             &policy_expr,
             &ttl_expr,
             &quote! { Option::<f64>::None },
+            &None,
+            &None,
+            None,
+            None,
+            None,
+            None,
             &invalidation_check,
             &block,
             &cache_insert,
@@ -535,6 +744,12 @@ mod tests {
             &policy_expr,
             &ttl_expr,
             &quote! { Option::<f64>::None },
+            &None,
+            &None,
+            None,
+            None,
+            None,
+            None,
             &invalidation_check,
             &block,
             &cache_insert,
@@ -554,7 +769,7 @@ mod tests {
     #[test]
     fn test_insert_call_format() {
         let max_memory_none = quote! { None };
-        let result_none = generate_insert_call(&max_memory_none);
+        let result_none = generate_insert_call(&max_memory_none, false);
 
         // Should call insert method
         assert_eq!(
@@ -563,7 +778,7 @@ mod tests {
         );
 
         let max_memory_some = quote! { Some(2048) };
-        let result_some = generate_insert_call(&max_memory_some);
+        let result_some = generate_insert_call(&max_memory_some, false);
 
         // Should call insert_with_memory method
         assert_eq!(
@@ -602,6 +817,12 @@ mod tests {
             &policy_expr,
             &ttl_expr,
             &quote! { Option::<f64>::None },
+            &None,
+            &None,
+            None,
+            None,
+            None,
+            None,
             &custom_invalidation,
             &block,
             &cache_insert,
@@ -641,6 +862,12 @@ mod tests {
             &policy_expr,
             &ttl_expr,
             &quote! { Option::<f64>::None },
+            &None,
+            &None,
+            None,
+            None,
+            None,
+            None,
             &invalidation_check,
             &block,
             &conditional_insert,
@@ -657,7 +884,7 @@ mod tests {
         let none_variants = vec![quote! { None }, quote! { ::std::option::Option::None }];
 
         for none_expr in none_variants {
-            let result = generate_insert_call(&none_expr);
+            let result = generate_insert_call(&none_expr, false);
             let result_str = result.to_string();
             assert!(
                 result_str.contains("insert") && !result_str.contains("insert_with_memory"),
@@ -677,7 +904,7 @@ mod tests {
         ];
 
         for some_expr in some_variants {
-            let result = generate_insert_call(&some_expr);
+            let result = generate_insert_call(&some_expr, false);
             let result_str = result.to_string();
             assert!(
                 result_str.contains("insert_with_memory"),
@@ -717,6 +944,12 @@ mod tests {
             &policy_expr,
             &ttl_expr,
             &quote! { Option::<f64>::None },
+            &None,
+            &None,
+            None,
+            None,
+            None,
+            None,
             &invalidation_check,
             &block,
             &cache_insert,
@@ -754,6 +987,12 @@ mod tests {
             &policy_expr,
             &ttl_expr,
             &quote! { Option::<f64>::None },
+            &None,
+            &None,
+            None,
+            None,
+            None,
+            None,
             &invalidation_check,
             &block,
             &cache_insert,
@@ -2,9 +2,9 @@ use cachelito_async::cache_async;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Duration;
 
-/// Example demonstrating the LFU (Least Frequently Used) eviction policy for async functions.
-///
-/// Run with: cargo run --example async_lfu
+// Example demonstrating the LFU (Least Frequently Used) eviction policy for async functions.
+//
+// Run with: cargo run --example async_lfu
 
 // Simulate API call counter
 static API_CALLS: AtomicU32 = AtomicU32::new(0);
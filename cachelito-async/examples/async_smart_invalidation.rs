@@ -8,6 +8,7 @@ use cachelito_core::{invalidate_by_dependency, invalidate_by_event, invalidate_b
 use std::time::Duration;
 use tokio::time::sleep;
 
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct UserProfile {
     id: u64,
@@ -159,7 +159,7 @@ async fn main() {
     // Example 4: High-concurrency stock price updates
     println!("\n\n--- Example 4: High-Concurrency Stock Price Cache ---");
 
-    let stocks = vec!["AAPL", "GOOGL", "MSFT", "AMZN", "TSLA"];
+    let stocks = ["AAPL", "GOOGL", "MSFT", "AMZN", "TSLA"];
 
     println!("📊 Fetching initial stock prices concurrently...");
 
@@ -1,6 +1,7 @@
 use cachelito_async::cache_async;
 use std::time::Duration;
 use tokio::time::sleep;
+#[allow(clippy::ptr_arg)]
 fn should_cache_non_empty(_key: &String, result: &Vec<String>) -> bool {
     !result.is_empty()
 }
@@ -4,7 +4,7 @@ static EXEC_COUNT: AtomicUsize = AtomicUsize::new(0);
 
 async fn simple(x: u32) -> u32 {
     static ASYNC_CACHE_SIMPLE: once_cell::sync::Lazy<dashmap::DashMap<String, (u32, u64)>> =
-        once_cell::sync::Lazy::new(|| dashmap::DashMap::new());
+        once_cell::sync::Lazy::new(dashmap::DashMap::new);
 
     let __key = {
         let mut __key_parts = Vec::new();
@@ -23,7 +23,7 @@ async fn simple(x: u32) -> u32 {
             false
         };
         if !__is_expired {
-            let __cached_value = __entry_ref.0.clone();
+            let __cached_value = __entry_ref.0;
             drop(__entry_ref);
             return __cached_value;
         }
@@ -41,7 +41,7 @@ async fn simple(x: u32) -> u32 {
         .unwrap()
         .as_secs();
 
-    ASYNC_CACHE_SIMPLE.insert(__key, (__result.clone(), __timestamp));
+    ASYNC_CACHE_SIMPLE.insert(__key, (__result, __timestamp));
     __result
 }
 
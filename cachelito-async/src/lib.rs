@@ -9,7 +9,7 @@
 //! ## Features
 //!
 //! - 🚀 **Lock-free caching**: Uses DashMap for concurrent access without blocking
-//! - 🎯 **Multiple eviction policies**: FIFO, LRU, LFU, ARC, Random, and TLRU
+//! - 🎯 **Multiple eviction policies**: FIFO, LRU, LFU, ARC, Random, TLRU, GDSF, SIEVE, and S3-FIFO
 //! - ⏰ **TLRU with frequency_weight**: Fine-tune recency vs frequency balance (v0.15.0)
 //! - 💾 **Memory-based limits**: Control cache size by memory usage
 //! - ⏱️ **TTL support**: Automatic expiration of cached entries
@@ -96,7 +96,7 @@
 //! ## Macro Parameters
 //!
 //! - `limit`: Maximum number of entries (default: unlimited)
-//! - `policy`: Eviction policy - `"fifo"`, `"lru"`, `"lfu"`, `"arc"`, `"random"`, or `"tlru"` (default: `"fifo"`)
+//! - `policy`: Eviction policy - `"fifo"`, `"lru"`, `"lfu"`, `"arc"`, `"random"`, `"tlru"`, `"gdsf"`, `"sieve"`, or `"s3fifo"` (default: `"fifo"`)
 //! - `ttl`: Time-to-live in seconds (default: none)
 //! - `frequency_weight`: Weight factor for frequency in TLRU policy (default: 1.0)
 //! - `name`: Custom cache identifier (default: function name)
@@ -1,7 +1,6 @@
 //! Integration tests for ARC (Adaptive Replacement Cache) eviction policy in async context
 
 use cachelito_async::cache_async;
-use tokio;
 
 #[cache_async(policy = "arc", limit = 5)]
 async fn cached_computation(x: i32) -> i32 {
@@ -2,6 +2,7 @@
 use cachelito_async::cache_async;
 use std::sync::atomic::{AtomicU32, Ordering};
 // Test 1: Only cache non-empty vectors
+#[allow(clippy::ptr_arg)]
 fn should_cache_non_empty(_key: &String, result: &Vec<i32>) -> bool {
     !result.is_empty()
 }
@@ -20,10 +21,10 @@ async fn test_async_cache_if_empty_not_cached() {
     ASYNC_CALL_COUNT_1.store(0, Ordering::SeqCst);
     // Empty results should not be cached
     let result1 = get_numbers_async(0).await;
-    assert_eq!(result1, vec![]);
+    assert_eq!(result1, Vec::<i32>::new());
     assert_eq!(ASYNC_CALL_COUNT_1.load(Ordering::SeqCst), 1);
     let result2 = get_numbers_async(0).await;
-    assert_eq!(result2, vec![]);
+    assert_eq!(result2, Vec::<i32>::new());
     // Should execute again (not cached)
     assert_eq!(ASYNC_CALL_COUNT_1.load(Ordering::SeqCst), 2);
     // Non-empty results should be cached
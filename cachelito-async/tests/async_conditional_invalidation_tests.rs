@@ -50,7 +50,7 @@ async fn test_async_conditional_by_pattern() {
     // Invalidate all products with 3-digit IDs (100-999)
     let result = invalidate_with("get_product_async_pred", |key: &str| {
         if let Ok(id) = key.parse::<u64>() {
-            id >= 100 && id < 1000
+            (100..1000).contains(&id)
         } else {
             false
         }
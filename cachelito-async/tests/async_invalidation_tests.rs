@@ -1,5 +1,8 @@
 //! Integration tests for async cache invalidation
 
+// The test mutex is held for the whole test on purpose to serialize them
+#![allow(clippy::await_holding_lock)]
+
 use cachelito_async::cache_async;
 use cachelito_core::{invalidate_by_dependency, invalidate_by_event, invalidate_by_tag};
 use std::sync::atomic::Ordering;
@@ -0,0 +1,51 @@
+//! Integration tests for async caches recording hits in an access buffer (`buffered_access = true`)
+
+use cachelito_async::cache_async;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cache_async(limit = 3, policy = "lru", buffered_access = true)]
+async fn lookup(x: u64) -> u64 {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    x * 10
+}
+
+#[cache_async(limit = 16, policy = "arc", buffered_access = true)]
+async fn contended(x: u64) -> u64 {
+    x + 7
+}
+
+#[tokio::test]
+async fn test_buffered_hits_reach_lru_order() {
+    for x in 1..=3 {
+        assert_eq!(lookup(x).await, x * 10);
+    }
+    // The hit is only buffered, and replayed before the next insertion evicts
+    assert_eq!(lookup(1).await, 10);
+    assert_eq!(lookup(4).await, 40);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 4);
+
+    // 1 survived the eviction, 2 did not
+    assert_eq!(lookup(1).await, 10);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 4);
+    assert_eq!(lookup(2).await, 20);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_buffered_hits() {
+    let tasks: Vec<_> = (0..8u64)
+        .map(|t| {
+            tokio::spawn(async move {
+                for i in 0..200u64 {
+                    let x = (i * (t + 1)) % 24;
+                    assert_eq!(contended(x).await, x + 7);
+                }
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+}
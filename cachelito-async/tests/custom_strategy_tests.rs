@@ -0,0 +1,49 @@
+//! Integration tests for custom eviction strategies in async caches
+
+use cachelito_async::cache_async;
+use cachelito_core::{EvictionCandidates, EvictionStrategy, RemovalCause};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static PRICE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Evicts the cheapest price, and records what leaves the cache
+struct KeepExpensive {
+    removed: Mutex<Vec<(String, RemovalCause)>>,
+}
+
+impl EvictionStrategy<u64> for KeepExpensive {
+    fn on_remove(&self, key: &str, _value: &u64, cause: RemovalCause) {
+        self.removed.lock().unwrap().push((key.to_string(), cause));
+    }
+
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, u64>) -> Option<usize> {
+        candidates.lowest_score(|entry, _| entry.value as f64)
+    }
+}
+
+static KEEP_EXPENSIVE: KeepExpensive = KeepExpensive {
+    removed: Mutex::new(Vec::new()),
+};
+
+#[cache_async(limit = 2, policy = KEEP_EXPENSIVE)]
+async fn price(id: u64) -> u64 {
+    PRICE_CALLS.fetch_add(1, Ordering::SeqCst);
+    [50, 10, 30][id as usize % 3]
+}
+
+#[tokio::test]
+async fn test_async_strategy_chooses_the_victim() {
+    assert_eq!(price(0).await, 50);
+    assert_eq!(price(1).await, 10);
+    // Full: the cheapest price (10) makes room
+    assert_eq!(price(2).await, 30);
+    assert_eq!(
+        *KEEP_EXPENSIVE.removed.lock().unwrap(),
+        vec![("1".to_string(), RemovalCause::Capacity)]
+    );
+
+    // The oldest entry is still cached
+    assert_eq!(price(0).await, 50);
+    assert_eq!(PRICE_CALLS.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,53 @@
+use cachelito_async::cache_async;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::time::Duration;
+
+#[tokio::test]
+async fn test_async_gdsf_evicts_large_entries_first() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[cache_async(max_memory = "20KB", policy = "gdsf")]
+    async fn payload(len: usize) -> Vec<u8> {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        // Same compute cost for every entry
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        vec![0; len]
+    }
+
+    payload(100).await;
+    payload(15_000).await;
+    // Over 20KB: the 15KB entry has the lowest frequency × cost / size
+    payload(8_000).await;
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+
+    payload(100).await; // Cache hit
+    payload(8_000).await; // Cache hit
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+
+    payload(15_000).await; // Recomputed
+    assert_eq!(CALLS.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn test_async_gdsf_keeps_expensive_entries() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[cache_async(limit = 2, policy = "gdsf")]
+    async fn compute(n: u64) -> u64 {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        if n == 0 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        n * 2
+    }
+
+    assert_eq!(compute(0).await, 0); // Slow
+    assert_eq!(compute(1).await, 2); // Fast
+
+    // Full: one of the fast entries is evicted, although the slow one is older
+    assert_eq!(compute(2).await, 4);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+
+    assert_eq!(compute(0).await, 0); // Cache hit
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,59 @@
+//! Integration tests for `AsyncGlobalCache::get_many`
+
+use cachelito_core::{AsyncGlobalCache, CacheEntry, EvictionPolicy};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+
+#[cfg(feature = "stats")]
+use cachelito_core::CacheStats;
+
+static CACHE: Lazy<DashMap<String, CacheEntry<String>>> = Lazy::new(DashMap::new);
+static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+#[cfg(feature = "stats")]
+static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
+
+fn cache() -> AsyncGlobalCache<'static, String> {
+    AsyncGlobalCache::new(
+        &CACHE,
+        &ORDER,
+        None,
+        None,
+        EvictionPolicy::FIFO,
+        None,
+        None,
+        #[cfg(feature = "stats")]
+        &STATS,
+    )
+}
+
+#[tokio::test]
+async fn test_get_many_loads_missing_keys_once() {
+    let loaded = Mutex::new(Vec::new());
+    let load = |ids: Vec<u64>| async {
+        loaded.lock().push(ids.clone());
+        tokio::task::yield_now().await;
+        ids.into_iter()
+            .filter(|id| *id != 0)
+            .map(|id| (id, format!("user {}", id)))
+            .collect::<HashMap<_, _>>()
+    };
+
+    let users = cache().get_many(&[1, 2], load).await;
+    assert_eq!(
+        users,
+        vec![Some("user 1".to_string()), Some("user 2".to_string())]
+    );
+
+    let users = cache().get_many(&[0, 2, 3], load).await;
+    assert_eq!(
+        users,
+        vec![None, Some("user 2".to_string()), Some("user 3".to_string())]
+    );
+
+    // Nothing missing: the loader is not called
+    cache().get_many(&[1, 3], load).await;
+    assert_eq!(*loaded.lock(), vec![vec![1, 2], vec![0, 3]]);
+    assert!(cache().get("0").is_none());
+}
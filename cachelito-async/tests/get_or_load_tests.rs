@@ -0,0 +1,137 @@
+//! Integration tests for `AsyncGlobalCache::get_or_try_load`
+
+use cachelito_core::{AsyncGlobalCache, CacheEntry, EvictionPolicy, InFlightLoads};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[cfg(feature = "stats")]
+use cachelito_core::CacheStats;
+
+/// Storage backing one test cache
+struct Storage {
+    map: DashMap<String, CacheEntry<u64>>,
+    order: Mutex<VecDeque<String>>,
+    loads: InFlightLoads<u64>,
+    #[cfg(feature = "stats")]
+    stats: CacheStats,
+}
+
+impl Storage {
+    fn new() -> Self {
+        Self {
+            map: DashMap::new(),
+            order: Mutex::new(VecDeque::new()),
+            loads: InFlightLoads::new(),
+            #[cfg(feature = "stats")]
+            stats: CacheStats::new(),
+        }
+    }
+
+    fn cache(&self, limit: Option<usize>, ttl: Option<u64>) -> AsyncGlobalCache<'_, u64> {
+        AsyncGlobalCache::new(
+            &self.map,
+            &self.order,
+            limit,
+            None,
+            EvictionPolicy::FIFO,
+            ttl,
+            None,
+            #[cfg(feature = "stats")]
+            &self.stats,
+        )
+        .with_in_flight_loads(&self.loads)
+    }
+}
+
+static COALESCED: Lazy<Storage> = Lazy::new(Storage::new);
+static FAILING: Lazy<Storage> = Lazy::new(Storage::new);
+static LIMITED: Lazy<Storage> = Lazy::new(Storage::new);
+static EXPIRING: Lazy<Storage> = Lazy::new(Storage::new);
+
+#[tokio::test]
+async fn test_concurrent_loads_are_coalesced() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            tokio::spawn(async {
+                COALESCED
+                    .cache(None, None)
+                    .get_or_try_load("user:1", || async {
+                        CALLS.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok::<_, String>(42)
+                    })
+                    .await
+            })
+        })
+        .collect();
+
+    for task in tasks {
+        assert_eq!(task.await.unwrap(), Ok(42));
+    }
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    assert!(COALESCED.loads.is_empty());
+}
+
+#[tokio::test]
+async fn test_errors_are_not_cached() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    let cache = FAILING.cache(None, None);
+    for _ in 0..2 {
+        let result = cache
+            .get_or_try_load("user:1", || async {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+                Err::<u64, _>("unavailable".to_string())
+            })
+            .await;
+        assert_eq!(result, Err("unavailable".to_string()));
+    }
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+    assert!(cache.get("user:1").is_none());
+
+    let loaded = cache
+        .get_or_try_load("user:1", || async { Ok::<_, String>(7) })
+        .await;
+    assert_eq!(loaded, Ok(7));
+    assert_eq!(cache.get("user:1"), Some(7));
+}
+
+#[tokio::test]
+async fn test_loaded_values_respect_limit() {
+    let cache = LIMITED.cache(Some(2), None);
+    for id in 0..3u64 {
+        let value = cache
+            .get_or_try_load(&format!("user:{}", id), || async move { Ok::<_, ()>(id) })
+            .await;
+        assert_eq!(value, Ok(id));
+    }
+
+    assert_eq!(LIMITED.map.len(), 2);
+    assert!(cache.get("user:0").is_none());
+    assert_eq!(cache.get("user:2"), Some(2));
+}
+
+#[tokio::test]
+async fn test_loaded_values_respect_ttl() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    let cache = EXPIRING.cache(None, Some(1));
+    let load = || async {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        Ok::<_, ()>(5)
+    };
+
+    assert_eq!(cache.get_or_try_load("k", load).await, Ok(5));
+    assert_eq!(cache.get_or_try_load("k", load).await, Ok(5));
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert_eq!(cache.get_or_try_load("k", load).await, Ok(5));
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,37 @@
+//! Integration tests for the running memory total of async `max_memory` caches
+
+use cachelito_async::cache_async;
+use cachelito_core::MemoryEstimator;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ESTIMATES: AtomicUsize = AtomicUsize::new(0);
+
+/// A value of 100 bytes counting how many times it is measured
+#[derive(Clone)]
+struct Blob(u32);
+
+impl MemoryEstimator for Blob {
+    fn estimate_memory(&self) -> usize {
+        ESTIMATES.fetch_add(1, Ordering::SeqCst);
+        100
+    }
+}
+
+#[cache_async(max_memory = 1000, policy = "lru")]
+async fn blob(id: u32) -> Blob {
+    Blob(id)
+}
+
+#[tokio::test]
+async fn test_values_are_measured_once() {
+    for id in 0..200 {
+        assert_eq!(blob(id).await.0, id);
+    }
+
+    // Filling and evicting never measures the cached values again
+    assert_eq!(ESTIMATES.load(Ordering::SeqCst), 200);
+    for id in 190..200 {
+        assert_eq!(blob(id).await.0, id);
+    }
+    assert_eq!(ESTIMATES.load(Ordering::SeqCst), 200);
+}
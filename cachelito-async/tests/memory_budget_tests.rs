@@ -0,0 +1,40 @@
+//! Integration tests for the global memory budget with async `max_memory` caches
+
+use cachelito_async::cache_async;
+use cachelito_core::{
+    clear_global_memory_budget, global_memory_usage, set_global_memory_budget, MemoryEstimator,
+};
+
+/// A value of 100 bytes
+#[derive(Clone)]
+struct Blob(u32);
+
+impl MemoryEstimator for Blob {
+    fn estimate_memory(&self) -> usize {
+        100
+    }
+}
+
+#[cache_async(max_memory = 10000, policy = "lru")]
+async fn first(id: u32) -> Blob {
+    Blob(id)
+}
+
+#[cache_async(max_memory = 10000, policy = "lru")]
+async fn second(id: u32) -> Blob {
+    Blob(id)
+}
+
+#[tokio::test]
+async fn test_budget_bounds_all_async_caches() {
+    set_global_memory_budget("1500");
+
+    for id in 0..20 {
+        assert_eq!(first(id).await.0, id);
+        assert_eq!(second(id).await.0, id);
+    }
+    let usage = global_memory_usage();
+    assert!(usage > 0 && usage <= 1500, "usage {}", usage);
+
+    clear_global_memory_budget();
+}
@@ -4,8 +4,6 @@
 /// particularly when values exceed the memory limit.
 use cachelito_async::cache_async;
 use cachelito_core::MemoryEstimator;
-use tokio;
-
 
 /// A type that allows us to control its memory size
 #[derive(Debug, Clone)]
@@ -0,0 +1,33 @@
+//! Integration tests for shrinking async caches under memory pressure
+
+use cachelito_async::cache_async;
+use cachelito_core::shrink_all_caches;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cache_async(limit = 100, policy = "lru")]
+async fn recent(x: u64) -> u64 {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    x
+}
+
+#[tokio::test]
+async fn test_shrink_evicts_least_recently_used() {
+    for x in 0..8 {
+        recent(x).await;
+    }
+    for x in 0..2 {
+        recent(x).await;
+    }
+
+    assert_eq!(shrink_all_caches(0.5), 4);
+
+    // Entries 0 and 1 were used last, and survive
+    let calls = CALLS.load(Ordering::SeqCst);
+    recent(0).await;
+    recent(1).await;
+    assert_eq!(CALLS.load(Ordering::SeqCst), calls);
+    recent(2).await;
+    assert_eq!(CALLS.load(Ordering::SeqCst), calls + 1);
+}
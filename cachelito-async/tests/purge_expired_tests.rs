@@ -0,0 +1,65 @@
+//! Integration tests for purging expired entries of async caches
+
+use cachelito_async::cache_async;
+use cachelito_core::{purge_expired, ExpirationSweeper};
+use std::time::Duration;
+
+#[cache_async(ttl = 1)]
+async fn manual(x: u64) -> u64 {
+    x
+}
+
+#[cache_async(ttl = 1, limit = 10, policy = "lru")]
+async fn swept(x: u64) -> u64 {
+    x
+}
+
+#[tokio::test]
+async fn test_purge_expired_by_name() {
+    for x in 0..4 {
+        manual(x).await;
+    }
+    assert_eq!(purge_expired("manual"), Some(0));
+
+    tokio::time::sleep(Duration::from_millis(2100)).await;
+    manual(100).await;
+    assert_eq!(purge_expired("manual"), Some(4));
+    assert_eq!(purge_expired("manual"), Some(0));
+}
+
+#[cache_async(ttl = 1)]
+async fn many(x: u64) -> u64 {
+    x
+}
+
+#[tokio::test]
+async fn test_purge_many_expired_entries() {
+    for x in 0..300 {
+        many(x).await;
+    }
+    tokio::time::sleep(Duration::from_millis(2100)).await;
+    for x in 300..310 {
+        many(x).await;
+    }
+
+    // The fresh entries are not due yet
+    assert_eq!(purge_expired("many"), Some(300));
+    assert_eq!(purge_expired("many"), Some(0));
+}
+
+#[tokio::test]
+async fn test_sweeper_task_purges_expired_entries() {
+    let sweeper =
+        ExpirationSweeper::spawn(Duration::from_millis(100), tokio::time::sleep, |task| {
+            tokio::spawn(task);
+        });
+    for x in 0..5 {
+        swept(x).await;
+    }
+
+    // Once swept, purging by name finds nothing left
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    assert_eq!(purge_expired("swept"), Some(0));
+    assert!(sweeper.is_running());
+    sweeper.stop();
+}
@@ -0,0 +1,86 @@
+//! Integration tests for async removal listeners (`on_evict`)
+
+use cachelito_async::cache_async;
+use cachelito_core::{invalidate_with, RemovalCause};
+use std::sync::Mutex;
+use std::time::Duration;
+
+type Removed = Mutex<Vec<(String, u64, RemovalCause)>>;
+
+static CAPACITY_REMOVED: Removed = Mutex::new(Vec::new());
+static EXPIRED_REMOVED: Removed = Mutex::new(Vec::new());
+static INVALIDATED_REMOVED: Removed = Mutex::new(Vec::new());
+
+fn on_capacity(key: &str, value: &u64, cause: RemovalCause) {
+    CAPACITY_REMOVED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+fn on_expired(key: &str, value: &u64, cause: RemovalCause) {
+    EXPIRED_REMOVED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+fn on_invalidated(key: &str, value: &u64, cause: RemovalCause) {
+    INVALIDATED_REMOVED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+#[cache_async(limit = 2, policy = "fifo", on_evict = on_capacity)]
+async fn capacity_square(x: u64) -> u64 {
+    x * x
+}
+
+#[cache_async(ttl = 1, on_evict = on_expired)]
+async fn expiring_double(x: u64) -> u64 {
+    x * 2
+}
+
+#[cache_async(name = "async_removal_invalidated", limit = 10, on_evict = on_invalidated)]
+async fn invalidated_plus_one(x: u64) -> u64 {
+    x + 1
+}
+
+#[tokio::test]
+async fn test_async_capacity_eviction_notifies_listener() {
+    capacity_square(1).await;
+    capacity_square(2).await;
+    assert!(CAPACITY_REMOVED.lock().unwrap().is_empty());
+
+    capacity_square(3).await;
+
+    let removed = CAPACITY_REMOVED.lock().unwrap();
+    assert_eq!(*removed, vec![("1".to_string(), 1, RemovalCause::Capacity)]);
+}
+
+#[tokio::test]
+async fn test_async_expiration_notifies_listener() {
+    expiring_double(5).await;
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    expiring_double(5).await;
+
+    let removed = EXPIRED_REMOVED.lock().unwrap();
+    assert_eq!(*removed, vec![("5".to_string(), 10, RemovalCause::Expired)]);
+}
+
+#[tokio::test]
+async fn test_async_invalidation_notifies_listener() {
+    invalidated_plus_one(1).await;
+    invalidated_plus_one(2).await;
+
+    assert!(invalidate_with("async_removal_invalidated", |key: &str| {
+        key == "2"
+    }));
+
+    let removed = INVALIDATED_REMOVED.lock().unwrap();
+    assert_eq!(
+        *removed,
+        vec![("2".to_string(), 3, RemovalCause::Invalidated)]
+    );
+}
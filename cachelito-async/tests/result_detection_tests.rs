@@ -0,0 +1,69 @@
+//! Integration tests for async Result detection (`result = true|false`, `CacheableOutcome`)
+
+use cachelito_async::cache_async;
+use cachelito_core::CacheableOutcome;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALIAS_CALLS: AtomicUsize = AtomicUsize::new(0);
+static PATH_CALLS: AtomicUsize = AtomicUsize::new(0);
+static STATUS_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+mod app {
+    pub type Result<T> = std::result::Result<T, String>;
+}
+
+type Fallible<T> = Result<T, String>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Status {
+    Ready,
+    Pending,
+}
+
+impl CacheableOutcome for Status {
+    fn is_cacheable(&self) -> bool {
+        *self == Status::Ready
+    }
+}
+
+#[cache_async]
+async fn path_alias_fetch(id: u32) -> app::Result<u32> {
+    PATH_CALLS.fetch_add(1, Ordering::SeqCst);
+    Err(format!("no item {}", id))
+}
+
+#[cache_async(result = true)]
+async fn alias_fetch(id: u32) -> Fallible<u32> {
+    ALIAS_CALLS.fetch_add(1, Ordering::SeqCst);
+    Err(format!("no item {}", id))
+}
+
+#[cache_async(result = true)]
+async fn status(id: u32) -> Status {
+    STATUS_CALLS.fetch_add(1, Ordering::SeqCst);
+    if id == 0 {
+        Status::Pending
+    } else {
+        Status::Ready
+    }
+}
+
+#[tokio::test]
+async fn test_async_result_alias_not_cached() {
+    assert!(path_alias_fetch(1).await.is_err());
+    assert!(path_alias_fetch(1).await.is_err());
+    assert_eq!(PATH_CALLS.load(Ordering::SeqCst), 2);
+
+    assert!(alias_fetch(1).await.is_err());
+    assert!(alias_fetch(1).await.is_err());
+    assert_eq!(ALIAS_CALLS.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_async_custom_outcome() {
+    assert_eq!(status(0).await, Status::Pending);
+    assert_eq!(status(0).await, Status::Pending);
+    assert_eq!(status(1).await, Status::Ready);
+    assert_eq!(status(1).await, Status::Ready);
+    assert_eq!(STATUS_CALLS.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,72 @@
+//! Integration tests for the S3-FIFO eviction policy in async caches
+
+use cachelito_async::cache_async;
+use cachelito_core::RemovalCause;
+use std::sync::Mutex;
+
+type Evicted = Mutex<Vec<u64>>;
+
+static PROMOTED_EVICTED: Evicted = Mutex::new(Vec::new());
+static GHOST_EVICTED: Evicted = Mutex::new(Vec::new());
+
+fn record(evicted: &Evicted, value: u64, cause: RemovalCause) {
+    assert_eq!(cause, RemovalCause::Capacity);
+    evicted.lock().unwrap().push(value);
+}
+
+fn on_promoted(_key: &str, value: &u64, cause: RemovalCause) {
+    record(&PROMOTED_EVICTED, *value, cause);
+}
+
+fn on_ghost(_key: &str, value: &u64, cause: RemovalCause) {
+    record(&GHOST_EVICTED, *value, cause);
+}
+
+#[cache_async(limit = 4, policy = "s3fifo", on_evict = on_promoted)]
+async fn promoted(n: u64) -> u64 {
+    n
+}
+
+#[cache_async(limit = 4, policy = "s3fifo", on_evict = on_ghost)]
+async fn ghost(n: u64) -> u64 {
+    n
+}
+
+/// Entries leave the small queue for the main queue once accessed more than once
+#[tokio::test]
+async fn test_async_s3fifo_promotes_entries_accessed_more_than_once() {
+    for n in 1..=4 {
+        promoted(n).await;
+    }
+    promoted(1).await;
+    promoted(1).await;
+    promoted(2).await;
+
+    // 1 moves to the main queue; 2 was accessed only once and is evicted
+    promoted(5).await;
+    assert_eq!(*PROMOTED_EVICTED.lock().unwrap(), [2]);
+
+    // The small queue keeps evicting new entries, 1 stays in the main queue
+    for n in 6..=9 {
+        promoted(n).await;
+    }
+    assert_eq!(*PROMOTED_EVICTED.lock().unwrap(), [2, 3, 4, 5, 6]);
+}
+
+/// Keys evicted from the small queue are remembered: inserted again, they enter the
+/// main queue directly
+#[tokio::test]
+async fn test_async_s3fifo_ghost_readmits_to_main_queue() {
+    for n in 1..=5 {
+        ghost(n).await;
+    }
+    assert_eq!(*GHOST_EVICTED.lock().unwrap(), [1]);
+
+    // 1 is computed again: the ghost queue admits it to the main queue, so the small
+    // queue evicts the entries inserted after it first
+    ghost(1).await;
+    for n in 6..=9 {
+        ghost(n).await;
+    }
+    assert_eq!(*GHOST_EVICTED.lock().unwrap(), [1, 2, 3, 4, 5, 6]);
+}
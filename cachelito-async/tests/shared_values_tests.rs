@@ -0,0 +1,41 @@
+//! Integration tests for async caches storing values in an `Arc` (`arc = true`)
+
+use cachelito_async::cache_async;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+static REPORT_CALLS: AtomicUsize = AtomicUsize::new(0);
+static SESSION_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Not `Clone`: only cacheable behind an `Arc`
+struct Session {
+    user: u64,
+}
+
+#[cache_async(limit = 10, arc = true)]
+async fn report(id: u64) -> Vec<u64> {
+    REPORT_CALLS.fetch_add(1, Ordering::SeqCst);
+    vec![id; 1000]
+}
+
+#[cache_async(limit = 10, arc = true)]
+async fn session(user: u64) -> Arc<Session> {
+    SESSION_CALLS.fetch_add(1, Ordering::SeqCst);
+    Arc::new(Session { user })
+}
+
+#[tokio::test]
+async fn test_shared_values_are_returned_by_value() {
+    assert_eq!(report(4).await, vec![4; 1000]);
+    assert_eq!(report(4).await, vec![4; 1000]);
+    assert_eq!(REPORT_CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_arc_return_types_are_not_wrapped_twice() {
+    let first = session(9).await;
+    let second = session(9).await;
+    assert_eq!(first.user, 9);
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(SESSION_CALLS.load(Ordering::SeqCst), 1);
+}
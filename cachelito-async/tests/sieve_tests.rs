@@ -0,0 +1,76 @@
+//! Integration tests for the SIEVE eviction policy in async caches
+
+use cachelito_async::cache_async;
+use cachelito_core::RemovalCause;
+use std::sync::Mutex;
+
+type Evicted = Mutex<Vec<u64>>;
+
+static RESUMED_EVICTED: Evicted = Mutex::new(Vec::new());
+static PASS_EVICTED: Evicted = Mutex::new(Vec::new());
+
+fn record(evicted: &Evicted, value: u64, cause: RemovalCause) {
+    assert_eq!(cause, RemovalCause::Capacity);
+    evicted.lock().unwrap().push(value);
+}
+
+fn on_resumed(_key: &str, value: &u64, cause: RemovalCause) {
+    record(&RESUMED_EVICTED, *value, cause);
+}
+
+fn on_pass(_key: &str, value: &u64, cause: RemovalCause) {
+    record(&PASS_EVICTED, *value, cause);
+}
+
+#[cache_async(limit = 4, policy = "sieve", on_evict = on_resumed)]
+async fn resumed(n: u64) -> u64 {
+    n
+}
+
+#[cache_async(limit = 3, policy = "sieve", on_evict = on_pass)]
+async fn pass(n: u64) -> u64 {
+    n
+}
+
+/// The hand stays where it evicted: the next eviction resumes there instead of going
+/// back to the entries it already passed
+#[tokio::test]
+async fn test_async_sieve_hand_resumes_where_it_stopped() {
+    for n in 1..=4 {
+        resumed(n).await;
+    }
+    resumed(1).await;
+    resumed(2).await;
+
+    // The hand passes 1 and 2, clearing their visited bits, and evicts 3. It stays
+    // between 2 and 4, so 1 and 2 outlive every new entry accessed only once
+    for n in 5..=8 {
+        resumed(n).await;
+    }
+    assert_eq!(*RESUMED_EVICTED.lock().unwrap(), [3, 4, 5, 6]);
+}
+
+/// Visited entries survive one pass of the hand, and are evicted by the next one unless
+/// they are visited again
+#[tokio::test]
+async fn test_async_sieve_visited_entries_survive_one_pass() {
+    for n in 1..=3 {
+        pass(n).await;
+    }
+    pass(1).await;
+    pass(2).await;
+
+    // First pass: 1 and 2 survive, 3 is evicted
+    pass(4).await;
+    assert_eq!(*PASS_EVICTED.lock().unwrap(), [3]);
+
+    // The hand passes 4 (visited), then starts over: 1 was not visited since
+    pass(4).await;
+    pass(5).await;
+    assert_eq!(*PASS_EVICTED.lock().unwrap(), [3, 1]);
+
+    // 2 is visited again, and survives this pass too
+    pass(2).await;
+    pass(6).await;
+    assert_eq!(*PASS_EVICTED.lock().unwrap(), [3, 1, 4]);
+}
@@ -35,17 +35,17 @@ static MEM_MAP: Lazy<RwLock<HashMap<String, CacheEntry<String>>>> =
 static MEM_ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
 #[cfg(feature = "stats")]
-static FIFO_STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+static FIFO_STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 #[cfg(feature = "stats")]
-static LRU_STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+static LRU_STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 #[cfg(feature = "stats")]
-static LFU_STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+static LFU_STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 #[cfg(feature = "stats")]
-static ARC_STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+static ARC_STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 #[cfg(feature = "stats")]
-static RANDOM_STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+static RANDOM_STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 #[cfg(feature = "stats")]
-static MEM_STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+static MEM_STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
 // Helper macro to create GlobalCache with or without stats (updated signature: + max_memory + frequency_weight)
 macro_rules! new_fifo_cache {
@@ -300,7 +300,7 @@ fn bench_concurrent_reads(c: &mut Criterion) {
         // Pre-populate cache
         let cache = new_fifo_cache!(Some(100));
         for i in 0..100 {
-            cache.insert(&format!("key{}", i), i as i32);
+            cache.insert(&format!("key{}", i), i);
         }
 
         group.bench_with_input(
@@ -347,7 +347,7 @@ fn bench_concurrent_mixed(c: &mut Criterion) {
                                     if i % 2 == 0 {
                                         cache.insert(
                                             &format!("key{}", thread_id * 50 + i),
-                                            std::hint::black_box(i as i32),
+                                            std::hint::black_box(i),
                                         );
                                     } else {
                                         std::hint::black_box(
@@ -377,7 +377,7 @@ fn bench_eviction(c: &mut Criterion) {
         b.iter(|| {
             let cache = new_fifo_cache!(Some(50));
             for i in 0..100 {
-                cache.insert(&format!("key{}", i), std::hint::black_box(i as i32));
+                cache.insert(&format!("key{}", i), std::hint::black_box(i));
             }
         });
     });
@@ -385,7 +385,7 @@ fn bench_eviction(c: &mut Criterion) {
         b.iter(|| {
             let cache = new_lru_cache!(Some(50));
             for i in 0..100 {
-                cache.insert(&format!("key{}", i), std::hint::black_box(i as i32));
+                cache.insert(&format!("key{}", i), std::hint::black_box(i));
             }
         });
     });
@@ -393,7 +393,7 @@ fn bench_eviction(c: &mut Criterion) {
         b.iter(|| {
             let cache = new_lfu_cache!(Some(50));
             for i in 0..100 {
-                cache.insert(&format!("key{}", i), std::hint::black_box(i as i32));
+                cache.insert(&format!("key{}", i), std::hint::black_box(i));
             }
         });
     });
@@ -401,7 +401,7 @@ fn bench_eviction(c: &mut Criterion) {
         b.iter(|| {
             let cache = new_arc_cache!(Some(50));
             for i in 0..100 {
-                cache.insert(&format!("key{}", i), std::hint::black_box(i as i32));
+                cache.insert(&format!("key{}", i), std::hint::black_box(i));
             }
         });
     });
@@ -414,7 +414,7 @@ fn bench_memory_eviction(c: &mut Criterion) {
     group.bench_function("LRU_memory_eviction", |b| {
         b.iter(|| {
             reset_mem();
-            let cache = new_mem_cache!(None, Some(1 * 1024 * 1024)); // 1MB
+            let cache = new_mem_cache!(None, Some(1024 * 1024)); // 1MB
             for i in 0..15 {
                 // ~ exceed memory
                 let val = "X".repeat(100_000); // ~100KB
@@ -431,7 +431,7 @@ fn bench_rwlock_concurrent_reads(c: &mut Criterion) {
     // Pre-populate cache
     let cache = new_fifo_cache!(Some(1000));
     for i in 0..1000 {
-        cache.insert(&format!("key{}", i), i as i32);
+        cache.insert(&format!("key{}", i), i);
     }
 
     for num_threads in [2, 4, 8, 16].iter() {
@@ -482,7 +482,7 @@ fn bench_read_heavy_workload(c: &mut Criterion) {
                                         // 10% writes
                                         cache.insert(
                                             &format!("key{}", thread_id * 100 + i),
-                                            std::hint::black_box(i as i32),
+                                            std::hint::black_box(i),
                                         );
                                     } else {
                                         // 90% reads
@@ -516,7 +516,7 @@ fn bench_random_eviction(c: &mut Criterion) {
             reset_random();
             let cache = new_random_cache!(Some(cache_limit));
             for i in 0..num_insertions {
-                cache.insert(&format!("key{}", i), std::hint::black_box(i as i32));
+                cache.insert(&format!("key{}", i), std::hint::black_box(i));
             }
         });
     });
@@ -526,7 +526,7 @@ fn bench_random_eviction(c: &mut Criterion) {
             reset_fifo();
             let cache = new_fifo_cache!(Some(cache_limit));
             for i in 0..num_insertions {
-                cache.insert(&format!("key{}", i), std::hint::black_box(i as i32));
+                cache.insert(&format!("key{}", i), std::hint::black_box(i));
             }
         });
     });
@@ -536,7 +536,7 @@ fn bench_random_eviction(c: &mut Criterion) {
             reset_lru();
             let cache = new_lru_cache!(Some(cache_limit));
             for i in 0..num_insertions {
-                cache.insert(&format!("key{}", i), std::hint::black_box(i as i32));
+                cache.insert(&format!("key{}", i), std::hint::black_box(i));
             }
         });
     });
@@ -546,7 +546,7 @@ fn bench_random_eviction(c: &mut Criterion) {
             reset_lfu();
             let cache = new_lfu_cache!(Some(cache_limit));
             for i in 0..num_insertions {
-                cache.insert(&format!("key{}", i), std::hint::black_box(i as i32));
+                cache.insert(&format!("key{}", i), std::hint::black_box(i));
             }
         });
     });
@@ -556,7 +556,7 @@ fn bench_random_eviction(c: &mut Criterion) {
             reset_arc();
             let cache = new_arc_cache!(Some(cache_limit));
             for i in 0..num_insertions {
-                cache.insert(&format!("key{}", i), std::hint::black_box(i as i32));
+                cache.insert(&format!("key{}", i), std::hint::black_box(i));
             }
         });
     });
@@ -0,0 +1,135 @@
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of stripes: threads record into different stripes to avoid contending
+const STRIPES: usize = 16;
+
+/// Number of accesses a stripe holds before it asks to be replayed
+const STRIPE_CAPACITY: usize = 32;
+
+/// Lossy buffer of cache hits, replayed into the eviction policy in batches.
+///
+/// Without it, every hit with the LRU, LFU, ARC or TLRU policy locks the eviction
+/// order (and, for frequency-based policies, the entry map) to record the access.
+/// With a buffer attached (see
+/// [`GlobalCache::with_access_buffer`](crate::GlobalCache::with_access_buffer) and
+/// [`AsyncGlobalCache::with_access_buffer`](crate::AsyncGlobalCache::with_access_buffer)),
+/// hits only append their key to one of several small stripes, and the recorded
+/// accesses are applied under the cache locks once a stripe fills up or before the
+/// next insertion evicts anything.
+///
+/// The buffer is lossy: when a stripe is busy or full, the access is dropped instead
+/// of waiting. Recency and frequency are therefore sampled, which keeps popular keys
+/// near the end of the eviction order without paying for every single hit.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::AccessBuffer;
+/// use once_cell::sync::Lazy;
+///
+/// static ACCESSES: Lazy<AccessBuffer> = Lazy::new(AccessBuffer::new);
+/// assert!(ACCESSES.is_empty());
+/// ```
+pub struct AccessBuffer {
+    stripes: Box<[Mutex<Vec<String>>]>,
+}
+
+impl AccessBuffer {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        let stripes = (0..STRIPES).map(|_| Mutex::new(Vec::new())).collect();
+        Self { stripes }
+    }
+
+    /// Returns the number of recorded accesses not replayed yet.
+    pub fn len(&self) -> usize {
+        self.stripes.iter().map(|stripe| stripe.lock().len()).sum()
+    }
+
+    /// Returns `true` if there is no access left to replay.
+    pub fn is_empty(&self) -> bool {
+        self.stripes.iter().all(|stripe| stripe.lock().is_empty())
+    }
+
+    /// Records a hit on `key`, dropping it if the stripe of this thread is busy or full.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the stripe is full and the buffer should be replayed.
+    pub(crate) fn record(&self, key: &str) -> bool {
+        let Some(mut stripe) = self.stripes[stripe_index()].try_lock() else {
+            return false;
+        };
+        if stripe.len() < STRIPE_CAPACITY {
+            stripe.push(key.to_string());
+        }
+        stripe.len() >= STRIPE_CAPACITY
+    }
+
+    /// Takes every recorded access, stripe by stripe.
+    pub(crate) fn drain(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        for stripe in self.stripes.iter() {
+            keys.append(&mut stripe.lock());
+        }
+        keys
+    }
+}
+
+impl Default for AccessBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the stripe of the current thread, assigned round-robin on first use.
+fn stripe_index() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    thread_local! {
+        static STRIPE: usize = NEXT.fetch_add(1, Ordering::Relaxed) % STRIPES;
+    }
+    STRIPE.with(|stripe| *stripe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_until_full() {
+        let buffer = AccessBuffer::new();
+        for i in 0..STRIPE_CAPACITY - 1 {
+            assert!(!buffer.record(&i.to_string()));
+        }
+        assert!(buffer.record("last"));
+        // A full stripe drops further accesses
+        assert!(buffer.record("dropped"));
+        assert_eq!(buffer.len(), STRIPE_CAPACITY);
+
+        let keys = buffer.drain();
+        assert_eq!(keys.len(), STRIPE_CAPACITY);
+        assert_eq!(keys.last().map(String::as_str), Some("last"));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_accesses_from_several_threads() {
+        let buffer = AccessBuffer::new();
+        std::thread::scope(|scope| {
+            for t in 0..4 {
+                let buffer = &buffer;
+                // One thread at a time, so that no access is dropped
+                scope
+                    .spawn(move || {
+                        for _ in 0..10 {
+                            buffer.record(&t.to_string());
+                        }
+                    })
+                    .join()
+                    .unwrap();
+            }
+        });
+        assert_eq!(buffer.drain().len(), 40);
+    }
+}
@@ -1,10 +1,20 @@
+use crate::batch::Batch;
+use crate::in_flight::Join;
+use crate::policy_engine::{Lookup, PolicyEngine};
+use crate::removal::PendingRemovals;
+use crate::utils::shrink_count;
 #[cfg(feature = "stats")]
 use crate::CacheStats;
-use crate::EvictionPolicy;
+use crate::{
+    AccessBuffer, CacheEntry, CacheableKey, EvictionPolicy, EvictionState, EvictionStrategy,
+    InFlightLoads, MemoryBudget, MemoryUsage, RemovalCause, RemovalListener, TimerWheel,
+};
 use dashmap::DashMap;
 use parking_lot::lock_api::MutexGuard;
 use parking_lot::{Mutex, RawMutex};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::hash::Hash;
 
 /// A thread-safe async global cache with configurable eviction policies and TTL support.
 ///
@@ -34,13 +44,18 @@ use std::collections::VecDeque;
 /// - **Statistics**: Optional cache hit/miss tracking (with `stats` feature)
 /// - **Frequency tracking**: For LFU, ARC, and TLRU policies
 /// - **Memory estimation**: Support for memory-based eviction (requires `MemoryEstimator`)
+/// - **Removal listener**: Optional callback notified with the [`RemovalCause`] of every removal
+/// - **Memory accounting**: Optional running total of entry sizes in a [`MemoryUsage`]
 ///
 /// # Cache Entry Structure
 ///
-/// Each cache entry is stored as a tuple: `(value, timestamp, frequency)`
+/// Cache entries are stored as `CacheEntry<R>`, like in the synchronous caches:
 /// - `value`: The cached value of type R
-/// - `timestamp`: Unix timestamp when the entry was created (for TTL and TLRU age factor)
+/// - `inserted_at`: Monotonic `Instant` when the entry was created (for TTL and TLRU age factor)
 /// - `frequency`: Access counter for LFU, ARC, and TLRU policies
+/// - `size`: Estimated memory of the value, measured by `insert_with_memory` (0 otherwise)
+///
+/// Being monotonic, insertion times are not affected by adjustments of the system clock.
 ///
 /// # Eviction Behavior
 ///
@@ -142,8 +157,7 @@ use std::collections::VecDeque;
 /// ```
 pub struct AsyncGlobalCache<'a, R: Clone> {
     /// The underlying DashMap storing cache entries
-    /// Structure: key -> (value, timestamp, frequency)
-    cache: &'a DashMap<String, (R, u64, u64)>,
+    cache: &'a DashMap<String, CacheEntry<R>>,
 
     /// Order queue for FIFO/LRU eviction tracking
     order: &'a Mutex<VecDeque<String>>,
@@ -166,6 +180,27 @@ pub struct AsyncGlobalCache<'a, R: Clone> {
     /// Cache statistics (when stats feature is enabled)
     #[cfg(feature = "stats")]
     stats: &'a CacheStats,
+
+    /// Listener notified whenever an entry leaves the cache
+    on_evict: Option<&'a RemovalListener<R>>,
+
+    /// Custom eviction strategy, taking precedence over `policy`
+    strategy: Option<&'a dyn EvictionStrategy<R>>,
+
+    /// Loads in progress, shared by concurrent `get_or_try_load` calls
+    loads: Option<&'a InFlightLoads<R>>,
+
+    /// Hits waiting to be applied to the order queue
+    accesses: Option<&'a AccessBuffer>,
+
+    /// Timer wheel tracking when entries expire
+    timers: Option<&'a TimerWheel>,
+
+    /// Running total of the entry sizes
+    memory: Option<&'a MemoryUsage>,
+
+    /// State of the built-in eviction strategies
+    eviction_state: Option<&'a EvictionState>,
 }
 
 impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
@@ -218,7 +253,7 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
     /// ```
     #[cfg(not(feature = "stats"))]
     pub fn new(
-        cache: &'a DashMap<String, (R, u64, u64)>,
+        cache: &'a DashMap<String, CacheEntry<R>>,
         order: &'a Mutex<VecDeque<String>>,
         limit: Option<usize>,
         max_memory: Option<usize>,
@@ -234,6 +269,13 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
             policy,
             ttl,
             frequency_weight,
+            on_evict: None,
+            strategy: None,
+            loads: None,
+            accesses: None,
+            timers: None,
+            memory: None,
+            eviction_state: None,
         }
     }
 
@@ -241,8 +283,9 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
     ///
     /// This version is available when the `stats` feature is enabled.
     #[cfg(feature = "stats")]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        cache: &'a DashMap<String, (R, u64, u64)>,
+        cache: &'a DashMap<String, CacheEntry<R>>,
         order: &'a Mutex<VecDeque<String>>,
         limit: Option<usize>,
         max_memory: Option<usize>,
@@ -260,7 +303,283 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
             ttl,
             frequency_weight,
             stats,
+            on_evict: None,
+            strategy: None,
+            loads: None,
+            accesses: None,
+            timers: None,
+            memory: None,
+            eviction_state: None,
+        }
+    }
+
+    /// Sets a listener that is called whenever an entry leaves the cache.
+    ///
+    /// The listener receives the key, the removed value and the [`RemovalCause`].
+    /// It is invoked after the order lock and the DashMap shard locks have been
+    /// released, so it may access the cache again.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn on_evict(key: &str, value: &User, cause: RemovalCause) {
+    ///     println!("{} left the cache: {:?}", key, cause);
+    /// }
+    ///
+    /// let async_cache = AsyncGlobalCache::new(/* ... */).with_removal_listener(&on_evict);
+    /// ```
+    pub fn with_removal_listener(mut self, listener: &'a RemovalListener<R>) -> Self {
+        self.on_evict = Some(listener);
+        self
+    }
+
+    /// Evicts entries with a custom [`EvictionStrategy`] instead of the policy of the cache.
+    ///
+    /// See [`GlobalCache::with_eviction_strategy`](crate::GlobalCache::with_eviction_strategy).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let async_cache = AsyncGlobalCache::new(/* ... */).with_eviction_strategy(&ByPriority);
+    /// ```
+    pub fn with_eviction_strategy(mut self, strategy: &'a dyn EvictionStrategy<R>) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Coalesces concurrent [`get_or_try_load`](Self::get_or_try_load) calls for the same key.
+    ///
+    /// Without it, every call missing the cache runs its own loader.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static LOADS: Lazy<InFlightLoads<User>> = Lazy::new(InFlightLoads::new);
+    ///
+    /// let async_cache = AsyncGlobalCache::new(/* ... */).with_in_flight_loads(&LOADS);
+    /// ```
+    pub fn with_in_flight_loads(mut self, loads: &'a InFlightLoads<R>) -> Self {
+        self.loads = Some(loads);
+        self
+    }
+
+    /// Records hits in `accesses` instead of moving them in the order queue one by one.
+    ///
+    /// With the LRU, ARC and TLRU policies, a hit normally locks the order queue to move
+    /// its key to the back. With a buffer, hits are recorded without waiting and
+    /// replayed in batches, when the buffer fills up and before insertions. Some hits may
+    /// be dropped, so recency becomes sampled (see [`AccessBuffer`]). Frequency counters
+    /// are still updated on every hit, under the DashMap shard lock.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static ACCESSES: Lazy<AccessBuffer> = Lazy::new(AccessBuffer::new);
+    ///
+    /// let async_cache = AsyncGlobalCache::new(/* ... */).with_access_buffer(&ACCESSES);
+    /// ```
+    pub fn with_access_buffer(mut self, accesses: &'a AccessBuffer) -> Self {
+        self.accesses = Some(accesses);
+        self
+    }
+
+    /// Tracks the expiration of entries in `timers`, so that
+    /// [`purge_expired`](Self::purge_expired) only visits the expired ones.
+    ///
+    /// See [`GlobalCache::with_timer_wheel`](crate::GlobalCache::with_timer_wheel).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static TIMERS: Lazy<TimerWheel> = Lazy::new(TimerWheel::new);
+    ///
+    /// let async_cache = AsyncGlobalCache::new(/* ... */).with_timer_wheel(&TIMERS);
+    /// ```
+    pub fn with_timer_wheel(mut self, timers: &'a TimerWheel) -> Self {
+        self.timers = Some(timers);
+        self
+    }
+
+    /// Keeps the total size of the entries in `memory`, so that enforcing `max_memory`
+    /// does not sum the sizes of every entry.
+    ///
+    /// See [`GlobalCache::with_memory_usage`](crate::GlobalCache::with_memory_usage).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static MEMORY: MemoryUsage = MemoryUsage::new();
+    ///
+    /// let async_cache = AsyncGlobalCache::new(/* ... */).with_memory_usage(&MEMORY);
+    /// ```
+    pub fn with_memory_usage(mut self, memory: &'a MemoryUsage) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Keeps the state of the built-in eviction strategies that need one in `state`.
+    ///
+    /// See [`GlobalCache::with_eviction_state`](crate::GlobalCache::with_eviction_state).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static STATE: Lazy<EvictionState> = Lazy::new(EvictionState::new);
+    ///
+    /// let async_cache = AsyncGlobalCache::new(/* ... */).with_eviction_state(&STATE);
+    /// ```
+    pub fn with_eviction_state(mut self, state: &'a EvictionState) -> Self {
+        self.eviction_state = Some(state);
+        self
+    }
+
+    /// Returns the engine applying the limits and eviction policy of this cache.
+    fn engine(&self) -> PolicyEngine<'a, R> {
+        PolicyEngine::new(self.policy, self.ttl, self.frequency_weight, self.strategy)
+            .with_limits(self.limit, self.max_memory)
+            .with_timer_wheel(self.timers)
+            .with_memory_usage(self.memory)
+            .with_eviction_state(self.eviction_state)
+    }
+
+    /// Collects removed entries for the removal listener and the eviction strategy.
+    fn pending_removals(&self) -> PendingRemovals<'a, R> {
+        PendingRemovals::new(self.on_evict).with_strategy(self.engine().removal_strategy())
+    }
+
+    /// Returns the estimated memory of the cached entries, in bytes.
+    ///
+    /// Only entries inserted with `insert_with_memory` are measured; caches without
+    /// `max_memory` report 0.
+    pub fn memory_usage(&self) -> usize {
+        self.engine().memory_in_use(&self.cache)
+    }
+
+    /// Moves the keys of the buffered hits to the back of the order queue.
+    fn replay_accesses(&self, order: &mut VecDeque<String>) {
+        let Some(accesses) = self.accesses else {
+            return;
+        };
+        for key in accesses.drain() {
+            // Entries removed since the hit are skipped
+            if self.cache.contains_key(&key) {
+                order.retain(|k| k != &key);
+                order.push_back(key);
+            }
+        }
+    }
+
+    /// Returns the cached value for `key`, or loads, caches and returns it.
+    ///
+    /// On a miss, `load` is called and its `Ok` value is inserted with the entry-count
+    /// limit, TTL and eviction policy of the cache. Errors are returned to the caller and
+    /// never cached. Use `get_or_try_load_with_memory` when `max_memory` is configured.
+    ///
+    /// With [`with_in_flight_loads`](Self::with_in_flight_loads), concurrent calls for a
+    /// key that is already loading wait for that load instead of running `load`. If it
+    /// fails (or is cancelled), one of them runs its own loader.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let user = async_cache
+    ///     .get_or_try_load("user:123", || async { fetch_user(123).await })
+    ///     .await?;
+    /// ```
+    pub async fn get_or_try_load<F, Fut, E>(&self, key: &str, load: F) -> Result<R, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+    {
+        self.try_load_with(key, load, Self::insert).await
+    }
+
+    /// Returns the values of many keys at once, loading the missing ones in a single call.
+    ///
+    /// Hits are served from the cache; `load` is awaited once with the keys that missed
+    /// (without duplicates), and only if there are any. The values it returns are inserted
+    /// with the limits, TTL and eviction policy of the cache. Use `get_many_with_memory`
+    /// when `max_memory` is configured.
+    ///
+    /// # Returns
+    ///
+    /// One value per requested key, in request order. Keys the loader returned no value
+    /// for are `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let users = async_cache
+    ///     .get_many(&[1, 2, 3], |missing| async move { db.fetch_users(&missing).await })
+    ///     .await;
+    /// ```
+    pub async fn get_many<K, F, Fut>(&self, keys: &[K], load: F) -> Vec<Option<R>>
+    where
+        K: CacheableKey + Clone + Eq + Hash,
+        F: FnOnce(Vec<K>) -> Fut,
+        Fut: Future<Output = HashMap<K, R>>,
+    {
+        self.load_many_with(keys, load, Self::insert).await
+    }
+
+    /// Shared implementation of the `get_many` variants, inserting with `insert`.
+    async fn load_many_with<K, F, Fut>(
+        &self,
+        keys: &[K],
+        load: F,
+        insert: fn(&Self, &str, R),
+    ) -> Vec<Option<R>>
+    where
+        K: CacheableKey + Clone + Eq + Hash,
+        F: FnOnce(Vec<K>) -> Fut,
+        Fut: Future<Output = HashMap<K, R>>,
+    {
+        let mut batch = Batch::lookup(keys, |key| self.get(key));
+        let missing = batch.take_missing();
+        let loaded = if missing.is_empty() {
+            HashMap::new()
+        } else {
+            load(missing).await
+        };
+        batch.fill(loaded, |key, value| insert(self, key, value))
+    }
+
+    /// Shared implementation of the `get_or_try_load` variants, inserting with `insert`.
+    async fn try_load_with<F, Fut, E>(
+        &self,
+        key: &str,
+        load: F,
+        insert: fn(&Self, &str, R),
+    ) -> Result<R, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+    {
+        let guard = loop {
+            if let Some(value) = self.get(key) {
+                return Ok(value);
+            }
+            let Some(loads) = self.loads else {
+                break None;
+            };
+            match loads.join(key) {
+                Join::Leader(guard) => break Some(guard),
+                Join::Waiter(wait) => {
+                    if let Some(value) = wait.await {
+                        return Ok(value);
+                    }
+                    // The load failed or was cancelled: try again, maybe as the loader
+                }
+            }
+        };
+
+        // Dropping the guard on error releases the waiting calls without a value
+        let value = load().await?;
+        insert(self, key, value.clone());
+        if let Some(guard) = guard {
+            guard.complete(Some(value.clone()));
         }
+        Ok(value)
     }
 
     /// Attempts to retrieve a value from the cache.
@@ -316,82 +635,57 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
     /// - **LRU, ARC, TLRU**: O(n) - requires finding and moving key in order queue
     /// - **LFU**: O(1) - only increments counter
     pub fn get(&self, key: &str) -> Option<R> {
-        // Check cache first
-        if let Some(mut entry_ref) = self.cache.get_mut(key) {
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-
-            // Check if expired
-            // Use saturating_sub to avoid underflow when system clock moves backwards
-            // Align comparison with sync variant: expire when age >= ttl
-            let is_expired = if let Some(ttl) = self.ttl {
-                let age = now.saturating_sub(entry_ref.1);
-                age >= ttl
-            } else {
-                false
-            };
-
-            if !is_expired {
-                let cached_value = entry_ref.0.clone();
-
-                // Update access patterns based on policy
-                match self.policy {
-                    EvictionPolicy::LFU => {
-                        // Increment frequency counter
-                        entry_ref.2 = entry_ref.2.saturating_add(1);
-                    }
-                    EvictionPolicy::ARC => {
-                        // Increment frequency counter for ARC
-                        entry_ref.2 = entry_ref.2.saturating_add(1);
-                        // LRU update happens after releasing the entry lock
-                    }
-                    EvictionPolicy::TLRU => {
-                        // Increment frequency counter for TLRU
-                        entry_ref.2 = entry_ref.2.saturating_add(1);
-                        // LRU update happens after releasing the entry lock
-                    }
-                    EvictionPolicy::LRU => {
-                        // LRU update happens after releasing the entry lock
-                    }
-                    EvictionPolicy::FIFO | EvictionPolicy::Random => {
-                        // No update needed
-                    }
+        let engine = self.engine();
+        let mut store = self.cache;
+        match engine.lookup(&store, key) {
+            Lookup::Hit(cached_value) => {
+                // Update access patterns based on policy; the order queue is updated
+                // after releasing the entry lock
+                engine.on_hit(key);
+                if engine.updates_on_access() {
+                    engine.record_access(&mut store, key);
                 }
 
-                drop(entry_ref);
-
                 // Record cache hit
                 #[cfg(feature = "stats")]
                 self.stats.record_hit();
 
                 // Update LRU order on cache hit (after releasing DashMap lock)
                 if self.limit.is_some()
-                    && (self.policy == EvictionPolicy::LRU
-                        || self.policy == EvictionPolicy::ARC
-                        || self.policy == EvictionPolicy::TLRU)
+                    && engine.promotes_on_access()
+                    && self.cache.contains_key(key)
                 {
-                    if self.cache.contains_key(key) {
-                        let mut order = self.order.lock();
-                        // Double-check after acquiring lock
-                        if self.cache.contains_key(key) {
-                            order.retain(|k| k != key);
-                            order.push_back(key.to_string());
+                    // Buffered hits are applied in batches, without waiting for the lock
+                    if let Some(accesses) = self.accesses {
+                        if accesses.record(key) {
+                            self.replay_accesses(&mut self.order.lock());
                         }
+                        return Some(cached_value);
+                    }
+
+                    let mut order = self.order.lock();
+                    // Double-check after acquiring lock
+                    if self.cache.contains_key(key) {
+                        order.retain(|k| k != key);
+                        order.push_back(key.to_string());
                     }
                 }
 
                 return Some(cached_value);
             }
-
-            // Expired - remove and continue
-            drop(entry_ref);
-            self.cache.remove(key);
-
-            // Also remove from order queue to prevent orphaned keys
-            let mut order = self.order.lock();
-            order.retain(|k| k != key);
+            Lookup::Expired => {
+                // Also removed from the order queue to prevent orphaned keys
+                let mut removed = self.pending_removals();
+                engine.remove(
+                    &mut store,
+                    &mut self.order.lock(),
+                    key,
+                    &mut removed,
+                    RemovalCause::Expired,
+                );
+                removed.notify();
+            }
+            Lookup::Miss => {}
         }
 
         // Record cache miss
@@ -404,8 +698,8 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
     /// Inserts a value into the cache.
     ///
     /// This method handles cache limit enforcement and eviction according to
-    /// the configured policy. If the cache is full, it evicts an entry before
-    /// inserting the new one.
+    /// the configured policy. If the cache is over its limit after inserting
+    /// the new entry, it evicts entries until it fits again.
     ///
     /// # Arguments
     ///
@@ -441,26 +735,29 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
     /// async_cache.insert("user:123", updated_user_data);
     /// ```
     pub fn insert(&self, key: &str, value: R) {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        let mut order = self.order.lock();
+        self.insert_entry(key, CacheEntry::new(value));
+    }
 
-        // Check if another task already inserted this key while we were computing
-        if self.is_already_key_inserted(key, &mut order) {
-            return;
-        }
+    /// Stores a prepared entry, then evicts entries until the limits hold again.
+    fn insert_entry(&self, key: &str, entry: CacheEntry<R>) {
+        let engine = self.engine();
+        let mut store = self.cache;
+        let mut removed = self.pending_removals();
+        {
+            let mut order = self.order.lock();
+            // Evictions must see the buffered hits
+            self.replay_accesses(&mut order);
 
-        // Handle entry-count limits
-        self.handle_entry_limit_eviction(&mut order);
+            // Check if another task already inserted this key while we were computing
+            if self.is_already_key_inserted(key, &mut order) {
+                return;
+            }
 
-        // Add the new entry to the order queue
-        order.push_back(key.to_string());
+            engine.insert(&mut store, &mut order, key, entry, &mut removed);
+        }
 
-        // Insert into cache with frequency initialized to 0
-        self.cache.insert(key.to_string(), (value, timestamp, 0));
+        // Notify the removal listener once the order lock is released
+        removed.notify();
     }
 
     /// Checks if a key is already present in the cache and updates its position in the eviction order
@@ -469,7 +766,7 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
     /// # Parameters
     /// - `key`: A reference to the key being checked as a `&str`.
     /// - `order`: A mutable reference to a locked `VecDeque<String>` wrapped in a `MutexGuard`.
-    ///    This represents the ordered list of keys, used to determine eviction order.
+    ///   This represents the ordered list of keys, used to determine eviction order.
     ///
     /// # Returns
     /// - `true` if the key is already present in the cache and was processed for eviction policy.
@@ -506,188 +803,135 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
         false
     }
 
-    /// Finds the key with minimum frequency for LFU eviction.
-    ///
-    /// # Parameters
+    /// Removes a single entry from the cache.
     ///
-    /// * `order` - The order queue to search
+    /// The removal listener (if any) is notified with [`RemovalCause::Explicit`].
     ///
     /// # Returns
     ///
-    /// * `Option<String>` - The key with minimum frequency, or None if not found
-    fn find_min_frequency_key(&self, order: &VecDeque<String>) -> Option<String> {
-        let mut min_freq_key: Option<String> = None;
-        let mut min_freq = u64::MAX;
-
-        for evict_key in order.iter() {
-            if let Some(entry) = self.cache.get(evict_key) {
-                if entry.2 < min_freq {
-                    min_freq = entry.2;
-                    min_freq_key = Some(evict_key.clone());
-                }
-            }
-        }
-
-        min_freq_key
+    /// `true` if the key was present in the cache.
+    pub fn remove(&self, key: &str) -> bool {
+        let mut store = self.cache;
+        let mut removed = self.pending_removals();
+        let found = self.engine().remove(
+            &mut store,
+            &mut self.order.lock(),
+            key,
+            &mut removed,
+            RemovalCause::Explicit,
+        );
+        removed.notify();
+        found
     }
 
-    /// Finds the key to evict using ARC (Adaptive Replacement Cache) policy.
-    ///
-    /// ARC uses a hybrid score combining frequency and recency.
-    /// Score = frequency * position_weight (higher position = more recent)
-    ///
-    /// # Parameters
-    ///
-    /// * `order` - The order queue to search
+    /// Clears all entries from the cache.
     ///
-    /// # Returns
+    /// The removal listener (if any) is notified with [`RemovalCause::Explicit`].
+    pub fn clear(&self) {
+        self.remove_where(|_| true, RemovalCause::Explicit);
+    }
+
+    /// Removes every entry on behalf of the invalidation registry.
     ///
-    /// * `Option<String>` - The key with lowest score, or None if not found
-    fn find_arc_eviction_key(&self, order: &VecDeque<String>) -> Option<String> {
-        let mut best_evict_key: Option<String> = None;
-        let mut best_score = f64::MAX;
+    /// Same as [`clear`](Self::clear), but the removal listener is notified with
+    /// [`RemovalCause::Invalidated`].
+    pub fn invalidate_all(&self) {
+        self.remove_where(|_| true, RemovalCause::Invalidated);
+    }
 
-        for (idx, evict_key) in order.iter().enumerate() {
-            if let Some(entry) = self.cache.get(evict_key) {
-                let frequency = entry.2 as f64;
-                let position_weight = (order.len() - idx) as f64;
-                let score = frequency * position_weight;
+    /// Removes the entries whose key matches `check` on behalf of the invalidation registry.
+    ///
+    /// The removal listener (if any) is notified with [`RemovalCause::Invalidated`].
+    pub fn invalidate_where(&self, check: &dyn Fn(&str) -> bool) {
+        self.remove_where(check, RemovalCause::Invalidated);
+    }
 
-                if score < best_score {
-                    best_score = score;
-                    best_evict_key = Some(evict_key.clone());
-                }
-            }
+    /// Removes every expired entry, returning how many were removed.
+    ///
+    /// Expired entries are otherwise only removed when their key is read again, and
+    /// keep counting against `limit` and `max_memory` until then. The removal listener
+    /// (if any) is notified with [`RemovalCause::Expired`].
+    pub fn purge_expired(&self) -> usize {
+        if self.ttl.is_none() {
+            return 0;
         }
-
-        best_evict_key
+        let engine = self.engine();
+        let mut store = self.cache;
+        let mut removed = self.pending_removals();
+        let purged = {
+            let mut order = self.order.lock();
+            // With a timer wheel, only the entries whose timer is due are visited
+            let due = engine.due_timers();
+            engine.purge_expired(&mut store, &mut order, due, &mut removed)
+        };
+        removed.notify();
+        purged
     }
 
-    /// Finds the key with the lowest TLRU score for eviction.
-    ///
-    /// TLRU (Time-aware Least Recently Used) combines recency, frequency, and age factors
-    /// to determine which entry should be evicted.
-    ///
-    /// Score formula: `frequency^weight × position_weight × age_factor` (when `frequency_weight` is set;
-    /// otherwise `frequency × position_weight × age_factor`)
+    /// Evicts a single entry chosen by the eviction policy, on behalf of the
+    /// [`MemoryBudget`](crate::MemoryBudget).
     ///
-    /// Where:
-    /// - `frequency`: Access count for the entry
-    /// - `position_weight`: Higher for more recently accessed entries
-    /// - `age_factor`: Decreases as entry approaches TTL expiration (if TTL is set)
+    /// The removal listener (if any) is notified with [`RemovalCause::Memory`].
     ///
     /// # Returns
     ///
-    /// * `Some(String)` - The key with the lowest TLRU score
-    /// * `None` - If the order queue is empty or no valid entries exist
-    fn find_tlru_eviction_key(&self, order: &VecDeque<String>) -> Option<String> {
-        let mut best_evict_key: Option<String> = None;
-        let mut best_score = f64::MAX;
-
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        for (idx, evict_key) in order.iter().enumerate() {
-            if let Some(entry) = self.cache.get(evict_key) {
-                let frequency = entry.2 as f64;
-                let position_weight = (order.len() - idx) as f64;
-
-                // Calculate age factor based on TTL
-                let age_factor = if let Some(ttl_secs) = self.ttl {
-                    let entry_timestamp = entry.1;
-                    let elapsed = now.saturating_sub(entry_timestamp) as f64;
-                    let ttl_f64 = ttl_secs as f64;
-                    // Entries close to expiration get lower scores (prioritized for eviction)
-                    (1.0 - (elapsed / ttl_f64).min(1.0)).max(0.0)
-                } else {
-                    1.0 // No TTL, age doesn't matter
-                };
-
-                // Apply frequency weight if provided
-                let frequency_component = if let Some(weight) = self.frequency_weight {
-                    if frequency > 0.0 {
-                        frequency.powf(weight)
-                    } else {
-                        0.0
-                    }
-                } else {
-                    frequency
-                };
-
-                // Score combines frequency, recency, and age
-                let score = frequency_component * position_weight * age_factor;
-
-                if score < best_score {
-                    best_score = score;
-                    best_evict_key = Some(evict_key.clone());
-                }
-            }
-        }
-
-        best_evict_key
+    /// `false` if there was nothing left to evict.
+    pub fn evict(&self) -> bool {
+        let mut removed = self.pending_removals();
+        let evicted = {
+            let mut order = self.order.lock();
+            // Evictions must see the buffered hits
+            self.replay_accesses(&mut order);
+            let mut store = self.cache;
+            self.engine()
+                .evict_into(&mut store, &mut order, &mut removed, RemovalCause::Memory)
+        };
+        removed.notify();
+        evicted
     }
 
-    /// Handles the eviction of entries from the cache to enforce the entry limit based on the eviction policy.
-    ///
-    /// This method ensures that the number of entries in the cache does not exceed the configured limit by removing
-    /// entries based on the specified eviction policy.
-    ///
-    /// # Parameters
-    ///
-    /// * `order` - A mutable reference to the order queue
-    ///
-    /// # Behavior
+    /// Evicts `fraction` (0.0 to 1.0) of the entries, chosen by the eviction policy,
+    /// returning how many were evicted.
     ///
-    /// If the cache's entry limit is exceeded:
-    /// - **LFU**: Evicts the entry with the lowest frequency counter
-    /// - **ARC**: Evicts based on a hybrid score of frequency and recency
-    /// - **FIFO/LRU**: Evicts from the front of the queue
-    fn handle_entry_limit_eviction(&self, order: &mut VecDeque<String>) {
-        if let Some(limit) = self.limit {
-            if self.cache.len() >= limit {
-                match self.policy {
-                    EvictionPolicy::LFU => {
-                        if let Some(evict_key) = self.find_min_frequency_key(order) {
-                            self.cache.remove(&evict_key);
-                            order.retain(|k| k != &evict_key);
-                        }
-                    }
-                    EvictionPolicy::ARC => {
-                        if let Some(evict_key) = self.find_arc_eviction_key(order) {
-                            self.cache.remove(&evict_key);
-                            order.retain(|k| k != &evict_key);
-                        }
-                    }
-                    EvictionPolicy::TLRU => {
-                        if let Some(evict_key) = self.find_tlru_eviction_key(order) {
-                            self.cache.remove(&evict_key);
-                            order.retain(|k| k != &evict_key);
-                        }
-                    }
-                    EvictionPolicy::Random => {
-                        // O(1) random eviction: select random position and remove directly
-                        if !order.is_empty() {
-                            let pos = fastrand::usize(..order.len());
-                            if let Some(evict_key) = order.remove(pos) {
-                                self.cache.remove(&evict_key);
-                            }
-                        }
-                    }
-                    EvictionPolicy::FIFO | EvictionPolicy::LRU => {
-                        // FIFO and LRU: evict from front of queue
-                        while let Some(evict_key) = order.pop_front() {
-                            if self.cache.contains_key(&evict_key) {
-                                self.cache.remove(&evict_key);
-                                break;
-                            }
-                            // Key doesn't exist in cache (already removed), try next one
-                        }
-                    }
+    /// Used to give memory back under pressure (see
+    /// [`MemoryPressureMonitor`](crate::MemoryPressureMonitor)). The removal listener
+    /// (if any) is notified with [`RemovalCause::Memory`].
+    pub fn shrink(&self, fraction: f64) -> usize {
+        let count = shrink_count(self.cache.len(), fraction);
+        if count == 0 {
+            return 0;
+        }
+        let mut removed = self.pending_removals();
+        let mut evicted = 0;
+        {
+            let mut order = self.order.lock();
+            // Evictions must see the buffered hits
+            self.replay_accesses(&mut order);
+            let engine = self.engine();
+            let mut store = self.cache;
+            for _ in 0..count {
+                if !engine.evict_into(&mut store, &mut order, &mut removed, RemovalCause::Memory) {
+                    break;
                 }
+                evicted += 1;
             }
         }
+        removed.notify();
+        evicted
+    }
+
+    /// Removes the entries whose key matches `check`, reporting them with `cause`.
+    fn remove_where(&self, check: impl Fn(&str) -> bool, cause: RemovalCause) {
+        let mut store = self.cache;
+        let mut removed = self.pending_removals();
+        self.engine().remove_where(
+            &mut store,
+            &mut self.order.lock(),
+            check,
+            &mut removed,
+            cause,
+        );
+        removed.notify();
     }
 
     /// Returns a reference to the cache statistics.
@@ -736,6 +980,33 @@ impl<'a, R: Clone> AsyncGlobalCache<'a, R> {
 // Separate implementation for types that implement MemoryEstimator
 // This allows memory-based eviction
 impl<'a, R: Clone + crate::MemoryEstimator> AsyncGlobalCache<'a, R> {
+    /// Returns the cached value for `key`, or loads, caches and returns it, applying
+    /// memory limits.
+    ///
+    /// See [`AsyncGlobalCache::get_or_try_load`].
+    pub async fn get_or_try_load_with_memory<F, Fut, E>(&self, key: &str, load: F) -> Result<R, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+    {
+        self.try_load_with(key, load, Self::insert_with_memory)
+            .await
+    }
+
+    /// Returns the values of many keys at once, loading the missing ones in a single call
+    /// and applying memory limits.
+    ///
+    /// See [`AsyncGlobalCache::get_many`].
+    pub async fn get_many_with_memory<K, F, Fut>(&self, keys: &[K], load: F) -> Vec<Option<R>>
+    where
+        K: CacheableKey + Clone + Eq + Hash,
+        F: FnOnce(Vec<K>) -> Fut,
+        Fut: Future<Output = HashMap<K, R>>,
+    {
+        self.load_many_with(keys, load, Self::insert_with_memory)
+            .await
+    }
+
     /// Insert with memory limit support.
     ///
     /// This method requires `R` to implement `MemoryEstimator` and handles both
@@ -750,9 +1021,9 @@ impl<'a, R: Clone + crate::MemoryEstimator> AsyncGlobalCache<'a, R> {
     ///
     /// # Memory Management
     ///
-    /// The method calculates the memory footprint of all cached entries and evicts
-    /// entries as needed to stay within the `max_memory` limit. Eviction follows
-    /// the configured policy.
+    /// The value is estimated once, and its size stored in its entry. Entries are then
+    /// evicted as needed to keep the total size within the `max_memory` limit. Eviction
+    /// follows the configured policy.
     ///
     /// # Safety Check
     ///
@@ -794,122 +1065,22 @@ impl<'a, R: Clone + crate::MemoryEstimator> AsyncGlobalCache<'a, R> {
     ///
     /// # Performance
     ///
-    /// - **Memory calculation**: O(n) - iterates all entries to sum memory
+    /// - **Memory calculation**: O(1) with a [`MemoryUsage`] counter (see
+    ///   [`with_memory_usage`](Self::with_memory_usage)), O(n) sums of stored sizes otherwise
     /// - **Eviction**: Varies by policy (see individual policy documentation)
     /// - May evict multiple entries in one call if memory limit is tight
     pub fn insert_with_memory(&self, key: &str, value: R) {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        let mut order = self.order.lock();
-
-        // Check if another task already inserted this key while we were computing
-        if self.is_already_key_inserted(key, &mut order) {
-            return;
-        }
-
-        // Check memory limit first (if specified)
-        if let Some(max_mem) = self.max_memory {
-            let value_size = value.estimate_memory();
-
-            // Safety check: if the value itself is larger than max_mem,
-            // we need to handle it to avoid infinite loop
-            if value_size > max_mem {
-                // Value is too large to fit in cache even when empty
-                // We have two options:
-                // 1. Don't cache it at all (skip insertion)
-                // 2. Clear all entries and cache it anyway
-                // We choose option 1 to respect the memory limit
-                return;
-            }
-
-            loop {
-                let current_mem: usize = self
-                    .cache
-                    .iter()
-                    .map(|entry| entry.value().0.estimate_memory())
-                    .sum();
-
-                if current_mem + value_size <= max_mem {
-                    break;
-                }
-
-                // Need to evict based on policy
-                let evicted = match self.policy {
-                    EvictionPolicy::LFU => {
-                        if let Some(evict_key) = self.find_min_frequency_key(&*order) {
-                            self.cache.remove(&evict_key);
-                            order.retain(|k| k != &evict_key);
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                    EvictionPolicy::ARC => {
-                        if let Some(evict_key) = self.find_arc_eviction_key(&*order) {
-                            self.cache.remove(&evict_key);
-                            order.retain(|k| k != &evict_key);
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                    EvictionPolicy::TLRU => {
-                        if let Some(evict_key) = self.find_tlru_eviction_key(&*order) {
-                            self.cache.remove(&evict_key);
-                            order.retain(|k| k != &evict_key);
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                    EvictionPolicy::Random => {
-                        // O(1) random eviction: select random position and remove directly
-                        if !order.is_empty() {
-                            let pos = fastrand::usize(..order.len());
-                            if let Some(evict_key) = order.remove(pos) {
-                                self.cache.remove(&evict_key);
-                                true
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
-                        }
-                    }
-                    EvictionPolicy::FIFO | EvictionPolicy::LRU => {
-                        if let Some(evict_key) = order.pop_front() {
-                            self.cache.remove(&evict_key);
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                };
-
-                if !evicted {
-                    break; // Nothing left to evict
-                }
-            }
-        }
-
-        // Handle entry-count limits (reuse the same method)
-        self.handle_entry_limit_eviction(&mut order);
-
-        // Add the new entry to the order queue
-        order.push_back(key.to_string());
-
-        // Insert into cache with frequency initialized to 0
-        self.cache.insert(key.to_string(), (value, timestamp, 0));
+        let mut entry = CacheEntry::new(value);
+        entry.size = entry.value.estimate_memory();
+        self.insert_entry(key, entry);
+        MemoryBudget::global().enforce();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::time::{Duration, Instant};
 
     #[test]
     fn test_async_cache_basic() {
@@ -1020,19 +1191,17 @@ mod tests {
             &stats,
         );
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        // Insert with timestamp exactly 1 second in the past (age == ttl)
-        cache.insert("k".to_string(), ("v", now.saturating_sub(1), 0));
+        // Insert exactly 1 second in the past (age == ttl)
+        let mut entry = CacheEntry::new("v");
+        entry.inserted_at = Instant::now() - Duration::from_secs(1);
+        cache.insert("k".to_string(), entry);
 
         // With >= comparison, this must be considered expired
         assert_eq!(async_cache.get("k"), None);
     }
 
     #[test]
-    fn test_async_cache_clock_moves_backwards_not_expired() {
+    fn test_async_cache_future_insertion_not_expired() {
         let cache = DashMap::new();
         let order = Mutex::new(VecDeque::new());
 
@@ -1061,21 +1230,15 @@ mod tests {
             &stats,
         );
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
         // Insert via the cache API so the 'order' queue is updated as well
         async_cache.insert("k", "v");
 
-        // Simulate a "future" timestamp (as if the clock moved backwards later)
-        // Adjust only the timestamp of the already inserted entry
-        let future_ts = now.saturating_add(100);
+        // Move the insertion time of the already inserted entry into the future
         if let Some(mut entry) = cache.get_mut("k") {
-            entry.1 = future_ts;
+            entry.inserted_at = Instant::now() + Duration::from_secs(100);
         }
 
-        // With saturating_sub, age = 0, which is < ttl => NOT expired
+        // The elapsed time saturates at 0, which is < ttl => NOT expired
         assert_eq!(async_cache.get("k"), Some("v"));
 
         // Verify that the order queue contains the key "k"
@@ -1572,4 +1735,52 @@ mod tests {
         // k3 should be evicted (least recently used and zero frequency)
         assert_eq!(async_cache.get("k3"), None);
     }
+
+    #[test]
+    fn test_tlru_frequency_weight_changes_victim() {
+        // Scores without a weight: k1 = 2 × 2, k2 = 3 × 1.
+        // With a weight of 3.0: k1 = 2³ × 2, k2 = 3³ × 1.
+        for (weight, evicted, kept) in [(None, "k2", "k1"), (Some(3.0), "k1", "k2")] {
+            let cache = DashMap::new();
+            let order = Mutex::new(VecDeque::new());
+
+            #[cfg(not(feature = "stats"))]
+            let async_cache = AsyncGlobalCache::new(
+                &cache,
+                &order,
+                Some(2),
+                None,
+                EvictionPolicy::TLRU,
+                None,
+                weight,
+            );
+
+            #[cfg(feature = "stats")]
+            let stats = CacheStats::new();
+            #[cfg(feature = "stats")]
+            let async_cache = AsyncGlobalCache::new(
+                &cache,
+                &order,
+                Some(2),
+                None,
+                EvictionPolicy::TLRU,
+                None,
+                weight,
+                &stats,
+            );
+
+            async_cache.insert("k1", 1);
+            async_cache.insert("k2", 2);
+            for _ in 0..2 {
+                let _ = async_cache.get("k1");
+            }
+            for _ in 0..3 {
+                let _ = async_cache.get("k2");
+            }
+
+            assert!(async_cache.evict());
+            assert!(async_cache.get(evicted).is_none());
+            assert!(async_cache.get(kept).is_some());
+        }
+    }
 }
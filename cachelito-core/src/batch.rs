@@ -0,0 +1,112 @@
+use crate::CacheableKey;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// A batch lookup in progress: the hits found in the cache and the keys left to load.
+///
+/// Shared by the `get_many` methods of every cache, which only differ in how they
+/// read, insert and call the loader.
+pub(crate) struct Batch<K, R> {
+    /// Cache key of each requested key, in request order
+    cache_keys: Vec<String>,
+    /// Value of each requested key, `None` until found or loaded
+    values: Vec<Option<R>>,
+    /// Keys to pass to the loader, without duplicates
+    missing: Vec<K>,
+}
+
+impl<K, R> Batch<K, R>
+where
+    K: CacheableKey + Clone + Eq + Hash,
+    R: Clone,
+{
+    /// Looks up every key with `get`, collecting the missing ones.
+    pub(crate) fn lookup(keys: &[K], mut get: impl FnMut(&str) -> Option<R>) -> Self {
+        let mut cache_keys = Vec::with_capacity(keys.len());
+        let mut values = Vec::with_capacity(keys.len());
+        let mut missing = Vec::new();
+        let mut seen = HashSet::new();
+
+        for key in keys {
+            let cache_key = key.to_cache_key();
+            let value = get(&cache_key);
+            if value.is_none() && seen.insert(cache_key.clone()) {
+                missing.push(key.clone());
+            }
+            cache_keys.push(cache_key);
+            values.push(value);
+        }
+
+        Self {
+            cache_keys,
+            values,
+            missing,
+        }
+    }
+
+    /// Takes the keys to load; empty when every key was a hit.
+    pub(crate) fn take_missing(&mut self) -> Vec<K> {
+        std::mem::take(&mut self.missing)
+    }
+
+    /// Inserts the loaded values with `insert` and returns all values in request order.
+    ///
+    /// Keys the loader returned no value for stay `None`; values for keys that were
+    /// not requested are ignored.
+    pub(crate) fn fill(
+        mut self,
+        loaded: HashMap<K, R>,
+        mut insert: impl FnMut(&str, R),
+    ) -> Vec<Option<R>> {
+        let loaded: HashMap<String, R> = loaded
+            .into_iter()
+            .map(|(key, value)| (key.to_cache_key(), value))
+            .collect();
+        let mut inserted = HashSet::new();
+
+        for (cache_key, slot) in self.cache_keys.iter().zip(self.values.iter_mut()) {
+            if slot.is_some() {
+                continue;
+            }
+            if let Some(value) = loaded.get(cache_key) {
+                // Duplicated keys share the loaded value, which is inserted once
+                if inserted.insert(cache_key.as_str()) {
+                    insert(cache_key, value.clone());
+                }
+                *slot = Some(value.clone());
+            }
+        }
+
+        self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_missing_keys_are_loaded() {
+        let cached: HashMap<String, u32> = [("1".to_string(), 10)].into_iter().collect();
+        let mut batch = Batch::lookup(&[1u32, 2, 2, 3], |key| cached.get(key).copied());
+        assert_eq!(batch.take_missing(), vec![2, 3]);
+
+        let mut inserted = Vec::new();
+        let values = batch.fill([(2, 20)].into_iter().collect(), |key, value| {
+            inserted.push((key.to_string(), value))
+        });
+
+        assert_eq!(values, vec![Some(10), Some(20), Some(20), None]);
+        assert_eq!(inserted, vec![("2".to_string(), 20)]);
+    }
+
+    #[test]
+    fn test_all_hits_leave_nothing_to_load() {
+        let mut batch = Batch::lookup(&[1u32, 2], |key| key.parse::<u32>().ok());
+        assert!(batch.take_missing().is_empty());
+        assert_eq!(
+            batch.fill(HashMap::new(), |_, _| {}),
+            vec![Some(1), Some(2)]
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Value of the limit while no budget is set
+const UNLIMITED: usize = usize::MAX;
+
+/// Callback returning the estimated memory of a cache, in bytes
+type UsageCallback = Arc<dyn Fn() -> usize + Send + Sync>;
+
+/// Callback evicting one entry of a cache, returning `false` if nothing was left to evict
+type EvictCallback = Arc<dyn Fn() -> bool + Send + Sync>;
+
+/// Hooks of a cache counting against the budget.
+#[derive(Clone)]
+struct BudgetedCache {
+    usage: UsageCallback,
+    evict: EvictCallback,
+}
+
+/// Process-wide memory budget shared by every memory-aware cache.
+///
+/// `max_memory` bounds each cache on its own, so ten caches at `"100MB"` can hold 1GB
+/// together. Global and async caches with `max_memory` register with the budget on
+/// their first call; once a budget is set (see [`set_global_memory_budget`]), every
+/// memory-aware insertion checks the total of the registered caches, and evicts entries
+/// until it fits again.
+///
+/// Entries are taken from the cache with the lowest hit rate (from the statistics of
+/// [`stats_registry`](crate::stats_registry)), and from the largest cache when hit rates
+/// are equal or not collected. Each cache evicts according to its own policy.
+///
+/// Thread-local caches cannot be reached from other threads and are not registered.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::MemoryBudget;
+///
+/// let budget = MemoryBudget::global();
+/// budget.set_limit(Some(512 * 1024 * 1024));
+/// assert_eq!(budget.limit(), Some(512 * 1024 * 1024));
+/// budget.set_limit(None);
+/// ```
+pub struct MemoryBudget {
+    limit: AtomicUsize,
+    caches: RwLock<HashMap<String, BudgetedCache>>,
+}
+
+impl MemoryBudget {
+    fn new() -> Self {
+        Self {
+            limit: AtomicUsize::new(UNLIMITED),
+            caches: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the budget shared by every cache of the process.
+    pub fn global() -> &'static MemoryBudget {
+        static INSTANCE: OnceLock<MemoryBudget> = OnceLock::new();
+        INSTANCE.get_or_init(MemoryBudget::new)
+    }
+
+    /// Sets the budget in bytes, or removes it with `None`.
+    ///
+    /// Lowering the budget does not evict anything by itself: the caches shrink on
+    /// their next memory-aware insertion.
+    pub fn set_limit(&self, limit: Option<usize>) {
+        self.limit
+            .store(limit.unwrap_or(UNLIMITED), Ordering::Relaxed);
+    }
+
+    /// Returns the budget in bytes, or `None` if no budget is set.
+    pub fn limit(&self) -> Option<usize> {
+        let limit = self.limit.load(Ordering::Relaxed);
+        (limit != UNLIMITED).then_some(limit)
+    }
+
+    /// Registers a cache under `cache_name`, replacing any cache registered under that name.
+    ///
+    /// This is called automatically by the `#[cache]` and `#[cache_async]` macros for
+    /// global caches with `max_memory`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_name` - The name of the cache, as registered in the statistics registry
+    /// * `usage` - Returns the estimated memory of the cache, in bytes
+    /// * `evict` - Evicts one entry, returning `false` if nothing was left to evict
+    pub fn register<U, E>(&self, cache_name: &str, usage: U, evict: E)
+    where
+        U: Fn() -> usize + Send + Sync + 'static,
+        E: Fn() -> bool + Send + Sync + 'static,
+    {
+        let cache = BudgetedCache {
+            usage: Arc::new(usage),
+            evict: Arc::new(evict),
+        };
+        self.caches.write().insert(cache_name.to_string(), cache);
+    }
+
+    /// Returns the names of the registered caches.
+    pub fn list(&self) -> Vec<String> {
+        self.caches.read().keys().cloned().collect()
+    }
+
+    /// Returns the estimated memory of all registered caches, in bytes.
+    pub fn usage(&self) -> usize {
+        self.caches
+            .read()
+            .values()
+            .map(|cache| (cache.usage)())
+            .sum()
+    }
+
+    /// Evicts entries from the registered caches until their total fits within the budget.
+    ///
+    /// Called by the caches after every memory-aware insertion, with no lock held.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries evicted.
+    pub fn enforce(&self) -> usize {
+        let Some(limit) = self.limit() else {
+            return 0;
+        };
+        // Release the registry lock first, so that the evicted caches can be locked
+        let caches: Vec<(String, BudgetedCache)> = self
+            .caches
+            .read()
+            .iter()
+            .map(|(name, cache)| (name.clone(), cache.clone()))
+            .collect();
+
+        let mut evicted = 0;
+        let mut exhausted = vec![false; caches.len()];
+        loop {
+            let usages: Vec<usize> = caches.iter().map(|(_, cache)| (cache.usage)()).collect();
+            if usages.iter().sum::<usize>() <= limit {
+                break;
+            }
+            let victim = (0..caches.len())
+                .filter(|&i| usages[i] > 0 && !exhausted[i])
+                .min_by(|&a, &b| {
+                    hit_rate(&caches[a].0)
+                        .total_cmp(&hit_rate(&caches[b].0))
+                        .then(usages[b].cmp(&usages[a]))
+                });
+            let Some(victim) = victim else {
+                break;
+            };
+            if (caches[victim].1.evict)() {
+                evicted += 1;
+            } else {
+                exhausted[victim] = true;
+            }
+        }
+        evicted
+    }
+
+    /// Removes every registered cache (mainly for testing).
+    pub fn clear(&self) {
+        self.caches.write().clear();
+    }
+}
+
+/// Returns the hit rate of the cache registered under `cache_name`, 0 if unknown.
+#[cfg(feature = "stats")]
+fn hit_rate(cache_name: &str) -> f64 {
+    crate::stats_registry::get_ref(cache_name).map_or(0.0, |stats| stats.hit_rate())
+}
+
+/// Without statistics, caches are only told apart by their size.
+#[cfg(not(feature = "stats"))]
+fn hit_rate(_cache_name: &str) -> f64 {
+    0.0
+}
+
+/// Parses a memory size such as `"512MB"`, `"1GB"`, `"500KB"` or a number of bytes.
+fn parse_memory_size(size: &str) -> Option<usize> {
+    let size = size.trim().to_uppercase();
+    let (number, unit) = if let Some(number) = size.strip_suffix("GB") {
+        (number, 1024 * 1024 * 1024)
+    } else if let Some(number) = size.strip_suffix("MB") {
+        (number, 1024 * 1024)
+    } else if let Some(number) = size.strip_suffix("KB") {
+        (number, 1024)
+    } else {
+        (size.as_str(), 1)
+    };
+    number.trim().parse::<usize>().ok()?.checked_mul(unit)
+}
+
+/// Sets a memory budget shared by all global and async caches with `max_memory`.
+///
+/// The budget accepts the same formats as `max_memory`: `"512MB"`, `"1GB"`, `"500KB"`,
+/// or a number of bytes. See [`MemoryBudget`].
+///
+/// # Panics
+///
+/// Panics if `budget` is not a valid memory size.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::{clear_global_memory_budget, set_global_memory_budget, MemoryBudget};
+///
+/// set_global_memory_budget("512MB");
+/// assert_eq!(MemoryBudget::global().limit(), Some(512 * 1024 * 1024));
+/// clear_global_memory_budget();
+/// ```
+pub fn set_global_memory_budget(budget: &str) {
+    let Some(bytes) = parse_memory_size(budget) else {
+        panic!(
+            "invalid memory budget {:?}: expected \"512MB\", \"1GB\", \"500KB\", or a number of bytes",
+            budget
+        );
+    };
+    MemoryBudget::global().set_limit(Some(bytes));
+}
+
+/// Removes the memory budget set by [`set_global_memory_budget`].
+pub fn clear_global_memory_budget() {
+    MemoryBudget::global().set_limit(None);
+}
+
+/// Returns the estimated memory of all caches registered with the budget, in bytes.
+pub fn global_memory_usage() -> usize {
+    MemoryBudget::global().usage()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_parse_memory_size() {
+        assert_eq!(parse_memory_size("512MB"), Some(512 * 1024 * 1024));
+        assert_eq!(parse_memory_size("1gb"), Some(1024 * 1024 * 1024));
+        assert_eq!(parse_memory_size("500KB"), Some(500 * 1024));
+        assert_eq!(parse_memory_size("4096"), Some(4096));
+        assert_eq!(parse_memory_size("lots"), None);
+        assert_eq!(parse_memory_size("-1MB"), None);
+    }
+
+    #[test]
+    fn test_enforce_evicts_from_largest_cache() {
+        let budget = MemoryBudget::new();
+        let sizes = Arc::new(Mutex::new(vec![300usize, 100]));
+        for index in 0..2 {
+            let (usage, evict) = (Arc::clone(&sizes), Arc::clone(&sizes));
+            budget.register(
+                &format!("__budget_test_{}", index),
+                move || usage.lock().unwrap()[index],
+                move || {
+                    let mut sizes = evict.lock().unwrap();
+                    if sizes[index] == 0 {
+                        return false;
+                    }
+                    sizes[index] -= 50;
+                    true
+                },
+            );
+        }
+
+        // Nothing happens until a budget is set
+        assert_eq!(budget.enforce(), 0);
+        assert_eq!(budget.usage(), 400);
+
+        budget.set_limit(Some(250));
+        assert_eq!(budget.enforce(), 3);
+        assert_eq!(*sizes.lock().unwrap(), vec![150, 100]);
+    }
+
+    #[test]
+    fn test_enforce_stops_when_nothing_can_be_evicted() {
+        let budget = MemoryBudget::new();
+        budget.register("__budget_test_stuck", || 1000, || false);
+        budget.set_limit(Some(10));
+        assert_eq!(budget.enforce(), 0);
+    }
+}
@@ -1,4 +1,5 @@
-use std::time::Instant;
+use std::cell::Cell;
+use std::time::{Duration, Instant};
 
 /// Internal wrapper that tracks when a value was inserted into the cache.
 /// Used for TTL expiration support.
@@ -16,6 +17,10 @@ use std::time::Instant;
 /// * `value` - The actual cached value
 /// * `inserted_at` - The `Instant` when this entry was created
 /// * `frequency` - The number of times this entry has been accessed (for LFU policy)
+/// * `ttl` - Optional per-entry time-to-live in seconds, overriding the TTL of the cache
+/// * `size` - Estimated memory of the value in bytes, measured when inserted into a cache
+///   with `max_memory` (0 otherwise)
+/// * `cost` - Time it took to compute the value (zero if unknown), for the GDSF policy
 ///
 /// # Examples
 ///
@@ -34,6 +39,9 @@ pub struct CacheEntry<R> {
     pub value: R,
     pub inserted_at: Instant,
     pub frequency: u64,
+    pub ttl: Option<u64>,
+    pub size: usize,
+    pub cost: Duration,
 }
 
 impl<R> CacheEntry<R> {
@@ -51,11 +59,53 @@ impl<R> CacheEntry<R> {
             value,
             inserted_at: Instant::now(),
             frequency: 0,
+            ttl: None,
+            size: 0,
+            cost: Duration::ZERO,
         }
     }
 
+    /// Sets a per-entry time-to-live that takes precedence over the TTL of the cache.
+    ///
+    /// Used to let some entries (e.g. cached errors) expire sooner than the others.
+    /// `None` keeps the TTL of the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cachelito_core::CacheEntry;
+    ///
+    /// let entry = CacheEntry::new("error").with_ttl(Some(0));
+    /// assert!(entry.is_expired(Some(60)));
+    /// assert!(entry.is_expired(None));
+    /// ```
+    pub fn with_ttl(mut self, ttl: Option<u64>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the time it took to compute the value.
+    ///
+    /// Entries inserted inside [`with_compute_cost`] get the cost given to it instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cachelito_core::CacheEntry;
+    /// use std::time::Duration;
+    ///
+    /// let entry = CacheEntry::new("report").with_cost(Duration::from_millis(250));
+    /// assert_eq!(entry.cost, Duration::from_millis(250));
+    /// ```
+    pub fn with_cost(mut self, cost: Duration) -> Self {
+        self.cost = cost;
+        self
+    }
+
     /// Returns true if the entry has expired based on the provided TTL.
     ///
+    /// The per-entry TTL (see [`CacheEntry::with_ttl`]) takes precedence over `ttl`.
+    ///
     /// # Arguments
     ///
     /// * `ttl` - Optional time-to-live in seconds. `None` means no expiration.
@@ -87,13 +137,57 @@ impl<R> CacheEntry<R> {
     /// assert!(!entry.is_expired(None));
     /// ```
     pub fn is_expired(&self, ttl: Option<u64>) -> bool {
-        if let Some(ttl_secs) = ttl {
+        if let Some(ttl_secs) = self.ttl.or(ttl) {
             self.inserted_at.elapsed().as_secs() >= ttl_secs
         } else {
             false
         }
     }
 
+    /// Returns the instant at which the entry expires, if it expires at all.
+    ///
+    /// The per-entry TTL (see [`CacheEntry::with_ttl`]) takes precedence over `ttl`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cachelito_core::CacheEntry;
+    /// use std::time::Duration;
+    ///
+    /// let entry = CacheEntry::new("data");
+    /// assert_eq!(entry.expires_at(Some(60)), Some(entry.inserted_at + Duration::from_secs(60)));
+    /// assert_eq!(entry.expires_at(None), None);
+    /// ```
+    pub fn expires_at(&self, ttl: Option<u64>) -> Option<Instant> {
+        let ttl_secs = self.ttl.or(ttl)?;
+        self.inserted_at.checked_add(Duration::from_secs(ttl_secs))
+    }
+
+    /// Returns how much of its lifetime the entry has left, from 1.0 (just inserted) to
+    /// 0.0 (expired), as used by the TLRU policy.
+    ///
+    /// The per-entry TTL (see [`CacheEntry::with_ttl`]) takes precedence over `ttl`.
+    /// Entries that never expire always return 1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cachelito_core::CacheEntry;
+    ///
+    /// let entry = CacheEntry::new("data");
+    /// assert!(entry.age_factor(Some(60)) > 0.99);
+    /// assert_eq!(entry.age_factor(None), 1.0);
+    /// ```
+    pub fn age_factor(&self, ttl: Option<u64>) -> f64 {
+        match self.ttl.or(ttl) {
+            Some(ttl_secs) => {
+                let elapsed = self.inserted_at.elapsed().as_secs_f64();
+                (1.0 - (elapsed / ttl_secs as f64).min(1.0)).max(0.0)
+            }
+            None => 1.0,
+        }
+    }
+
     /// Increments the access frequency counter.
     ///
     /// This method is used by the LFU (Least Frequently Used) eviction policy
@@ -118,6 +212,41 @@ impl<R> CacheEntry<R> {
     }
 }
 
+thread_local! {
+    static COMPUTE_COST: Cell<Option<Duration>> = const { Cell::new(None) };
+}
+
+/// Runs `f`, giving `cost` to the entries it inserts into any cache on this thread.
+///
+/// The macros call the insert methods of the caches inside it, with the time the cached
+/// function took to compute its value, so that cost-aware policies (GDSF, custom
+/// [`EvictionStrategy`](crate::EvictionStrategy) implementations) can weigh it.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::with_compute_cost;
+/// use std::time::{Duration, Instant};
+///
+/// let started = Instant::now();
+/// let value = 6 * 7;
+/// with_compute_cost(started.elapsed(), || {
+///     // cache.insert("answer", value);
+/// });
+/// # let _ = value;
+/// ```
+pub fn with_compute_cost<T>(cost: Duration, f: impl FnOnce() -> T) -> T {
+    let previous = COMPUTE_COST.with(|current| current.replace(Some(cost)));
+    let result = f();
+    COMPUTE_COST.with(|current| current.set(previous));
+    result
+}
+
+/// Returns the cost set by the innermost [`with_compute_cost`] call running on this thread.
+pub(crate) fn compute_cost() -> Option<Duration> {
+    COMPUTE_COST.with(Cell::get)
+}
+
 // Implement MemoryEstimator for CacheEntry
 use crate::MemoryEstimator;
 
@@ -163,6 +292,15 @@ mod tests {
         assert!(!entry.is_expired(None));
     }
 
+    #[test]
+    fn test_entry_ttl_overrides_cache_ttl() {
+        let entry = CacheEntry::new(1).with_ttl(Some(1));
+        assert!(!entry.is_expired(None));
+        thread::sleep(Duration::from_millis(1100));
+        assert!(entry.is_expired(Some(60)));
+        assert!(entry.is_expired(None));
+    }
+
     #[test]
     fn test_memory_estimation_primitive() {
         let entry = CacheEntry::new(42i32);
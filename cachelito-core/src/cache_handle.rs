@@ -0,0 +1,229 @@
+use crate::{GlobalCache, ThreadLocalCache};
+
+#[cfg(feature = "stats")]
+use crate::CacheStats;
+
+/// The storage behind a [`CacheHandle`].
+///
+/// The `scope` attribute of `#[cache]` selects the storage at compile time: the
+/// generated `<fn_name>_cache()` wraps a thread-local cache for `scope = "thread"`,
+/// and a global cache for the other scopes (instance-scoped caches included).
+pub enum CacheBackend<'a, R: 'static> {
+    /// Cache stored in thread-local storage (`scope = "thread"`)
+    ThreadLocal(ThreadLocalCache<R>),
+    /// Cache shared across all threads (`scope = "global"`), or owned by an instance
+    /// (`scope = "instance"`)
+    Global(GlobalCache<'a, R>),
+}
+
+/// Typed handle to the cache of a `#[cache]` function.
+///
+/// The `#[cache]` macro generates a companion function named `<fn_name>_cache()`
+/// returning a handle to the function's cache. The handle builds keys exactly like
+/// the cached function does, so single entries can be read, inserted or invalidated
+/// from the function arguments without knowing the internal key format.
+///
+/// # Type Parameters
+///
+/// * `A` - The arguments of the cached function: the argument type itself for
+///   single-argument functions, a tuple otherwise (`()` when there are none).
+///   For methods, the receiver (`&Self`) comes first.
+/// * `R` - The return type of the cached function
+///
+/// # Examples
+///
+/// ```ignore
+/// #[cache(scope = "global", limit = 100)]
+/// fn fetch_user(id: u64) -> User {
+///     load_user(id)
+/// }
+///
+/// fetch_user(42);
+/// assert!(fetch_user_cache().get(&42).is_some());
+///
+/// // Remove a single entry without scanning the cache
+/// fetch_user_cache().invalidate(&42);
+/// assert_eq!(fetch_user_cache().len(), 0);
+/// ```
+pub struct CacheHandle<'a, A, R: 'static> {
+    backend: CacheBackend<'a, R>,
+    key_fn: fn(&A) -> String,
+    insert_fn: fn(&CacheBackend<'a, R>, &str, R),
+}
+
+impl<'a, A, R: Clone + 'static> CacheHandle<'a, A, R> {
+    /// Creates a new handle.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - The cache storage
+    /// * `key_fn` - Builds the cache key from the function arguments
+    /// * `insert_fn` - Inserts a value, honoring the memory limit when one is configured
+    pub fn new(
+        backend: CacheBackend<'a, R>,
+        key_fn: fn(&A) -> String,
+        insert_fn: fn(&CacheBackend<'a, R>, &str, R),
+    ) -> Self {
+        Self {
+            backend,
+            key_fn,
+            insert_fn,
+        }
+    }
+
+    /// Returns the underlying cache storage.
+    pub fn backend(&self) -> &CacheBackend<'a, R> {
+        &self.backend
+    }
+
+    /// Returns the cache key the function uses for `args`.
+    pub fn key(&self, args: &A) -> String {
+        (self.key_fn)(args)
+    }
+
+    /// Returns the cached value for `args`, if present and not expired.
+    pub fn get(&self, args: &A) -> Option<R> {
+        let key = self.key(args);
+        match &self.backend {
+            CacheBackend::ThreadLocal(cache) => cache.get(&key),
+            CacheBackend::Global(cache) => cache.get(&key),
+        }
+    }
+
+    /// Stores `value` as the result for `args`, replacing any previous value.
+    pub fn insert(&self, args: &A, value: R) {
+        let key = self.key(args);
+        (self.insert_fn)(&self.backend, &key, value);
+    }
+
+    /// Removes the entry for `args`.
+    ///
+    /// The removal listener (if any) is notified with [`RemovalCause::Explicit`](crate::RemovalCause::Explicit).
+    ///
+    /// # Returns
+    ///
+    /// `true` if an entry was removed.
+    pub fn invalidate(&self, args: &A) -> bool {
+        let key = self.key(args);
+        match &self.backend {
+            CacheBackend::ThreadLocal(cache) => cache.remove(&key),
+            CacheBackend::Global(cache) => cache.remove(&key),
+        }
+    }
+
+    /// Returns the number of cached entries (for the current thread with thread-local scope).
+    pub fn len(&self) -> usize {
+        match &self.backend {
+            CacheBackend::ThreadLocal(cache) => cache.len(),
+            CacheBackend::Global(cache) => cache.len(),
+        }
+    }
+
+    /// Returns `true` if the cache contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every entry from the cache.
+    pub fn clear(&self) {
+        match &self.backend {
+            CacheBackend::ThreadLocal(cache) => cache.clear(),
+            CacheBackend::Global(cache) => cache.clear(),
+        }
+    }
+
+    /// Removes the expired entries (of the current thread with thread-local scope),
+    /// returning how many were removed.
+    ///
+    /// Unlike [`purge_expired`](crate::purge_expired), this also reaches thread-local caches.
+    pub fn purge_expired(&self) -> usize {
+        match &self.backend {
+            CacheBackend::ThreadLocal(cache) => cache.purge_expired(),
+            CacheBackend::Global(cache) => cache.purge_expired(),
+        }
+    }
+
+    /// Returns the estimated memory of the cached entries (of the current thread with
+    /// thread-local scope), in bytes.
+    ///
+    /// Entries are only measured by caches with `max_memory`; the others report 0.
+    pub fn memory_usage(&self) -> usize {
+        match &self.backend {
+            CacheBackend::ThreadLocal(cache) => cache.memory_usage(),
+            CacheBackend::Global(cache) => cache.memory_usage(),
+        }
+    }
+
+    /// Returns the statistics of a global cache.
+    ///
+    /// Thread-local caches do not keep statistics between calls, so `None`
+    /// is returned for them.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Option<&CacheStats> {
+        match &self.backend {
+            CacheBackend::ThreadLocal(_) => None,
+            CacheBackend::Global(cache) => Some(cache.stats()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CacheEntry, EvictionPolicy};
+    use once_cell::sync::Lazy;
+    use parking_lot::{Mutex, RwLock};
+    use std::collections::{HashMap, VecDeque};
+
+    static MAP: Lazy<RwLock<HashMap<String, CacheEntry<u32>>>> =
+        Lazy::new(|| RwLock::new(HashMap::new()));
+    static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+    #[cfg(feature = "stats")]
+    static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
+
+    fn handle() -> CacheHandle<'static, (u32, u32), u32> {
+        #[cfg(feature = "stats")]
+        let cache = GlobalCache::new(
+            &MAP,
+            &ORDER,
+            None,
+            None,
+            EvictionPolicy::FIFO,
+            None,
+            None,
+            &STATS,
+        );
+        #[cfg(not(feature = "stats"))]
+        let cache = GlobalCache::new(&MAP, &ORDER, None, None, EvictionPolicy::FIFO, None, None);
+
+        CacheHandle::new(
+            CacheBackend::Global(cache),
+            |args: &(u32, u32)| format!("{}|{}", args.0, args.1),
+            |backend, key, value| {
+                if let CacheBackend::Global(cache) = backend {
+                    cache.insert(key, value);
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn test_handle_typed_operations() {
+        let handle = handle();
+        assert!(handle.is_empty());
+
+        handle.insert(&(1, 2), 3);
+        handle.insert(&(2, 3), 5);
+        assert_eq!(handle.key(&(1, 2)), "1|2");
+        assert_eq!(handle.get(&(1, 2)), Some(3));
+        assert_eq!(handle.len(), 2);
+
+        assert!(handle.invalidate(&(1, 2)));
+        assert!(!handle.invalidate(&(1, 2)));
+        assert_eq!(handle.get(&(1, 2)), None);
+        assert_eq!(handle.get(&(2, 3)), Some(5));
+
+        handle.clear();
+        assert!(handle.is_empty());
+    }
+}
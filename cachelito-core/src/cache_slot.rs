@@ -0,0 +1,175 @@
+use crate::{CacheEntry, EvictionPolicy, EvictionState, GlobalCache, MemoryUsage};
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+#[cfg(feature = "stats")]
+use crate::CacheStats;
+
+/// Cache storage owned by a struct instance.
+///
+/// Methods annotated with `#[cache(scope = "instance", cache_field = "...")]` store
+/// their results in a `CacheSlot` field of `self` instead of a global static. Entries
+/// therefore live exactly as long as the owning instance, instances never see each
+/// other's results, and `self` does not need to be part of the cache key (so no
+/// `Debug` or `CacheableKey` implementation is required on the struct).
+///
+/// The slot only holds the storage; the cache configuration (limit, policy, TTL, ...)
+/// comes from the `#[cache]` attribute and is passed to [`CacheSlot::cache`].
+///
+/// Cloning a slot yields an empty slot: a cloned instance starts with a cold cache
+/// rather than sharing or copying the entries of the original.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::{CacheSlot, EvictionPolicy};
+///
+/// struct Repository {
+///     cache: CacheSlot<String>,
+/// }
+///
+/// let repo = Repository { cache: CacheSlot::new() };
+/// let cache = repo.cache.cache(Some(100), None, EvictionPolicy::LRU, None, None);
+///
+/// cache.insert("1", "one".to_string());
+/// assert_eq!(cache.get("1"), Some("one".to_string()));
+/// assert_eq!(repo.cache.len(), 1);
+/// ```
+pub struct CacheSlot<R: 'static> {
+    map: RwLock<HashMap<String, CacheEntry<R>>>,
+    order: Mutex<VecDeque<String>>,
+    memory: MemoryUsage,
+    eviction_state: EvictionState,
+    #[cfg(feature = "stats")]
+    stats: CacheStats,
+}
+
+impl<R: Clone + 'static> CacheSlot<R> {
+    /// Creates an empty slot.
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+            order: Mutex::new(VecDeque::new()),
+            memory: MemoryUsage::new(),
+            eviction_state: EvictionState::new(),
+            #[cfg(feature = "stats")]
+            stats: CacheStats::new(),
+        }
+    }
+
+    /// Returns a cache view over the storage of this slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Optional maximum number of entries
+    /// * `max_memory` - Optional maximum memory size in bytes
+    /// * `policy` - Eviction policy
+    /// * `ttl` - Optional time-to-live in seconds
+    /// * `frequency_weight` - Optional weight factor for frequency in TLRU policy
+    pub fn cache(
+        &self,
+        limit: Option<usize>,
+        max_memory: Option<usize>,
+        policy: EvictionPolicy,
+        ttl: Option<u64>,
+        frequency_weight: Option<f64>,
+    ) -> GlobalCache<'_, R> {
+        GlobalCache::new(
+            &self.map,
+            &self.order,
+            limit,
+            max_memory,
+            policy,
+            ttl,
+            frequency_weight,
+            #[cfg(feature = "stats")]
+            &self.stats,
+        )
+        .with_memory_usage(&self.memory)
+        .with_eviction_state(&self.eviction_state)
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.map.read().len()
+    }
+
+    /// Returns `true` if the slot contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.read().is_empty()
+    }
+
+    /// Removes every entry from the slot.
+    ///
+    /// Removal listeners are not notified; use the cache handle of the method
+    /// (`<method>_cache()`) to clear with notifications.
+    pub fn clear(&self) {
+        self.map.write().clear();
+        self.order.lock().clear();
+        self.memory.reset();
+        self.eviction_state.clear();
+    }
+
+    /// Returns the hit/miss statistics of this slot.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &CacheStats {
+        &self.stats
+    }
+}
+
+impl<R: Clone + 'static> Default for CacheSlot<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Clone + 'static> Clone for CacheSlot<R> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<R: 'static> fmt::Debug for CacheSlot<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheSlot")
+            .field("len", &self.map.read().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slots_are_independent() {
+        let first = CacheSlot::<u32>::new();
+        let second = CacheSlot::<u32>::new();
+
+        first
+            .cache(None, None, EvictionPolicy::FIFO, None, None)
+            .insert("k", 1);
+
+        assert_eq!(first.len(), 1);
+        assert!(second.is_empty());
+        assert!(first.clone().is_empty());
+
+        first.clear();
+        assert!(first.is_empty());
+    }
+
+    #[test]
+    fn test_slot_honors_limit() {
+        let slot = CacheSlot::<u32>::default();
+        let cache = slot.cache(Some(2), None, EvictionPolicy::FIFO, None, None);
+
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("c", 3);
+
+        assert_eq!(slot.len(), 2);
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("c"), Some(3));
+    }
+}
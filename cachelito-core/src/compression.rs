@@ -0,0 +1,286 @@
+//! # Value Compression
+//!
+//! Large cached values (JSON documents, byte buffers, ...) can be stored compressed,
+//! trading CPU on insert and hit for memory.
+//!
+//! - [`Compression`] - The codec (`lz4` or `zstd` feature)
+//! - [`Compressible`] - Converts values to and from the bytes that get compressed
+//! - [`Compressed`] - The value stored in the cache: compressed bytes, or the value
+//!   itself when it is small or does not compress
+//!
+//! With `#[cache(compress = "lz4")]` the cache stores `Compressed<R>` instead of `R`:
+//! values are compressed on insert and decompressed on every hit. Memory limits and
+//! [`MemoryEstimator`] see the compressed size.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "lz4")]
+//! # {
+//! use cachelito_core::{Compressed, Compression};
+//!
+//! let json = "{\"name\": \"cachelito\"} ".repeat(100);
+//! let compressed = Compressed::new(json.clone(), Compression::Lz4, 1024);
+//! assert!(compressed.is_compressed());
+//! assert_eq!(compressed.decompress(), Some(json));
+//! # }
+//! ```
+
+use crate::MemoryEstimator;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Compression codec of a [`Compressed`] value.
+///
+/// Each codec is behind the feature of the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// LZ4 block format: fast, with a moderate ratio (`lz4` feature)
+    #[cfg(feature = "lz4")]
+    Lz4,
+    /// Zstandard at its default level: slower, with a better ratio (`zstd` feature)
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    /// Compresses `bytes`.
+    // Without a codec feature the enum has no variants, and the arguments go unused
+    #[cfg_attr(not(any(feature = "lz4", feature = "zstd")), allow(unused_variables))]
+    fn compress(self, bytes: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Some(lz4_flex::block::compress(bytes)),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => zstd::bulk::compress(bytes, 0).ok(),
+        }
+    }
+
+    /// Decompresses `bytes` into the `original_len` bytes they were compressed from.
+    #[cfg_attr(not(any(feature = "lz4", feature = "zstd")), allow(unused_variables))]
+    fn decompress(self, bytes: &[u8], original_len: usize) -> Option<Vec<u8>> {
+        match self {
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => lz4_flex::block::decompress(bytes, original_len).ok(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => zstd::bulk::decompress(bytes, original_len).ok(),
+        }
+    }
+}
+
+/// Converts cached values to and from the bytes a [`Compressed`] value holds.
+///
+/// Returning `None` from [`to_bytes`](Self::to_bytes) stores the value uncompressed;
+/// returning `None` from [`from_bytes`](Self::from_bytes) turns the hit into a miss.
+///
+/// Implemented for `String` and `Vec<u8>`, and for `Option` and `Result` of them,
+/// where `None` and `Err` values are stored uncompressed.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::Compressible;
+/// use std::borrow::Cow;
+///
+/// #[derive(Clone)]
+/// struct Page {
+///     html: String,
+/// }
+///
+/// impl Compressible for Page {
+///     fn to_bytes(&self) -> Option<Cow<'_, [u8]>> {
+///         Some(Cow::Borrowed(self.html.as_bytes()))
+///     }
+///
+///     fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+///         String::from_utf8(bytes).ok().map(|html| Page { html })
+///     }
+/// }
+/// ```
+pub trait Compressible: Sized {
+    /// Returns the bytes to compress, or `None` to store this value uncompressed.
+    fn to_bytes(&self) -> Option<Cow<'_, [u8]>>;
+
+    /// Rebuilds a value from the bytes returned by [`to_bytes`](Self::to_bytes).
+    fn from_bytes(bytes: Vec<u8>) -> Option<Self>;
+}
+
+impl Compressible for String {
+    fn to_bytes(&self) -> Option<Cow<'_, [u8]>> {
+        Some(Cow::Borrowed(self.as_bytes()))
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        String::from_utf8(bytes).ok()
+    }
+}
+
+impl Compressible for Vec<u8> {
+    fn to_bytes(&self) -> Option<Cow<'_, [u8]>> {
+        Some(Cow::Borrowed(self))
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        Some(bytes)
+    }
+}
+
+/// `Some` values are compressed, `None` is stored as is.
+impl<T: Compressible> Compressible for Option<T> {
+    fn to_bytes(&self) -> Option<Cow<'_, [u8]>> {
+        self.as_ref().and_then(T::to_bytes)
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        T::from_bytes(bytes).map(Some)
+    }
+}
+
+/// `Ok` values are compressed, `Err` values are stored as is.
+impl<T: Compressible, E> Compressible for Result<T, E> {
+    fn to_bytes(&self) -> Option<Cow<'_, [u8]>> {
+        self.as_ref().ok().and_then(T::to_bytes)
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        T::from_bytes(bytes).map(Ok)
+    }
+}
+
+/// A cached value, stored compressed when that saves memory.
+///
+/// Values whose bytes are shorter than the minimum size, or that do not shrink when
+/// compressed, are kept as they are. Compressed bytes are shared, so cloning the value
+/// on a hit does not copy them.
+#[derive(Clone, Debug)]
+pub struct Compressed<R> {
+    repr: Repr<R>,
+}
+
+#[derive(Clone, Debug)]
+enum Repr<R> {
+    Plain(R),
+    Packed {
+        codec: Compression,
+        bytes: Arc<[u8]>,
+        original_len: usize,
+    },
+}
+
+impl<R: Compressible> Compressed<R> {
+    /// Compresses `value` with `codec` if its bytes are at least `min_size` long.
+    pub fn new(value: R, codec: Compression, min_size: usize) -> Self {
+        let packed = value
+            .to_bytes()
+            .filter(|bytes| bytes.len() >= min_size)
+            .and_then(|bytes| {
+                let compressed = codec.compress(&bytes)?;
+                (compressed.len() < bytes.len()).then(|| Repr::Packed {
+                    codec,
+                    bytes: compressed.into(),
+                    original_len: bytes.len(),
+                })
+            });
+
+        Self {
+            repr: packed.unwrap_or(Repr::Plain(value)),
+        }
+    }
+
+    /// Returns the value, decompressing it if needed.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the bytes cannot be decompressed or converted back into a value.
+    pub fn decompress(self) -> Option<R> {
+        match self.repr {
+            Repr::Plain(value) => Some(value),
+            Repr::Packed {
+                codec,
+                bytes,
+                original_len,
+            } => R::from_bytes(codec.decompress(&bytes, original_len)?),
+        }
+    }
+}
+
+impl<R> Compressed<R> {
+    /// Returns `true` if the value is stored compressed.
+    pub fn is_compressed(&self) -> bool {
+        matches!(self.repr, Repr::Packed { .. })
+    }
+
+    /// Returns the original and compressed sizes of the value in bytes, if it is
+    /// stored compressed.
+    pub fn compressed_sizes(&self) -> Option<(usize, usize)> {
+        match &self.repr {
+            Repr::Plain(_) => None,
+            Repr::Packed {
+                bytes,
+                original_len,
+                ..
+            } => Some((*original_len, bytes.len())),
+        }
+    }
+}
+
+/// Compressed values count their compressed bytes.
+impl<R: MemoryEstimator> MemoryEstimator for Compressed<R> {
+    fn estimate_memory(&self) -> usize {
+        match &self.repr {
+            Repr::Plain(value) => value.estimate_memory(),
+            Repr::Packed { bytes, .. } => std::mem::size_of::<Self>() + bytes.len(),
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "lz4", feature = "zstd")))]
+mod tests {
+    use super::*;
+
+    fn codecs() -> Vec<Compression> {
+        vec![
+            #[cfg(feature = "lz4")]
+            Compression::Lz4,
+            #[cfg(feature = "zstd")]
+            Compression::Zstd,
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = "cachelito ".repeat(500);
+        for codec in codecs() {
+            let compressed = Compressed::new(text.clone(), codec, 1024);
+            let (original, stored) = compressed.compressed_sizes().unwrap();
+            assert_eq!(original, text.len());
+            assert!(stored < original);
+            assert!(compressed.estimate_memory() < text.estimate_memory());
+            assert_eq!(compressed.decompress(), Some(text.clone()));
+        }
+    }
+
+    #[test]
+    fn test_small_and_incompressible_values_stay_plain() {
+        for codec in codecs() {
+            let small = Compressed::new("tiny".to_string(), codec, 1024);
+            assert!(!small.is_compressed());
+            assert_eq!(small.decompress(), Some("tiny".to_string()));
+
+            let noise: Vec<u8> = (0..4096).map(|_| fastrand::u8(..)).collect();
+            let random = Compressed::new(noise.clone(), codec, 0);
+            assert!(!random.is_compressed());
+            assert_eq!(random.decompress(), Some(noise));
+        }
+    }
+
+    #[test]
+    fn test_err_values_stay_plain() {
+        for codec in codecs() {
+            let err: Result<String, String> = Err("x".repeat(4096));
+            let compressed = Compressed::new(err.clone(), codec, 0);
+            assert!(!compressed.is_compressed());
+            assert_eq!(compressed.decompress(), Some(err));
+        }
+    }
+}
@@ -3,7 +3,8 @@ use std::cmp::PartialEq;
 /// Represents the policy used for evicting elements from a cache when it reaches its limit.
 ///
 /// Eviction policies determine which cached entry should be removed when the cache is full
-/// and a new entry needs to be added.
+/// and a new entry needs to be added. Each policy is implemented by an
+/// [`EvictionStrategy`](crate::EvictionStrategy); custom strategies take precedence over it.
 ///
 /// # Variants
 ///
@@ -52,6 +53,32 @@ use std::cmp::PartialEq;
 ///   - O(n) operations for eviction due to scoring
 ///   - Requires TTL to be configured for optimal behavior
 ///
+/// * `GDSF` - **Greedy-Dual-Size-Frequency** eviction policy
+///   - Elements are evicted based on their access frequency, compute cost and size
+///   - Priority formula: `L + frequency × cost / size`, the lowest priority is evicted first
+///   - `L` is the priority of the last evicted entry: entries that are no longer hit age out
+///   - Cost is the time the cached function took to compute the value
+///   - Size is the estimated memory of the value, measured in caches with `max_memory`
+///   - Keeps small, popular and expensive entries, evicts large, cold and cheap ones
+///   - O(n) operations for eviction due to scoring
+///
+/// * `SIEVE` - **SIEVE** eviction policy
+///   - Elements are kept in insertion order, and a hand sweeps them from the oldest
+///   - Each cache hit only sets a visited bit: no reordering, O(1) cache hits
+///   - The hand clears the visited bits it passes, and evicts the first unvisited entry
+///   - The next eviction resumes where the hand stopped
+///   - Evicts new entries accessed only once quickly, keeps popular ones in place
+///   - O(n) worst case eviction, when the hand sweeps many visited entries
+///
+/// * `S3FIFO` - **Simple, Scalable, Static FIFO** eviction policy
+///   - A small FIFO queue (about 10% of the entries) and a main FIFO queue
+///   - Each cache hit only increments a counter capped at 3: no reordering, O(1) cache hits
+///   - New entries accessed at most once are evicted from the small queue, others are moved
+///     to the main queue, where entries get one more chance per access
+///   - A ghost queue remembers the keys evicted from the small queue: inserted again, they
+///     enter the main queue
+///   - O(n) eviction, to find the oldest entry of each queue
+///
 /// # Examples
 ///
 /// ```
@@ -64,6 +91,9 @@ use std::cmp::PartialEq;
 /// let arc = EvictionPolicy::ARC;
 /// let random = EvictionPolicy::Random;
 /// let tlru = EvictionPolicy::TLRU;
+/// let gdsf = EvictionPolicy::GDSF;
+/// let sieve = EvictionPolicy::SIEVE;
+/// let s3fifo = EvictionPolicy::S3FIFO;
 ///
 /// // Using default (LRU)
 /// let default_policy = EvictionPolicy::default();
@@ -84,6 +114,9 @@ use std::cmp::PartialEq;
 /// | ARC    | O(n)     | O(n)      | O(1)       | Mixed workloads, self-tuning |
 /// | Random | O(1)     | O(1)      | O(1)       | Baseline, unpredictable patterns |
 /// | TLRU   | O(n)     | O(n)      | O(1)       | Time-sensitive, mixed access patterns |
+/// | GDSF   | O(n)     | O(1)      | O(1)       | Entries of varying size and compute cost |
+/// | SIEVE  | O(n)     | O(1)      | O(1)       | Web workloads, popular and one-hit entries |
+/// | S3FIFO | O(n)     | O(1)      | O(1)       | Web workloads, many one-hit entries |
 ///
 /// # Derives
 ///
@@ -101,6 +134,9 @@ pub enum EvictionPolicy {
     ARC,
     Random,
     TLRU,
+    GDSF,
+    SIEVE,
+    S3FIFO,
 }
 
 impl EvictionPolicy {
@@ -135,11 +171,14 @@ impl EvictionPolicy {
     /// assert!(EvictionPolicy::is_valid("random"));
     /// assert!(EvictionPolicy::is_valid("lfu"));
     /// assert!(EvictionPolicy::is_valid("arc"));
+    /// assert!(EvictionPolicy::is_valid("gdsf"));
+    /// assert!(EvictionPolicy::is_valid("sieve"));
+    /// assert!(EvictionPolicy::is_valid("s3fifo"));
     /// ```
     pub fn is_valid(p: &str) -> bool {
         matches!(
             p.to_lowercase().as_str(),
-            "fifo" | "lru" | "lfu" | "arc" | "random" | "tlru"
+            "fifo" | "lru" | "lfu" | "arc" | "random" | "tlru" | "gdsf" | "sieve" | "s3fifo"
         )
     }
 }
@@ -155,6 +194,10 @@ impl EvictionPolicy {
 /// - `"lfu"` or `"LFU"` → `EvictionPolicy::LFU`
 /// - `"arc"` or `"ARC"` → `EvictionPolicy::ARC`
 /// - `"random"` or `"RANDOM"` → `EvictionPolicy::Random`
+/// - `"tlru"` or `"TLRU"` → `EvictionPolicy::TLRU`
+/// - `"gdsf"` or `"GDSF"` → `EvictionPolicy::GDSF`
+/// - `"sieve"` or `"SIEVE"` → `EvictionPolicy::SIEVE`
+/// - `"s3fifo"` or `"S3FIFO"` → `EvictionPolicy::S3FIFO`
 /// - Any other value → `EvictionPolicy::LRU` (default)
 ///
 /// # Examples
@@ -177,6 +220,15 @@ impl EvictionPolicy {
 /// let random: EvictionPolicy = "random".into();
 /// assert_eq!(random, EvictionPolicy::Random);
 ///
+/// let gdsf: EvictionPolicy = "gdsf".into();
+/// assert_eq!(gdsf, EvictionPolicy::GDSF);
+///
+/// let sieve: EvictionPolicy = "sieve".into();
+/// assert_eq!(sieve, EvictionPolicy::SIEVE);
+///
+/// let s3fifo: EvictionPolicy = "s3fifo".into();
+/// assert_eq!(s3fifo, EvictionPolicy::S3FIFO);
+///
 /// let unknown: EvictionPolicy = "unknown".into();
 /// assert_eq!(unknown, EvictionPolicy::LRU); // defaults to LRU
 /// ```
@@ -188,6 +240,9 @@ impl From<&str> for EvictionPolicy {
             "arc" => EvictionPolicy::ARC,
             "random" => EvictionPolicy::Random,
             "tlru" => EvictionPolicy::TLRU,
+            "gdsf" => EvictionPolicy::GDSF,
+            "sieve" => EvictionPolicy::SIEVE,
+            "s3fifo" => EvictionPolicy::S3FIFO,
             _ => EvictionPolicy::LRU,
         }
     }
@@ -195,14 +250,17 @@ impl From<&str> for EvictionPolicy {
 
 impl PartialEq for EvictionPolicy {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (EvictionPolicy::FIFO, EvictionPolicy::FIFO) => true,
-            (EvictionPolicy::LRU, EvictionPolicy::LRU) => true,
-            (EvictionPolicy::LFU, EvictionPolicy::LFU) => true,
-            (EvictionPolicy::ARC, EvictionPolicy::ARC) => true,
-            (EvictionPolicy::Random, EvictionPolicy::Random) => true,
-            (EvictionPolicy::TLRU, EvictionPolicy::TLRU) => true,
-            _ => false,
-        }
+        matches!(
+            (self, other),
+            (EvictionPolicy::FIFO, EvictionPolicy::FIFO)
+                | (EvictionPolicy::LRU, EvictionPolicy::LRU)
+                | (EvictionPolicy::LFU, EvictionPolicy::LFU)
+                | (EvictionPolicy::ARC, EvictionPolicy::ARC)
+                | (EvictionPolicy::Random, EvictionPolicy::Random)
+                | (EvictionPolicy::TLRU, EvictionPolicy::TLRU)
+                | (EvictionPolicy::GDSF, EvictionPolicy::GDSF)
+                | (EvictionPolicy::SIEVE, EvictionPolicy::SIEVE)
+                | (EvictionPolicy::S3FIFO, EvictionPolicy::S3FIFO)
+        )
     }
 }
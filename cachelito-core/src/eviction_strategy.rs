@@ -0,0 +1,739 @@
+//! # Eviction Strategies
+//!
+//! [`EvictionStrategy`] is the extension point behind the eviction policies: it is told
+//! about insertions, hits and removals, and picks the entry to evict when a cache is over
+//! its `limit` or `max_memory`.
+//!
+//! Each [`EvictionPolicy`](crate::EvictionPolicy) is implemented by one of the strategies
+//! of this module ([`FifoStrategy`], [`LruStrategy`], [`LfuStrategy`], [`ArcStrategy`],
+//! [`RandomStrategy`], [`TlruStrategy`], [`GdsfStrategy`], [`SieveStrategy`] and
+//! [`S3FifoStrategy`]). Custom strategies are set with
+//! `policy = path::to::Strategy` in the macros, or `with_eviction_strategy()` on the
+//! cache structs, and take precedence over the policy of the cache.
+//!
+//! # Examples
+//!
+//! Evicting the entries with the lowest business priority first:
+//!
+//! ```
+//! use cachelito_core::{EvictionCandidates, EvictionStrategy};
+//!
+//! #[derive(Clone)]
+//! struct Offer {
+//!     priority: u8,
+//! }
+//!
+//! struct ByPriority;
+//!
+//! impl EvictionStrategy<Offer> for ByPriority {
+//!     fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, Offer>) -> Option<usize> {
+//!         candidates.lowest_score(|entry, _| entry.value.priority as f64)
+//!     }
+//! }
+//! ```
+
+use crate::policy_engine::EntryStore;
+use crate::{CacheEntry, RemovalCause};
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+/// Decides which entry a cache evicts when it is over its limits.
+///
+/// The cache keeps its keys in an order queue: keys are pushed to the back when inserted,
+/// and moved to the back on hits if [`promotes_on_access`](Self::promotes_on_access)
+/// returns `true`. When an entry must be evicted, the cache calls
+/// [`choose_victim`](Self::choose_victim) with a view of that queue.
+///
+/// The hooks are called while the cache is locked (or its `RefCell`s borrowed), so they
+/// must not access the cache. They take `&self`: strategies keeping state of their own
+/// need interior mutability, and must be `Send + Sync` since global caches share them
+/// between threads.
+///
+/// The value type `R` is the type stored in the cache: `Arc<T>` with `arc = true`, and
+/// `Compressed<T>` with `compress`.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::{CacheEntry, EvictionCandidates, EvictionStrategy};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// /// LRU, counting the hits of the cache
+/// #[derive(Default)]
+/// struct CountingLru {
+///     hits: AtomicUsize,
+/// }
+///
+/// impl<R> EvictionStrategy<R> for CountingLru {
+///     fn on_access(&self, _key: &str, _entry: &mut CacheEntry<R>) {
+///         self.hits.fetch_add(1, Ordering::Relaxed);
+///     }
+///
+///     fn choose_victim(&self, _candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+///         Some(0)
+///     }
+///
+///     fn promotes_on_access(&self) -> bool {
+///         true
+///     }
+/// }
+/// ```
+pub trait EvictionStrategy<R>: Send + Sync {
+    /// Called when `entry` is stored under `key`, before it is inserted.
+    ///
+    /// Replacing the value of a key calls `on_insert` with the new entry, and not
+    /// [`on_remove`](Self::on_remove) with the old one.
+    fn on_insert(&self, _key: &str, _entry: &mut CacheEntry<R>) {}
+
+    /// Called on every hit on `entry`, if [`updates_on_access`](Self::updates_on_access)
+    /// returns `true`.
+    ///
+    /// Caches with buffered access replay hits in batches, and may drop some.
+    fn on_access(&self, _key: &str, _entry: &mut CacheEntry<R>) {}
+
+    /// Called on every hit on the entry stored under `key`, without access to the entry.
+    ///
+    /// Unlike [`on_access`](Self::on_access), it is not buffered and does not lock the
+    /// entries of the cache, which may be read concurrently: strategies keeping their own
+    /// per-key state (e.g. atomic bits) use it, and return `false` from
+    /// [`updates_on_access`](Self::updates_on_access).
+    fn on_hit(&self, _key: &str) {}
+
+    /// Called when the entry stored under `key` leaves the cache for any reason other
+    /// than being replaced: eviction, expiration, invalidation or explicit removal.
+    fn on_remove(&self, _key: &str, _value: &R, _cause: RemovalCause) {}
+
+    /// Returns the position in the order queue of the entry to evict.
+    ///
+    /// `None`, or a position out of range, evicts the front of the queue. Positions of
+    /// keys whose entry was already removed are dropped, and the strategy is asked again.
+    ///
+    /// Strategies giving entries a second chance can update them and move their key to
+    /// the back of the queue meanwhile, through [`EvictionCandidates::update`] and
+    /// [`EvictionCandidates::requeue`].
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize>;
+
+    /// Returns `true` if hits move their key to the back of the order queue.
+    fn promotes_on_access(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if hits must call [`on_access`](Self::on_access).
+    ///
+    /// Strategies that do not need it should return `false`, to spare hits from locking
+    /// the entries of the cache.
+    fn updates_on_access(&self) -> bool {
+        true
+    }
+}
+
+/// The keys of a cache that can be evicted, in the order of its order queue, as passed
+/// to [`EvictionStrategy::choose_victim`].
+///
+/// The queue may still hold the keys of entries removed elsewhere: their entry is `None`.
+pub struct EvictionCandidates<'a, R> {
+    store: &'a mut dyn EntryStore<R>,
+    order: &'a mut VecDeque<String>,
+}
+
+impl<'a, R> EvictionCandidates<'a, R> {
+    pub(crate) fn new(store: &'a mut dyn EntryStore<R>, order: &'a mut VecDeque<String>) -> Self {
+        Self { store, order }
+    }
+
+    /// Returns the number of keys in the queue.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Returns the key at `position`, from 0 (front of the queue).
+    pub fn key(&self, position: usize) -> Option<&str> {
+        self.order.get(position).map(String::as_str)
+    }
+
+    /// Calls `f` with the entry of the key at `position`, if it is still in the cache.
+    pub fn entry<T>(&self, position: usize, f: impl FnOnce(&CacheEntry<R>) -> T) -> Option<T> {
+        let key = self.order.get(position)?;
+        let mut f = Some(f);
+        let mut result = None;
+        self.store.inspect(key, &mut |entry| {
+            if let Some(f) = f.take() {
+                result = Some(f(entry));
+            }
+        });
+        result
+    }
+
+    /// Calls `f` with the mutable entry of the key at `position`, if it is still in the cache.
+    pub fn update<T>(
+        &mut self,
+        position: usize,
+        f: impl FnOnce(&mut CacheEntry<R>) -> T,
+    ) -> Option<T> {
+        let key = self.order.get(position)?;
+        let mut f = Some(f);
+        let mut result = None;
+        self.store.update(key, &mut |entry| {
+            if let Some(f) = f.take() {
+                result = Some(f(entry));
+            }
+        });
+        result
+    }
+
+    /// Moves the key at `position` to the back of the queue, as if it was inserted again.
+    ///
+    /// The keys behind it move forward by one position. Returns `false` if `position` is
+    /// out of range.
+    pub fn requeue(&mut self, position: usize) -> bool {
+        match self.order.remove(position) {
+            Some(key) => {
+                self.order.push_back(key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the position of the live entry with the lowest `score`, the first one on ties.
+    ///
+    /// `score` receives the entry and its position weight: `len()` for the front of the
+    /// queue, down to 1 for its back.
+    pub fn lowest_score(&self, mut score: impl FnMut(&CacheEntry<R>, f64) -> f64) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (position, key) in self.order.iter().enumerate() {
+            let position_weight = (self.order.len() - position) as f64;
+            let mut entry_score = None;
+            self.store.inspect(key, &mut |entry| {
+                entry_score = Some(score(entry, position_weight));
+            });
+            let Some(entry_score) = entry_score else {
+                continue;
+            };
+            if best.map_or(true, |(_, best_score)| entry_score < best_score) {
+                best = Some((position, entry_score));
+            }
+        }
+        best.map(|(position, _)| position)
+    }
+}
+
+/// [`EvictionPolicy::FIFO`](crate::EvictionPolicy::FIFO): evicts the oldest insertion.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FifoStrategy;
+
+impl<R> EvictionStrategy<R> for FifoStrategy {
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        (!candidates.is_empty()).then_some(0)
+    }
+
+    fn updates_on_access(&self) -> bool {
+        false
+    }
+}
+
+/// [`EvictionPolicy::LRU`](crate::EvictionPolicy::LRU): evicts the least recently used entry.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LruStrategy;
+
+impl<R> EvictionStrategy<R> for LruStrategy {
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        (!candidates.is_empty()).then_some(0)
+    }
+
+    fn promotes_on_access(&self) -> bool {
+        true
+    }
+
+    fn updates_on_access(&self) -> bool {
+        false
+    }
+}
+
+/// [`EvictionPolicy::LFU`](crate::EvictionPolicy::LFU): evicts the least frequently used
+/// entry, the oldest one on ties.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LfuStrategy;
+
+impl<R> EvictionStrategy<R> for LfuStrategy {
+    fn on_access(&self, _key: &str, entry: &mut CacheEntry<R>) {
+        entry.increment_frequency();
+    }
+
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        candidates.lowest_score(|entry, _| entry.frequency as f64)
+    }
+}
+
+/// [`EvictionPolicy::ARC`](crate::EvictionPolicy::ARC): evicts the entry with the lowest
+/// `frequency × position_weight`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ArcStrategy;
+
+impl<R> EvictionStrategy<R> for ArcStrategy {
+    fn on_access(&self, _key: &str, entry: &mut CacheEntry<R>) {
+        entry.increment_frequency();
+    }
+
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        candidates.lowest_score(|entry, position_weight| entry.frequency as f64 * position_weight)
+    }
+
+    fn promotes_on_access(&self) -> bool {
+        true
+    }
+}
+
+/// [`EvictionPolicy::Random`](crate::EvictionPolicy::Random): evicts any entry.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomStrategy;
+
+impl<R> EvictionStrategy<R> for RandomStrategy {
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        (!candidates.is_empty()).then(|| fastrand::usize(..candidates.len()))
+    }
+
+    fn updates_on_access(&self) -> bool {
+        false
+    }
+}
+
+/// [`EvictionPolicy::TLRU`](crate::EvictionPolicy::TLRU): evicts the entry with the lowest
+/// `frequency^frequency_weight × position_weight × age_factor`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TlruStrategy {
+    /// TTL of the cache, for the entries without one of their own
+    pub ttl: Option<u64>,
+    /// Weight of the frequency in the score (1.0 when `None`)
+    pub frequency_weight: Option<f64>,
+}
+
+impl<R> EvictionStrategy<R> for TlruStrategy {
+    fn on_access(&self, _key: &str, entry: &mut CacheEntry<R>) {
+        entry.increment_frequency();
+    }
+
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        candidates.lowest_score(|entry, position_weight| {
+            tlru_score(entry, position_weight, self.ttl, self.frequency_weight)
+        })
+    }
+
+    fn promotes_on_access(&self) -> bool {
+        true
+    }
+}
+
+/// [`EvictionPolicy::GDSF`](crate::EvictionPolicy::GDSF): Greedy Dual-Size Frequency.
+/// Evicts the entry with the lowest priority `L + frequency × cost / size`, the oldest one
+/// on ties.
+///
+/// `L` is the clock of the cache: the priority of the last entry evicted. Priorities are
+/// computed when entries are inserted and hit, so an entry that is no longer hit falls
+/// behind the clock as it rises, however hot it was. The frequency counts the request
+/// that inserted the entry.
+///
+/// The cost of an entry is the time its value took to compute, and its size the estimated
+/// memory of its value. Both count as 1 when unknown: entries inserted outside
+/// [`with_compute_cost`](crate::with_compute_cost), or into caches without `max_memory`.
+///
+/// The clock and priorities are kept in the [`EvictionState`] of the cache.
+#[derive(Debug, Default)]
+pub struct GdsfStrategy {
+    pub(crate) state: Mutex<GdsfClock>,
+}
+
+/// The clock `L` of a [`GdsfStrategy`], and the priorities computed from it.
+#[derive(Debug, Default)]
+pub(crate) struct GdsfClock {
+    /// Priority of the last entry evicted
+    pub(crate) clock: f64,
+    /// Priority of each entry, by key
+    priorities: HashMap<String, f64>,
+}
+
+impl GdsfStrategy {
+    /// Sets the priority of `entry` to `L + frequency × cost / size`.
+    fn prioritize<R>(&self, key: &str, entry: &CacheEntry<R>) {
+        let mut state = self.state.lock();
+        let priority = state.clock + gdsf_score(entry);
+        match state.priorities.get_mut(key) {
+            Some(current) => *current = priority,
+            None => {
+                state.priorities.insert(key.to_string(), priority);
+            }
+        }
+    }
+
+    /// Resets the clock and forgets every priority.
+    fn clear(&self) {
+        *self.state.lock() = GdsfClock::default();
+    }
+}
+
+impl<R> EvictionStrategy<R> for GdsfStrategy {
+    fn on_insert(&self, key: &str, entry: &mut CacheEntry<R>) {
+        self.prioritize(key, entry);
+    }
+
+    fn on_access(&self, key: &str, entry: &mut CacheEntry<R>) {
+        entry.increment_frequency();
+        self.prioritize(key, entry);
+    }
+
+    fn on_remove(&self, key: &str, _value: &R, _cause: RemovalCause) {
+        self.state.lock().priorities.remove(key);
+    }
+
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        let mut state = self.state.lock();
+        let mut victim: Option<(usize, f64)> = None;
+        for position in 0..candidates.len() {
+            let Some(score) = candidates.entry(position, gdsf_score) else {
+                continue;
+            };
+            // Entries inserted before the state was attached have no priority yet
+            let priority = candidates
+                .key(position)
+                .and_then(|key| state.priorities.get(key))
+                .copied()
+                .unwrap_or(state.clock + score);
+            if victim.map_or(true, |(_, lowest)| priority < lowest) {
+                victim = Some((position, priority));
+            }
+        }
+        let (position, priority) = victim?;
+        state.clock = priority;
+        Some(position)
+    }
+}
+
+/// [`EvictionPolicy::SIEVE`](crate::EvictionPolicy::SIEVE): a hand sweeps the queue from its
+/// front, clearing the visited bit of the entries it passes, and evicts the first entry
+/// that was not visited since.
+///
+/// Hits only set the visited bit of their key, in [`on_hit`](EvictionStrategy::on_hit):
+/// keys are never moved, and entries are not locked. The hand stays where it evicted, and
+/// the next eviction resumes from there; once it reaches the back of the queue, it starts
+/// over from the front. The newest entry is not a candidate, so that an entry is never
+/// evicted by its own insertion.
+///
+/// The hand and visited bits are kept in the [`EvictionState`] of the cache.
+#[derive(Debug, Default)]
+pub struct SieveStrategy {
+    /// Position of the hand in the order queue
+    hand: AtomicUsize,
+    /// Visited bit of each entry, by key
+    visited: RwLock<HashMap<String, AtomicBool>>,
+}
+
+impl SieveStrategy {
+    /// Resets the hand and forgets every visited bit.
+    fn clear(&self) {
+        self.hand.store(0, Ordering::Relaxed);
+        self.visited.write().clear();
+    }
+}
+
+impl<R> EvictionStrategy<R> for SieveStrategy {
+    fn on_insert(&self, key: &str, _entry: &mut CacheEntry<R>) {
+        let mut visited = self.visited.write();
+        match visited.get(key) {
+            // Replaced values are stored as new entries, at the back of the queue
+            Some(bit) => bit.store(false, Ordering::Relaxed),
+            None => {
+                visited.insert(key.to_string(), AtomicBool::new(false));
+            }
+        }
+    }
+
+    fn on_hit(&self, key: &str) {
+        if let Some(bit) = self.visited.read().get(key) {
+            bit.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn on_remove(&self, key: &str, _value: &R, _cause: RemovalCause) {
+        self.visited.write().remove(key);
+    }
+
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        let newest = candidates.len().checked_sub(1)?;
+        let visited = self.visited.read();
+        let mut hand = self.hand.load(Ordering::Relaxed);
+        // Two sweeps at most: the first one clears every visited bit
+        for _ in 0..2 * newest {
+            if hand >= newest {
+                hand = 0;
+            }
+            let was_visited = candidates
+                .key(hand)
+                .and_then(|key| visited.get(key))
+                .is_some_and(|bit| bit.swap(false, Ordering::Relaxed));
+            if !was_visited {
+                // The next key takes the position of the victim
+                self.hand.store(hand, Ordering::Relaxed);
+                return Some(hand);
+            }
+            hand += 1;
+        }
+        self.hand.store(hand, Ordering::Relaxed);
+        None
+    }
+
+    fn updates_on_access(&self) -> bool {
+        false
+    }
+}
+
+/// Highest value of the S3-FIFO access counter.
+const S3FIFO_MAX_FREQUENCY: u8 = 3;
+
+/// [`EvictionPolicy::S3FIFO`](crate::EvictionPolicy::S3FIFO): a small FIFO queue holding
+/// about 10% of the entries filters out those accessed at most once, and a main FIFO queue
+/// keeps the others.
+///
+/// Hits only increment a counter capped at 3, in [`on_hit`](EvictionStrategy::on_hit): keys
+/// are never moved, and entries are not locked.
+/// - New entries enter the small queue. When it is over its share, its oldest entry is
+///   evicted if it was accessed at most once since its insertion, or moved to the main
+///   queue otherwise
+/// - Otherwise, the oldest entry of the main queue is evicted if its counter is 0, or
+///   decremented and moved to the back of the main queue
+/// - Keys evicted from the small queue are remembered in a ghost queue, as long as the
+///   cache has entries. Inserted again while remembered, they enter the main queue
+///
+/// Both queues share the order queue of the cache. The newest entry is not a candidate, so
+/// that an entry is never evicted by its own insertion.
+///
+/// The queue of each entry, the counters and the ghost queue are kept in the
+/// [`EvictionState`] of the cache.
+#[derive(Debug, Default)]
+pub struct S3FifoStrategy {
+    /// Queue and access counter of each entry, by key
+    entries: RwLock<HashMap<String, S3FifoEntry>>,
+    /// Keys recently evicted from the small queue
+    ghost: Mutex<GhostQueue>,
+}
+
+/// The S3-FIFO bookkeeping of one entry.
+#[derive(Debug, Default)]
+struct S3FifoEntry {
+    /// Hits since the insertion (small queue) or the last pass (main queue), capped
+    frequency: AtomicU8,
+    /// `true` once moved to the main queue
+    main: AtomicBool,
+}
+
+/// Keys evicted from the S3-FIFO small queue, oldest first.
+#[derive(Debug, Default)]
+struct GhostQueue {
+    order: VecDeque<String>,
+    keys: HashSet<String>,
+}
+
+impl GhostQueue {
+    /// Remembers `key`, forgetting the oldest keys beyond `capacity`.
+    fn push(&mut self, key: &str, capacity: usize) {
+        if self.keys.insert(key.to_string()) {
+            self.order.push_back(key.to_string());
+        }
+        while self.order.len() > capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+    }
+
+    /// Forgets `key`, returning `true` if it was remembered.
+    fn take(&mut self, key: &str) -> bool {
+        let found = self.keys.remove(key);
+        if found {
+            self.order.retain(|k| k != key);
+        }
+        found
+    }
+}
+
+impl S3FifoStrategy {
+    /// Empties both queues and the ghost queue.
+    fn clear(&self) {
+        self.entries.write().clear();
+        *self.ghost.lock() = GhostQueue::default();
+    }
+}
+
+impl<R> EvictionStrategy<R> for S3FifoStrategy {
+    fn on_insert(&self, key: &str, _entry: &mut CacheEntry<R>) {
+        let admitted = self.ghost.lock().take(key);
+        // Replaced values stay in their queue
+        self.entries
+            .write()
+            .entry(key.to_string())
+            .or_insert_with(|| S3FifoEntry {
+                frequency: AtomicU8::new(0),
+                main: AtomicBool::new(admitted),
+            });
+    }
+
+    fn on_hit(&self, key: &str) {
+        if let Some(entry) = self.entries.read().get(key) {
+            let _ =
+                entry
+                    .frequency
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |frequency| {
+                        (frequency < S3FIFO_MAX_FREQUENCY).then_some(frequency + 1)
+                    });
+        }
+    }
+
+    fn on_remove(&self, key: &str, _value: &R, cause: RemovalCause) {
+        let mut entries = self.entries.write();
+        let Some(entry) = entries.remove(key) else {
+            return;
+        };
+        let capacity = entries.len().max(1);
+        drop(entries);
+        if !entry.main.into_inner()
+            && matches!(cause, RemovalCause::Capacity | RemovalCause::Memory)
+        {
+            self.ghost.lock().push(key, capacity);
+        }
+    }
+
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        // Requeued keys move the others forward: the newest one is tracked by key
+        let newest = candidates
+            .key(candidates.len().checked_sub(1)?)?
+            .to_string();
+        let entries = self.entries.read();
+        // Keys without bookkeeping (inserted before the state was attached) are in the
+        // small queue, and were never accessed
+        let entry = |candidates: &EvictionCandidates<'_, R>, position: usize| {
+            candidates.key(position).and_then(|key| entries.get(key))
+        };
+        let in_queue = |candidates: &EvictionCandidates<'_, R>, position: usize, main: bool| {
+            candidates.key(position) != Some(newest.as_str())
+                && entry(candidates, position)
+                    .is_some_and(|entry| entry.main.load(Ordering::Relaxed))
+                    == main
+        };
+
+        let small_share = (candidates.len() + 9) / 10;
+        let mut small = (0..candidates.len())
+            .filter(|&position| in_queue(candidates, position, false))
+            .count();
+        let mut main = small < small_share;
+        let mut position = 0;
+        let mut switched = false;
+        loop {
+            // The oldest entry of the queue, at or after the entries already requeued
+            let Some(found) = (position..candidates.len()).find(|&p| in_queue(candidates, p, main))
+            else {
+                if switched {
+                    return None;
+                }
+                (main, position, switched) = (!main, 0, true);
+                continue;
+            };
+            let Some(state) = entry(candidates, found) else {
+                return Some(found);
+            };
+            let frequency = state.frequency.load(Ordering::Relaxed);
+            if main {
+                if frequency == 0 {
+                    return Some(found);
+                }
+                state.frequency.store(frequency - 1, Ordering::Relaxed);
+            } else {
+                if frequency <= 1 {
+                    return Some(found);
+                }
+                state.frequency.store(0, Ordering::Relaxed);
+                state.main.store(true, Ordering::Relaxed);
+            }
+            candidates.requeue(found);
+            position = found;
+            if !main {
+                small -= 1;
+                if small < small_share {
+                    (main, position) = (true, 0);
+                }
+            }
+        }
+    }
+
+    fn updates_on_access(&self) -> bool {
+        false
+    }
+}
+
+/// The state of the built-in strategies of a cache: the clock of [`GdsfStrategy`], the
+/// hand of [`SieveStrategy`] and the queues of [`S3FifoStrategy`].
+///
+/// Each cache needs its own: the macros keep one per cache (per shard of sharded caches,
+/// per thread of thread-local ones), attached with `with_eviction_state()` on the cache
+/// structs. Caches without one give these strategies a fresh state on every call, which
+/// degrades GDSF to `frequency × cost / size` without aging, and SIEVE and S3-FIFO to
+/// FIFO. Stateless policies, and custom strategies, ignore it.
+///
+/// # Examples
+///
+/// ```ignore
+/// static STATE: Lazy<EvictionState> = Lazy::new(EvictionState::new);
+///
+/// let cache = GlobalCache::new(/* ... */).with_eviction_state(&STATE);
+/// ```
+#[derive(Debug, Default)]
+pub struct EvictionState {
+    pub(crate) gdsf: GdsfStrategy,
+    pub(crate) sieve: SieveStrategy,
+    pub(crate) s3fifo: S3FifoStrategy,
+}
+
+impl EvictionState {
+    /// Creates an empty state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets everything known about the entries, for caches cleared without
+    /// notifications.
+    pub(crate) fn clear(&self) {
+        self.gdsf.clear();
+        self.sieve.clear();
+        self.s3fifo.clear();
+    }
+}
+
+/// Returns the GDSF score of `entry`: `frequency × cost / size`, without the clock.
+///
+/// New entries have a frequency of 0, counted as 1 so that their cost still matters.
+pub(crate) fn gdsf_score<R>(entry: &CacheEntry<R>) -> f64 {
+    let frequency = entry.frequency.saturating_add(1) as f64;
+    let cost = entry.cost.as_nanos().max(1) as f64;
+    frequency * cost / entry.size.max(1) as f64
+}
+
+/// Returns the TLRU score of `entry`: `frequency^weight × position_weight × age_factor`.
+///
+/// Entries close to expiration get lower scores, and are evicted first. Entries never
+/// accessed score 0, whatever the weight.
+pub(crate) fn tlru_score<R>(
+    entry: &CacheEntry<R>,
+    position_weight: f64,
+    ttl: Option<u64>,
+    frequency_weight: Option<f64>,
+) -> f64 {
+    let frequency = match frequency_weight {
+        Some(weight) if entry.frequency > 0 => (entry.frequency as f64).powf(weight),
+        _ => entry.frequency as f64,
+    };
+    frequency * position_weight * entry.age_factor(ttl)
+}
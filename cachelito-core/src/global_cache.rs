@@ -1,14 +1,18 @@
-use crate::{CacheEntry, EvictionPolicy};
-use once_cell::sync::Lazy;
-use parking_lot::lock_api::MutexGuard;
-use parking_lot::{Mutex, RawMutex, RwLock};
+use crate::batch::Batch;
+use crate::policy_engine::{Lookup, PolicyEngine};
+use crate::removal::PendingRemovals;
+use crate::shards::shard_share;
+use crate::{
+    AccessBuffer, CacheEntry, CacheShards, CacheableKey, EvictionPolicy, EvictionState,
+    EvictionStrategy, MemoryBudget, MemoryUsage, RemovalCause, RemovalListener, SecondaryCache,
+    TimerWheel,
+};
+use parking_lot::{Mutex, RwLock};
 use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug;
+use std::hash::Hash;
 
-use crate::utils::{
-    find_arc_eviction_key, find_min_frequency_key, find_tlru_eviction_key, move_key_to_end,
-    remove_key_from_global_cache,
-};
+use crate::utils::{move_key_to_end, shrink_count};
 #[cfg(feature = "stats")]
 use crate::CacheStats;
 
@@ -41,12 +45,17 @@ use crate::CacheStats;
 /// - **Statistics**: Optional cache hit/miss tracking (with `stats` feature)
 /// - **Frequency tracking**: For LFU, ARC, and TLRU policies
 /// - **Memory estimation**: Support for memory-based eviction (requires `MemoryEstimator`)
+/// - **Removal listener**: Optional callback notified with the [`RemovalCause`] of every removal
+/// - **Sharding**: Optional partitioning into independently locked [`CacheShards`]
+/// - **Buffered access**: Optional batching of hit bookkeeping through an [`AccessBuffer`]
+/// - **Expiration tracking**: Optional [`TimerWheel`] so that purging skips unexpired entries
+/// - **Memory accounting**: Optional running total of entry sizes in a [`MemoryUsage`]
 ///
 /// # Cache Entry Structure
 ///
 /// Cache entries are stored as `CacheEntry<R>` which contains:
 /// - `value`: The cached value of type R
-/// - `timestamp`: Unix timestamp when the entry was created (for TTL and TLRU age factor)
+/// - `inserted_at`: Monotonic `Instant` when the entry was created (for TTL and TLRU age factor)
 /// - `frequency`: Access counter for LFU, ARC, and TLRU policies
 ///
 /// # Eviction Behavior
@@ -166,25 +175,34 @@ use crate::CacheStats;
 /// // Insert with memory tracking (requires MemoryEstimator implementation)
 /// cache.insert_with_memory("key", value);
 /// ```
-pub struct GlobalCache<R: 'static> {
-    pub map: &'static Lazy<RwLock<HashMap<String, CacheEntry<R>>>>,
-    pub order: &'static Lazy<Mutex<VecDeque<String>>>,
+#[derive(Clone)]
+pub struct GlobalCache<'a, R: 'static> {
+    pub map: &'a RwLock<HashMap<String, CacheEntry<R>>>,
+    pub order: &'a Mutex<VecDeque<String>>,
     pub limit: Option<usize>,
     pub max_memory: Option<usize>,
     pub policy: EvictionPolicy,
     pub ttl: Option<u64>,
     pub frequency_weight: Option<f64>,
     #[cfg(feature = "stats")]
-    pub stats: &'static Lazy<CacheStats>,
+    pub stats: &'a CacheStats,
+    pub on_evict: Option<&'a RemovalListener<R>>,
+    pub strategy: Option<&'a dyn EvictionStrategy<R>>,
+    pub secondary: Option<&'a SecondaryCache<R>>,
+    pub shards: Option<&'a CacheShards<R>>,
+    pub accesses: Option<&'a AccessBuffer>,
+    pub timers: Option<&'a TimerWheel>,
+    pub memory: Option<&'a MemoryUsage>,
+    pub eviction_state: Option<&'a EvictionState>,
 }
 
-impl<R: Clone + 'static> GlobalCache<R> {
+impl<'a, R: Clone + 'static> GlobalCache<'a, R> {
     /// Creates a new global cache instance.
     ///
     /// # Parameters
     ///
-    /// * `map` - Static reference to a RwLock-protected HashMap for storing cache entries
-    /// * `order` - Static reference to a Mutex-protected VecDeque for tracking entry order
+    /// * `map` - Reference to a RwLock-protected HashMap for storing cache entries
+    /// * `order` - Reference to a Mutex-protected VecDeque for tracking entry order
     /// * `limit` - Optional maximum number of entries (None for unlimited)
     /// * `max_memory` - Optional maximum memory size in bytes (None for unlimited)
     /// * `policy` - Eviction policy (FIFO, LRU, LFU, ARC, Random, or TLRU)
@@ -194,7 +212,7 @@ impl<R: Clone + 'static> GlobalCache<R> {
     ///   - Values > 1.0: Emphasize frequency
     ///   - None or 1.0: Balanced approach (default)
     ///   - Only used when policy is TLRU, ignored otherwise
-    /// * `stats` - Static reference to CacheStats for tracking hit/miss statistics (stats feature only)
+    /// * `stats` - Reference to CacheStats for tracking hit/miss statistics (stats feature only)
     ///
     /// # Returns
     ///
@@ -234,15 +252,16 @@ impl<R: Clone + 'static> GlobalCache<R> {
     /// );
     /// ```
     #[cfg(feature = "stats")]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        map: &'static Lazy<RwLock<HashMap<String, CacheEntry<R>>>>,
-        order: &'static Lazy<Mutex<VecDeque<String>>>,
+        map: &'a RwLock<HashMap<String, CacheEntry<R>>>,
+        order: &'a Mutex<VecDeque<String>>,
         limit: Option<usize>,
         max_memory: Option<usize>,
         policy: EvictionPolicy,
         ttl: Option<u64>,
         frequency_weight: Option<f64>,
-        stats: &'static Lazy<CacheStats>,
+        stats: &'a CacheStats,
     ) -> Self {
         Self {
             map,
@@ -253,13 +272,21 @@ impl<R: Clone + 'static> GlobalCache<R> {
             ttl,
             frequency_weight,
             stats,
+            on_evict: None,
+            strategy: None,
+            secondary: None,
+            shards: None,
+            accesses: None,
+            timers: None,
+            memory: None,
+            eviction_state: None,
         }
     }
 
     #[cfg(not(feature = "stats"))]
     pub fn new(
-        map: &'static Lazy<RwLock<HashMap<String, CacheEntry<R>>>>,
-        order: &'static Lazy<Mutex<VecDeque<String>>>,
+        map: &'a RwLock<HashMap<String, CacheEntry<R>>>,
+        order: &'a Mutex<VecDeque<String>>,
         limit: Option<usize>,
         max_memory: Option<usize>,
         policy: EvictionPolicy,
@@ -274,9 +301,289 @@ impl<R: Clone + 'static> GlobalCache<R> {
             policy,
             ttl,
             frequency_weight,
+            on_evict: None,
+            strategy: None,
+            secondary: None,
+            shards: None,
+            accesses: None,
+            timers: None,
+            memory: None,
+            eviction_state: None,
+        }
+    }
+
+    /// Sets a listener that is called whenever an entry leaves the cache.
+    ///
+    /// The listener receives the key, the removed value and the [`RemovalCause`]
+    /// (capacity or memory eviction, expiration, invalidation, replacement or
+    /// explicit removal). It is always invoked after the cache locks have been
+    /// released, so it may do I/O or even access the cache again.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn on_evict(key: &str, value: &User, cause: RemovalCause) {
+    ///     metrics::increment(format!("cache.removed.{:?}", cause));
+    /// }
+    ///
+    /// let cache = GlobalCache::new(/* ... */).with_removal_listener(&on_evict);
+    /// ```
+    pub fn with_removal_listener(mut self, listener: &'a RemovalListener<R>) -> Self {
+        self.on_evict = Some(listener);
+        self
+    }
+
+    /// Evicts entries with a custom [`EvictionStrategy`] instead of the policy of the cache.
+    ///
+    /// The strategy is shared by all shards of a sharded cache, and is told about the
+    /// insertions, hits and removals of every one of them.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// struct ByPriority;
+    ///
+    /// impl EvictionStrategy<Offer> for ByPriority {
+    ///     fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, Offer>) -> Option<usize> {
+    ///         candidates.lowest_score(|entry, _| entry.value.priority as f64)
+    ///     }
+    /// }
+    ///
+    /// let cache = GlobalCache::new(/* ... */).with_eviction_strategy(&ByPriority);
+    /// ```
+    pub fn with_eviction_strategy(mut self, strategy: &'a dyn EvictionStrategy<R>) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Sets a secondary (L2) tier receiving the entries evicted by `limit` or `max_memory`.
+    ///
+    /// Evicted entries are serialized into the [`SecondaryCache`] instead of being
+    /// dropped, and [`promote_secondary`](Self::promote_secondary) moves them back on a
+    /// miss. Entries removed for any other reason (expiration, invalidation, explicit
+    /// removal) are removed from the secondary tier too.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static L2: Lazy<SecondaryCache<Report>> = Lazy::new(|| {
+    ///     SecondaryCache::new(FileStore::new("/var/cache/reports").unwrap(), JsonSerializer)
+    /// });
+    ///
+    /// let cache = GlobalCache::new(/* ... */).with_secondary_cache(&L2);
+    /// ```
+    pub fn with_secondary_cache(mut self, secondary: &'a SecondaryCache<R>) -> Self {
+        self.secondary = Some(secondary);
+        self
+    }
+
+    /// Partitions the cache over `shards`, each locked independently.
+    ///
+    /// Keys are routed to a shard by hash, and each shard enforces its share of `limit`
+    /// and `max_memory` with the configured policy (see [`CacheShards`]). The `map` and
+    /// `order` passed to `new` are replaced by those of the first shard.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static SHARDS: Lazy<CacheShards<User>> = Lazy::new(|| CacheShards::new(16));
+    ///
+    /// let cache = GlobalCache::new(SHARDS.map(0), SHARDS.order(0), /* ... */)
+    ///     .with_shards(&SHARDS);
+    /// ```
+    pub fn with_shards(mut self, shards: &'a CacheShards<R>) -> Self {
+        self.map = shards.map(0);
+        self.order = shards.order(0);
+        self.shards = Some(shards);
+        self
+    }
+
+    /// Records hits in `accesses` instead of updating the eviction policy on every hit.
+    ///
+    /// With the LRU, LFU, ARC and TLRU policies, a hit normally locks the eviction order
+    /// (and the entry map for frequency updates). With a buffer, hits are recorded
+    /// without waiting and replayed in batches under the locks, when the buffer fills up
+    /// and before insertions. Some hits may be dropped, so recency and frequency become
+    /// sampled (see [`AccessBuffer`]). Sharded caches use the buffers of their shards.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static ACCESSES: Lazy<AccessBuffer> = Lazy::new(AccessBuffer::new);
+    ///
+    /// let cache = GlobalCache::new(/* ... */).with_access_buffer(&ACCESSES);
+    /// ```
+    pub fn with_access_buffer(mut self, accesses: &'a AccessBuffer) -> Self {
+        self.accesses = Some(accesses);
+        self
+    }
+
+    /// Tracks the expiration of entries in `timers`, so that
+    /// [`purge_expired`](Self::purge_expired) only visits the expired ones.
+    ///
+    /// Without a wheel, purging scans every entry. With one, each insertion of an entry
+    /// with a TTL (the cache's or its own) schedules a timer, and purging costs
+    /// O(expired). Sharded caches use the wheels of their shards.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static TIMERS: Lazy<TimerWheel> = Lazy::new(TimerWheel::new);
+    ///
+    /// let cache = GlobalCache::new(/* ... */).with_timer_wheel(&TIMERS);
+    /// ```
+    pub fn with_timer_wheel(mut self, timers: &'a TimerWheel) -> Self {
+        self.timers = Some(timers);
+        self
+    }
+
+    /// Keeps the total size of the entries in `memory`, so that enforcing `max_memory`
+    /// does not sum the sizes of every entry.
+    ///
+    /// Sizes are estimated once per entry by `insert_with_memory`. Without a counter, they
+    /// are summed on every insertion. Sharded caches use the counters of their shards.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static MEMORY: MemoryUsage = MemoryUsage::new();
+    ///
+    /// let cache = GlobalCache::new(/* ... */).with_memory_usage(&MEMORY);
+    /// ```
+    pub fn with_memory_usage(mut self, memory: &'a MemoryUsage) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Keeps the state of the built-in eviction strategies that need one in `state`:
+    /// the clock of GDSF, the hand of SIEVE and the queues of S3-FIFO.
+    ///
+    /// Without it, these strategies start from a fresh state on every call (see
+    /// [`EvictionState`]). Sharded caches use the states of their shards.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static STATE: Lazy<EvictionState> = Lazy::new(EvictionState::new);
+    ///
+    /// let cache = GlobalCache::new(/* ... */).with_eviction_state(&STATE);
+    /// ```
+    pub fn with_eviction_state(mut self, state: &'a EvictionState) -> Self {
+        self.eviction_state = Some(state);
+        self
+    }
+
+    /// Returns the total size of the entries of this (unsharded) view.
+    fn memory_in_use(&self) -> usize {
+        self.engine().memory_in_use(&*self.map.read())
+    }
+
+    /// Returns the engine applying the limits and eviction policy of this (unsharded) view.
+    fn engine(&self) -> PolicyEngine<'a, R> {
+        PolicyEngine::new(self.policy, self.ttl, self.frequency_weight, self.strategy)
+            .with_limits(self.limit, self.max_memory)
+            .with_timer_wheel(self.timers)
+            .with_memory_usage(self.memory)
+            .with_eviction_state(self.eviction_state)
+    }
+
+    /// Applies the hits recorded in the access buffer to the eviction policy.
+    fn replay_accesses(&self) {
+        let Some(accesses) = self.accesses else {
+            return;
+        };
+        let keys = accesses.drain();
+        if keys.is_empty() {
+            return;
+        }
+
+        let engine = self.engine();
+        let mut o = self.order.lock();
+        let mut map_write = self.map.write();
+        for key in &keys {
+            // Entries removed since the hit are skipped
+            let Some(entry) = map_write.get_mut(key) else {
+                continue;
+            };
+            engine.on_access(key, entry);
+            if engine.promotes_on_access() {
+                move_key_to_end(&mut o, key);
+            }
         }
     }
 
+    /// Returns the number of shards in use: never more than `limit`, so that every shard
+    /// can hold at least one entry.
+    fn active_shards(&self, shards: &CacheShards<R>) -> usize {
+        match self.limit {
+            Some(limit) => shards.count().min(limit.max(1)),
+            None => shards.count(),
+        }
+    }
+
+    /// Returns the unsharded view of shard `index`, limited to its share of the cache.
+    fn shard_view(&self, shards: &'a CacheShards<R>, index: usize, active: usize) -> Self {
+        Self {
+            map: shards.map(index),
+            order: shards.order(index),
+            limit: self.limit.map(|limit| shard_share(limit, index, active)),
+            max_memory: self
+                .max_memory
+                .map(|max_memory| shard_share(max_memory, index, active)),
+            shards: None,
+            accesses: self.accesses.map(|_| shards.access_buffer(index)),
+            timers: self.timers.map(|_| shards.timer_wheel(index)),
+            memory: self.memory.map(|_| shards.memory_usage(index)),
+            eviction_state: self.eviction_state.map(|_| shards.eviction_state(index)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the view of the shard holding `key`, or `None` if the cache is not sharded.
+    fn shard_for(&self, key: &str) -> Option<Self> {
+        let shards = self.shards?;
+        let active = self.active_shards(shards);
+        Some(self.shard_view(shards, shards.index_of(key, active), active))
+    }
+
+    /// Returns the views of all shards in use, or the cache itself if it is not sharded.
+    pub(crate) fn shard_views(&self) -> Vec<Self> {
+        match self.shards {
+            Some(shards) => {
+                let active = self.active_shards(shards);
+                (0..active)
+                    .map(|index| self.shard_view(shards, index, active))
+                    .collect()
+            }
+            None => vec![self.clone()],
+        }
+    }
+
+    /// Collects removed entries for the removal listener, the secondary tier and the
+    /// eviction strategy.
+    fn pending_removals(&self) -> PendingRemovals<'a, R> {
+        PendingRemovals::new(self.on_evict)
+            .with_secondary(self.secondary)
+            .with_strategy(self.engine().removal_strategy())
+    }
+
+    /// Moves the entry for `key` from the secondary tier back into this cache.
+    ///
+    /// Used after a [`get`](Self::get) miss. The entry keeps the age it had when it was
+    /// demoted, so it still expires on time. Only entry-count limits are applied; use
+    /// `promote_secondary_with_memory` when `max_memory` is configured.
+    ///
+    /// # Returns
+    ///
+    /// The promoted value, or `None` if there is no secondary tier, or no live entry
+    /// for `key` in it.
+    pub fn promote_secondary(&self, key: &str) -> Option<R> {
+        let entry = self.secondary?.promote(key, self.ttl)?;
+        let value = entry.value.clone();
+        self.insert_entry(key, entry);
+        Some(value)
+    }
+
     /// Retrieves a cached value by key.
     ///
     /// This method attempts to retrieve a cached value, checking for expiration
@@ -346,81 +653,66 @@ impl<R: Clone + 'static> GlobalCache<R> {
     /// assert_eq!(cache.get("temp"), None);
     /// ```
     pub fn get(&self, key: &str) -> Option<R> {
-        let mut result = None;
-        let mut expired = false;
+        if let Some(shard) = self.shard_for(key) {
+            return shard.get(key);
+        }
 
+        let engine = self.engine();
         // Acquire read lock - allows concurrent reads
-        {
-            let m = self.map.read();
-            if let Some(entry) = m.get(key) {
-                if entry.is_expired(self.ttl) {
-                    expired = true;
-                } else {
-                    result = Some(entry.value.clone());
+        let lookup = engine.lookup(&*self.map.read(), key);
+        let result = match lookup {
+            Lookup::Hit(value) => value,
+            Lookup::Expired => {
+                let mut removed = self.pending_removals();
+                {
+                    // Acquiring order lock to modify order queue
+                    let mut o = self.order.lock();
+                    // Acquire write lock to modify the map
+                    let mut map_write = self.map.write();
+                    engine.remove(
+                        &mut *map_write,
+                        &mut o,
+                        key,
+                        &mut removed,
+                        RemovalCause::Expired,
+                    );
                 }
+                removed.notify();
+                #[cfg(feature = "stats")]
+                self.stats.record_miss();
+                return None;
             }
-        } // Read lock released here
-
-        if expired {
-            // Acquiring order lock to modify order queue
-            let mut o = self.order.lock();
-            // Acquire write lock to modify the map
-            let mut map_write = self.map.write();
-            remove_key_from_global_cache(&mut map_write, &mut o, key);
-            #[cfg(feature = "stats")]
-            self.stats.record_miss();
-            return None;
-        }
-
-        // Record stats
-        #[cfg(feature = "stats")]
-        {
-            if result.is_some() {
-                self.stats.record_hit();
-            } else {
+            Lookup::Miss => {
+                #[cfg(feature = "stats")]
                 self.stats.record_miss();
+                return None;
             }
-        }
+        };
+
+        #[cfg(feature = "stats")]
+        self.stats.record_hit();
 
         // Update access patterns based on policy
-        if result.is_some() {
-            match self.policy {
-                EvictionPolicy::LRU => {
-                    // Move key to end of order queue (most recently used)
-                    move_key_to_end(&mut self.order.lock(), key);
-                }
-                EvictionPolicy::LFU => {
-                    // Increment frequency counter
-                    self.increment_frequency(key);
-                }
-                EvictionPolicy::ARC => {
-                    // Adaptive Replacement: Update both recency (LRU) and frequency (LFU)
-                    // Move key to end (recency) - lock is automatically released after this call
-                    move_key_to_end(&mut self.order.lock(), key);
-                    // Increment frequency counter
-                    self.increment_frequency(key);
-                }
-                EvictionPolicy::TLRU => {
-                    // Time-aware LRU: Update both recency and frequency
-                    // Similar to ARC but considers age in eviction
-                    move_key_to_end(&mut self.order.lock(), key);
-                    self.increment_frequency(key);
-                }
-                EvictionPolicy::FIFO | EvictionPolicy::Random => {
-                    // No update needed for FIFO or Random
+        engine.on_hit(key);
+        if engine.tracks_accesses() {
+            // Buffered hits are applied in batches, without waiting for the locks
+            if let Some(accesses) = self.accesses {
+                if accesses.record(key) {
+                    self.replay_accesses();
                 }
+                return Some(result);
             }
-        }
 
-        result
-    }
-
-    /// Increments the frequency counter for the specified key.
-    fn increment_frequency(&self, key: &str) {
-        let mut m = self.map.write();
-        if let Some(entry) = m.get_mut(key) {
-            entry.increment_frequency();
+            if engine.promotes_on_access() {
+                // Move key to end of order queue (most recently used)
+                move_key_to_end(&mut self.order.lock(), key);
+            }
+            if engine.updates_on_access() {
+                engine.record_access(&mut *self.map.write(), key);
+            }
         }
+
+        Some(result)
     }
 
     /// Inserts or updates a value in the cache.
@@ -479,303 +771,272 @@ impl<R: Clone + 'static> GlobalCache<R> {
     /// If `max_memory` is configured, use `insert_with_memory()` instead, which requires
     /// the type to implement `MemoryEstimator`.
     pub fn insert(&self, key: &str, value: R) {
-        let key_s = key.to_string();
-        let entry = CacheEntry::new(value);
-
-        // Acquire write lock for modification
-        self.map.write().insert(key_s.clone(), entry);
-
-        let mut o = self.order.lock();
-        if let Some(pos) = o.iter().position(|k| *k == key_s) {
-            o.remove(pos);
-        }
-        o.push_back(key_s.clone());
-
-        // Always handle entry-count limits, regardless of memory limits
-        self.handle_entry_limit_eviction(&mut o);
+        self.insert_entry(key, CacheEntry::new(value));
     }
 
-    /// Handles the eviction of entries from a global cache when the number of entries exceeds the limit.
+    /// Inserts a value with its own time-to-live, overriding the TTL of the cache.
     ///
-    /// The eviction behavior depends on the specified eviction policy. The function ensures that the cache
-    /// adheres to the defined entry limit by evicting entries based on the configured policy:
+    /// Used for negative caching, where errors expire sooner than successful results.
+    /// `None` keeps the TTL of the cache.
+    pub fn insert_with_ttl(&self, key: &str, value: R, ttl: Option<u64>) {
+        self.insert_entry(key, CacheEntry::new(value).with_ttl(ttl));
+    }
+
+    /// Returns the values of many keys at once, loading the missing ones in a single call.
     ///
-    /// - **LFU (Least Frequently Used):** Finds and evicts the entry with the minimum frequency of usage.
-    /// - **ARC (Adaptive Replacement Cache):** Leverages the ARC eviction strategy to find and evict a specific entry.
-    /// - **FIFO (First In First Out):** Evicts the oldest entry in the queue to ensure the limit is maintained.
-    /// - **LRU (Least Recently Used):** Evicts the least recently accessed entry from the queue.
+    /// Hits are served from the cache; `load` is called once with the keys that missed
+    /// (without duplicates), and only if there are any. The values it returns are inserted
+    /// with the limits, TTL and eviction policy of the cache. Use `get_many_with_memory`
+    /// when `max_memory` is configured.
     ///
-    /// # Parameters
+    /// # Returns
     ///
-    /// - `o`: A mutable reference to a `MutexGuard` that holds a `VecDeque<String>`.
-    ///   This represents the global cache where entries are stored.
+    /// One value per requested key, in request order. Keys the loader returned no value
+    /// for are `None`.
     ///
-    /// # Behavior
+    /// # Examples
     ///
-    /// 1. **Check Limit:** The function first checks if the `limit` is defined and if the length of the
-    ///    cache (`o`) exceeds the defined `limit`.
-    ///
-    /// 2. **Eviction By Policy:** Based on the configured `EvictionPolicy`, different eviction strategies
-    ///    are employed:
-    ///
-    ///   - **LFU:** The method identifies the key with the minimum frequency count by inspecting the
-    ///     associated frequency map and removes it from the cache.
-    ///   - **ARC:** Uses an ARC strategy to determine which key should be evicted and removes it from the cache.
-    ///   - **FIFO or LRU:** Dequeues entries in sequence (from the front of the queue) and checks if the
-    ///     entry still exists in the global cache. If found, the entry is removed from both the queue and cache.
-    ///
-    /// 3. **Thread-Safe Access:** The function ensures thread-safe read/write access to the cache and
-    ///    associated data structures using mutexes.
-    fn handle_entry_limit_eviction(&self, mut o: &mut MutexGuard<RawMutex, VecDeque<String>>) {
-        if let Some(limit) = self.limit {
-            if o.len() > limit {
-                match self.policy {
-                    EvictionPolicy::LFU => {
-                        // Find and evict the entry with the minimum frequency
-                        let mut map_write = self.map.write();
-                        let min_freq_key = find_min_frequency_key(&map_write, &o);
-
-                        if let Some(evict_key) = min_freq_key {
-                            remove_key_from_global_cache(&mut map_write, &mut o, &evict_key);
-                        }
-                    }
-                    EvictionPolicy::ARC => {
-                        let mut map_write = self.map.write();
-                        if let Some(evict_key) =
-                            find_arc_eviction_key(&map_write, o.iter().enumerate())
-                        {
-                            remove_key_from_global_cache(&mut map_write, &mut o, &evict_key);
-                        }
-                    }
-                    EvictionPolicy::TLRU => {
-                        let mut map_write = self.map.write();
-                        if let Some(evict_key) = find_tlru_eviction_key(
-                            &map_write,
-                            o.iter().enumerate(),
-                            self.ttl,
-                            self.frequency_weight,
-                        ) {
-                            remove_key_from_global_cache(&mut map_write, &mut o, &evict_key);
-                        }
-                    }
-                    EvictionPolicy::Random => {
-                        // O(1) random eviction: select random position and remove directly
-                        if !o.is_empty() {
-                            let pos = fastrand::usize(..o.len());
-                            if let Some(evict_key) = o.remove(pos) {
-                                let mut map_write = self.map.write();
-                                map_write.remove(&evict_key);
-                            }
-                        }
-                    }
-                    EvictionPolicy::FIFO | EvictionPolicy::LRU => {
-                        // Keep trying to evict until we find a valid entry or queue is empty
-                        let mut map_write = self.map.write();
-                        while let Some(evict_key) = o.pop_front() {
-                            // Check if the key still exists in the cache before removing
-                            if map_write.contains_key(&evict_key) {
-                                map_write.remove(&evict_key);
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
+    /// ```ignore
+    /// let users = cache.get_many(&[1, 2, 3], |missing| db.fetch_users(&missing));
+    /// ```
+    pub fn get_many<K, F>(&self, keys: &[K], load: F) -> Vec<Option<R>>
+    where
+        K: CacheableKey + Clone + Eq + Hash,
+        F: FnOnce(Vec<K>) -> HashMap<K, R>,
+    {
+        let mut batch = Batch::lookup(keys, |key| {
+            self.get(key).or_else(|| self.promote_secondary(key))
+        });
+        let missing = batch.take_missing();
+        let loaded = if missing.is_empty() {
+            HashMap::new()
+        } else {
+            load(missing)
+        };
+        batch.fill(loaded, |key, value| self.insert(key, value))
+    }
+
+    /// Stores a prepared entry, applying memory and entry-count limits.
+    pub(crate) fn insert_entry(&self, key: &str, entry: CacheEntry<R>) {
+        if let Some(shard) = self.shard_for(key) {
+            return shard.insert_entry(key, entry);
+        }
+        // Evictions must see the buffered hits
+        self.replay_accesses();
+
+        let mut removed = self.pending_removals();
+        {
+            let mut o = self.order.lock();
+            let mut map_write = self.map.write();
+            self.engine()
+                .insert(&mut *map_write, &mut o, key, entry, &mut removed);
         }
+
+        // Notify the removal listener once all locks are released
+        removed.notify();
     }
-}
 
-// Separate implementation for types that implement MemoryEstimator
-// This allows memory-based eviction
-impl<R: Clone + 'static + crate::MemoryEstimator> GlobalCache<R> {
-    /// Insert with memory limit support.
+    /// Removes a single entry from the cache.
     ///
-    /// This method requires `R` to implement `MemoryEstimator` and handles both
-    /// memory-based and entry-count-based eviction.
+    /// The removal listener (if any) is notified with [`RemovalCause::Explicit`].
     ///
-    /// Use this method when `max_memory` is configured in the cache.
-    ///
-    /// # Arguments
-    ///
-    /// * `key` - The cache key
-    /// * `value` - The value to cache (must implement `MemoryEstimator`)
+    /// # Returns
     ///
-    /// # Memory Management
+    /// `true` if the key was present in the cache or in its secondary tier.
+    pub fn remove(&self, key: &str) -> bool {
+        if let Some(shard) = self.shard_for(key) {
+            return shard.remove(key);
+        }
+
+        let mut removed = self.pending_removals();
+        let found = {
+            let mut o = self.order.lock();
+            let mut map_write = self.map.write();
+            self.engine().remove(
+                &mut *map_write,
+                &mut o,
+                key,
+                &mut removed,
+                RemovalCause::Explicit,
+            )
+        };
+        removed.notify();
+
+        // The entry may have been demoted to the secondary tier
+        let demoted = self
+            .secondary
+            .is_some_and(|secondary| secondary.remove(key));
+        found || demoted
+    }
+
+    /// Clears all entries from the cache.
     ///
-    /// The method calculates the memory footprint of all cached entries and evicts
-    /// entries as needed to stay within the `max_memory` limit. Eviction follows
-    /// the configured policy.
+    /// This method removes all entries from both the cache map and the order queue.
+    /// It's useful for testing or when you need to completely reset the cache state.
+    /// The removal listener (if any) is notified with [`RemovalCause::Explicit`].
     ///
-    /// # Safety Check
+    /// # Thread Safety
     ///
-    /// If the value to be inserted is larger than `max_memory`, the insertion is
-    /// skipped entirely to avoid infinite eviction loops. This ensures the cache
-    /// respects the memory limit even if individual values are very large.
+    /// This method is thread-safe and can be safely called from multiple threads.
     ///
-    /// # Eviction Behavior by Policy
+    /// # Example
     ///
-    /// When memory limit is exceeded:
-    /// - **FIFO/LRU**: Evicts from front of order queue
-    /// - **LFU**: Evicts entry with lowest frequency
-    /// - **ARC**: Evicts based on hybrid score (frequency × position_weight)
-    /// - **TLRU**: Evicts based on TLRU score (frequency^weight × position × age_factor)
-    /// - **Random**: Evicts randomly selected entry
+    /// ```ignore
+    /// cache.insert("key1", 42);
+    /// cache.insert("key2", 84);
     ///
-    /// The eviction loop continues until there's enough memory for the new value.
+    /// cache.clear();
     ///
-    /// # Entry Count Limit
+    /// assert_eq!(cache.get("key1"), None);
+    /// assert_eq!(cache.get("key2"), None);
+    /// ```
+    pub fn clear(&self) {
+        self.remove_where(|_| true, RemovalCause::Explicit);
+    }
+
+    /// Removes every entry on behalf of the invalidation registry.
     ///
-    /// After satisfying memory constraints, this method also checks the entry count
-    /// limit (if configured) and evicts additional entries if needed.
+    /// Same as [`clear`](Self::clear), but the removal listener is notified with
+    /// [`RemovalCause::Invalidated`].
+    pub fn invalidate_all(&self) {
+        self.remove_where(|_| true, RemovalCause::Invalidated);
+    }
+
+    /// Removes the entries whose key matches `check` on behalf of the invalidation registry.
     ///
-    /// # Thread Safety
+    /// The removal listener (if any) is notified with [`RemovalCause::Invalidated`].
+    pub fn invalidate_where(&self, check: &dyn Fn(&str) -> bool) {
+        self.remove_where(check, RemovalCause::Invalidated);
+    }
+
+    /// Removes every expired entry, returning how many were removed.
     ///
-    /// This method uses write locks to ensure consistency between the map and
-    /// order queue during eviction and insertion.
+    /// Expired entries are otherwise only removed when their key is read again, and
+    /// keep counting against `limit` and `max_memory` until then. The removal listener
+    /// (if any) is notified with [`RemovalCause::Expired`].
     ///
     /// # Examples
     ///
     /// ```ignore
-    /// use cachelito_core::MemoryEstimator;
-    ///
-    /// // Type must implement MemoryEstimator
-    /// impl MemoryEstimator for MyLargeStruct {
-    ///     fn estimate_memory(&self) -> usize {
-    ///         std::mem::size_of::<Self>() + self.data.capacity()
-    ///     }
-    /// }
-    ///
-    /// // Insert with automatic memory-based eviction
-    /// cache.insert_with_memory("large_data", expensive_value);
+    /// cache.insert("temp", data);
+    /// std::thread::sleep(Duration::from_secs(61)); // Wait for TTL expiration
+    /// assert_eq!(cache.purge_expired(), 1);
+    /// assert!(cache.is_empty());
     /// ```
-    ///
-    /// # Performance
-    ///
-    /// - **Memory calculation**: O(n) - iterates all entries to sum memory
-    /// - **Eviction**: Varies by policy (see individual policy documentation)
-    /// - May evict multiple entries in one call if memory limit is tight
-    pub fn insert_with_memory(&self, key: &str, value: R) {
-        let key_s = key.to_string();
-        let entry = CacheEntry::new(value);
-
-        // Acquire write lock for modification
-        self.map.write().insert(key_s.clone(), entry);
-
-        let mut o = self.order.lock();
-        if let Some(pos) = o.iter().position(|k| *k == key_s) {
-            o.remove(pos);
-        }
-        o.push_back(key_s.clone());
-
-        // Check memory limit first (if specified)
-        if let Some(max_mem) = self.max_memory {
-            // First, check if the new value by itself exceeds max_mem
-            // This is a safety check to prevent infinite eviction loop
-            let new_value_size = {
-                let map_read = self.map.read();
-                map_read
-                    .get(&key_s)
-                    .map(|e| e.value.estimate_memory())
-                    .unwrap_or(0)
-            };
+    pub fn purge_expired(&self) -> usize {
+        let mut purged = 0;
+        for shard in self.shard_views() {
+            let engine = shard.engine();
+            // With a timer wheel, only the entries whose timer is due are visited
+            let due = engine.due_timers();
+            if due.as_ref().is_some_and(Vec::is_empty) {
+                continue;
+            }
 
-            if new_value_size > max_mem {
-                // The value itself is too large for the cache
-                // Remove it and return early to respect memory limit
-                self.map.write().remove(&key_s);
-                o.pop_back(); // Remove from order queue as well
-                return;
+            let mut removed = shard.pending_removals();
+            {
+                let mut o = shard.order.lock();
+                let mut map_write = shard.map.write();
+                purged += engine.purge_expired(&mut *map_write, &mut o, due, &mut removed);
             }
+            removed.notify();
+        }
+        purged
+    }
 
-            loop {
-                let current_mem = {
-                    let map_read = self.map.read();
-                    map_read
-                        .values()
-                        .map(|e| e.value.estimate_memory())
-                        .sum::<usize>()
-                };
-
-                if current_mem <= max_mem {
-                    break;
-                }
+    /// Evicts a single entry chosen by the eviction policy, on behalf of the
+    /// [`MemoryBudget`](crate::MemoryBudget).
+    ///
+    /// In a sharded cache, the entry is taken from the shard using the most memory.
+    /// The removal listener (if any) is notified with [`RemovalCause::Memory`].
+    ///
+    /// # Returns
+    ///
+    /// `false` if there was nothing left to evict.
+    pub fn evict(&self) -> bool {
+        let Some(shard) = self
+            .shard_views()
+            .into_iter()
+            .max_by_key(Self::memory_in_use)
+        else {
+            return false;
+        };
+        // Evictions must see the buffered hits
+        shard.replay_accesses();
+
+        let mut removed = shard.pending_removals();
+        let evicted = {
+            let mut o = shard.order.lock();
+            let mut map_write = shard.map.write();
+            shard
+                .engine()
+                .evict_into(&mut *map_write, &mut o, &mut removed, RemovalCause::Memory)
+        };
+        removed.notify();
+        evicted
+    }
 
-                // Need to evict based on policy
-                let evicted = match self.policy {
-                    EvictionPolicy::LFU => {
-                        let mut map_write = self.map.write();
-                        let min_freq_key = find_min_frequency_key(&map_write, &o);
-                        if let Some(evict_key) = min_freq_key {
-                            remove_key_from_global_cache(&mut map_write, &mut o, &evict_key);
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                    EvictionPolicy::ARC => {
-                        let mut map_write = self.map.write();
-                        if let Some(evict_key) =
-                            find_arc_eviction_key(&map_write, o.iter().enumerate())
-                        {
-                            remove_key_from_global_cache(&mut map_write, &mut o, &evict_key);
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                    EvictionPolicy::TLRU => {
-                        let mut map_write = self.map.write();
-                        if let Some(evict_key) = find_tlru_eviction_key(
-                            &map_write,
-                            o.iter().enumerate(),
-                            self.ttl,
-                            self.frequency_weight,
-                        ) {
-                            remove_key_from_global_cache(&mut map_write, &mut o, &evict_key);
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                    EvictionPolicy::Random => {
-                        // O(1) random eviction: select random position and remove directly
-                        if !o.is_empty() {
-                            let pos = fastrand::usize(..o.len());
-                            if let Some(evict_key) = o.remove(pos) {
-                                let mut map_write = self.map.write();
-                                map_write.remove(&evict_key);
-                                true
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
-                        }
-                    }
-                    EvictionPolicy::FIFO | EvictionPolicy::LRU => {
-                        // Ensure we only count as evicted if we actually remove from the map
-                        let mut successfully_evicted = false;
-                        let mut map_write = self.map.write();
-                        while let Some(evict_key) = o.pop_front() {
-                            if map_write.contains_key(&evict_key) {
-                                map_write.remove(&evict_key);
-                                successfully_evicted = true;
-                                break;
-                            }
-                            // If key wasn't in map (orphan), continue popping until we remove a real one
-                        }
-                        successfully_evicted
+    /// Evicts `fraction` (0.0 to 1.0) of the entries, chosen by the eviction policy,
+    /// returning how many were evicted.
+    ///
+    /// Used to give memory back under pressure (see
+    /// [`MemoryPressureMonitor`](crate::MemoryPressureMonitor)); sharded caches shrink
+    /// every shard by the same fraction. The removal listener (if any) is notified with
+    /// [`RemovalCause::Memory`].
+    pub fn shrink(&self, fraction: f64) -> usize {
+        let mut evicted = 0;
+        for shard in self.shard_views() {
+            let count = shrink_count(shard.map.read().len(), fraction);
+            if count == 0 {
+                continue;
+            }
+            // Evictions must see the buffered hits
+            shard.replay_accesses();
+
+            let mut removed = shard.pending_removals();
+            {
+                let engine = shard.engine();
+                let mut o = shard.order.lock();
+                let mut map_write = shard.map.write();
+                for _ in 0..count {
+                    if !engine.evict_into(
+                        &mut *map_write,
+                        &mut o,
+                        &mut removed,
+                        RemovalCause::Memory,
+                    ) {
+                        break;
                     }
-                };
-
-                if !evicted {
-                    break; // Nothing left to evict
+                    evicted += 1;
                 }
             }
+            removed.notify();
+        }
+        evicted
+    }
+
+    /// Returns the number of entries currently stored in the cache.
+    pub fn len(&self) -> usize {
+        match self.shards {
+            Some(shards) => shards.len(),
+            None => self.map.read().len(),
+        }
+    }
+
+    /// Returns `true` if the cache contains no entries.
+    pub fn is_empty(&self) -> bool {
+        match self.shards {
+            Some(shards) => shards.is_empty(),
+            None => self.map.read().is_empty(),
         }
+    }
 
-        // Handle entry-count limits
-        self.handle_entry_limit_eviction(&mut o);
+    /// Returns the estimated memory of the cached entries, in bytes.
+    ///
+    /// Only entries inserted with `insert_with_memory` are measured; caches without
+    /// `max_memory` report 0.
+    pub fn memory_usage(&self) -> usize {
+        self.shard_views().iter().map(Self::memory_in_use).sum()
     }
 
     /// Returns a reference to the cache statistics.
@@ -821,29 +1082,146 @@ impl<R: Clone + 'static + crate::MemoryEstimator> GlobalCache<R> {
         self.stats
     }
 
-    /// Clears all entries from the cache.
+    /// Removes the entries whose key matches `check`, reporting them with `cause`.
+    fn remove_where(&self, check: impl Fn(&str) -> bool, cause: RemovalCause) {
+        for shard in self.shard_views() {
+            let mut removed = shard.pending_removals();
+            {
+                let mut o = shard.order.lock();
+                let mut map_write = shard.map.write();
+                shard
+                    .engine()
+                    .remove_where(&mut *map_write, &mut o, &check, &mut removed, cause);
+            }
+            removed.notify();
+        }
+
+        // Demoted entries are no longer in memory, so they are matched separately
+        if let Some(secondary) = self.secondary {
+            secondary.remove_where(&check);
+        }
+    }
+}
+
+// Separate implementation for types that implement MemoryEstimator
+// This allows memory-based eviction
+impl<'a, R: Clone + 'static + crate::MemoryEstimator> GlobalCache<'a, R> {
+    /// Insert with memory limit support.
     ///
-    /// This method removes all entries from both the cache map and the order queue.
-    /// It's useful for testing or when you need to completely reset the cache state.
+    /// This method requires `R` to implement `MemoryEstimator` and handles both
+    /// memory-based and entry-count-based eviction.
+    ///
+    /// Use this method when `max_memory` is configured in the cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The cache key
+    /// * `value` - The value to cache (must implement `MemoryEstimator`)
+    ///
+    /// # Memory Management
+    ///
+    /// The value is estimated once, and its size stored in its entry. Entries are then
+    /// evicted as needed to keep the total size within the `max_memory` limit. Eviction
+    /// follows the configured policy.
+    ///
+    /// # Safety Check
+    ///
+    /// If the value to be inserted is larger than `max_memory`, the insertion is
+    /// skipped entirely to avoid infinite eviction loops. This ensures the cache
+    /// respects the memory limit even if individual values are very large.
+    ///
+    /// # Eviction Behavior by Policy
+    ///
+    /// When memory limit is exceeded:
+    /// - **FIFO/LRU**: Evicts from front of order queue
+    /// - **LFU**: Evicts entry with lowest frequency
+    /// - **ARC**: Evicts based on hybrid score (frequency × position_weight)
+    /// - **TLRU**: Evicts based on TLRU score (frequency^weight × position × age_factor)
+    /// - **Random**: Evicts randomly selected entry
+    ///
+    /// The eviction loop continues until there's enough memory for the new value.
+    ///
+    /// # Entry Count Limit
+    ///
+    /// After satisfying memory constraints, this method also checks the entry count
+    /// limit (if configured) and evicts additional entries if needed.
     ///
     /// # Thread Safety
     ///
-    /// This method is thread-safe and can be safely called from multiple threads.
+    /// This method uses write locks to ensure consistency between the map and
+    /// order queue during eviction and insertion.
     ///
-    /// # Example
+    /// # Examples
     ///
     /// ```ignore
-    /// cache.insert("key1", 42);
-    /// cache.insert("key2", 84);
+    /// use cachelito_core::MemoryEstimator;
     ///
-    /// cache.clear();
+    /// // Type must implement MemoryEstimator
+    /// impl MemoryEstimator for MyLargeStruct {
+    ///     fn estimate_memory(&self) -> usize {
+    ///         std::mem::size_of::<Self>() + self.data.capacity()
+    ///     }
+    /// }
     ///
-    /// assert_eq!(cache.get("key1"), None);
-    /// assert_eq!(cache.get("key2"), None);
+    /// // Insert with automatic memory-based eviction
+    /// cache.insert_with_memory("large_data", expensive_value);
     /// ```
-    pub fn clear(&self) {
-        self.map.write().clear();
-        self.order.lock().clear();
+    ///
+    /// # Performance
+    ///
+    /// - **Memory calculation**: O(1) with a [`MemoryUsage`] counter (see
+    ///   [`with_memory_usage`](Self::with_memory_usage)), O(n) sums of stored sizes otherwise
+    /// - **Eviction**: Varies by policy (see individual policy documentation)
+    /// - May evict multiple entries in one call if memory limit is tight
+    pub fn insert_with_memory(&self, key: &str, value: R) {
+        self.insert_entry_with_memory(key, CacheEntry::new(value));
+    }
+
+    /// Inserts a value with its own time-to-live and memory limit support.
+    ///
+    /// See [`GlobalCache::insert_with_ttl`] and [`GlobalCache::insert_with_memory`].
+    pub fn insert_with_memory_and_ttl(&self, key: &str, value: R, ttl: Option<u64>) {
+        self.insert_entry_with_memory(key, CacheEntry::new(value).with_ttl(ttl));
+    }
+
+    /// Returns the values of many keys at once, loading the missing ones in a single call
+    /// and applying memory limits.
+    ///
+    /// See [`GlobalCache::get_many`].
+    pub fn get_many_with_memory<K, F>(&self, keys: &[K], load: F) -> Vec<Option<R>>
+    where
+        K: CacheableKey + Clone + Eq + Hash,
+        F: FnOnce(Vec<K>) -> HashMap<K, R>,
+    {
+        let mut batch = Batch::lookup(keys, |key| {
+            self.get(key)
+                .or_else(|| self.promote_secondary_with_memory(key))
+        });
+        let missing = batch.take_missing();
+        let loaded = if missing.is_empty() {
+            HashMap::new()
+        } else {
+            load(missing)
+        };
+        batch.fill(loaded, |key, value| self.insert_with_memory(key, value))
+    }
+
+    /// Moves the entry for `key` from the secondary tier back into this cache,
+    /// applying memory limits.
+    ///
+    /// See [`GlobalCache::promote_secondary`].
+    pub fn promote_secondary_with_memory(&self, key: &str) -> Option<R> {
+        let entry = self.secondary?.promote(key, self.ttl)?;
+        let value = entry.value.clone();
+        self.insert_entry_with_memory(key, entry);
+        Some(value)
+    }
+
+    /// Stores a prepared entry, measuring its value first.
+    pub(crate) fn insert_entry_with_memory(&self, key: &str, mut entry: CacheEntry<R>) {
+        entry.size = entry.value.estimate_memory();
+        self.insert_entry(key, entry);
+        MemoryBudget::global().enforce();
     }
 }
 
@@ -875,7 +1253,7 @@ impl<R: Clone + 'static + crate::MemoryEstimator> GlobalCache<R> {
 /// // If result was Err, nothing is cached
 /// // If result was Ok, the value is cached
 /// ```
-impl<T: Clone + Debug + 'static, E: Clone + Debug + 'static> GlobalCache<Result<T, E>> {
+impl<'a, T: Clone + Debug + 'static, E: Clone + Debug + 'static> GlobalCache<'a, Result<T, E>> {
     /// Inserts a Result into the cache, but only if it's an `Ok` variant.
     ///
     /// This method intelligently caches only successful results, preventing
@@ -919,6 +1297,23 @@ impl<T: Clone + Debug + 'static, E: Clone + Debug + 'static> GlobalCache<Result<
             self.insert(key, Ok(v.clone()));
         }
     }
+
+    /// Inserts a `Result` into the cache, including `Err` values (negative caching).
+    ///
+    /// Caching failures for a short time protects the underlying resource from retry
+    /// storms while it is unavailable.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The cache key
+    /// * `value` - The `Result` to cache
+    /// * `err_ttl` - Time-to-live in seconds for `Err` values. `None` uses the TTL of the cache.
+    pub fn insert_result_caching_err(&self, key: &str, value: &Result<T, E>, err_ttl: Option<u64>) {
+        match value {
+            Ok(v) => self.insert(key, Ok(v.clone())),
+            Err(err) => self.insert_with_ttl(key, Err(err.clone()), err_ttl),
+        }
+    }
 }
 
 /// Implementation of `GlobalCache` for `Result` types WITH MemoryEstimator support.
@@ -930,9 +1325,10 @@ impl<T: Clone + Debug + 'static, E: Clone + Debug + 'static> GlobalCache<Result<
 /// * `T` - The success type, must be `Clone`, `Debug`, and implement `MemoryEstimator`
 /// * `E` - The error type, must be `Clone`, `Debug`, and implement `MemoryEstimator`
 impl<
+        'a,
         T: Clone + Debug + 'static + crate::MemoryEstimator,
         E: Clone + Debug + 'static + crate::MemoryEstimator,
-    > GlobalCache<Result<T, E>>
+    > GlobalCache<'a, Result<T, E>>
 {
     /// Inserts a Result into the cache with memory limit support.
     ///
@@ -953,11 +1349,55 @@ impl<
             self.insert_with_memory(key, Ok(v.clone()));
         }
     }
+
+    /// Inserts a `Result` into the cache, including `Err` values, with memory limit support.
+    ///
+    /// See `insert_result_caching_err()`.
+    pub fn insert_result_caching_err_with_memory(
+        &self,
+        key: &str,
+        value: &Result<T, E>,
+        err_ttl: Option<u64>,
+    ) {
+        match value {
+            Ok(v) => self.insert_with_memory(key, Ok(v.clone())),
+            Err(err) => self.insert_with_memory_and_ttl(key, Err(err.clone()), err_ttl),
+        }
+    }
+}
+
+/// Specialized implementation for caching `Option<T>` return types.
+///
+/// Used with `cache_none = false`, so that a missing value is looked up again on
+/// the next call instead of being remembered.
+impl<'a, T: Clone + 'static> GlobalCache<'a, Option<T>> {
+    /// Inserts an `Option` into the cache, but only if it's a `Some` value.
+    ///
+    /// # Behavior
+    ///
+    /// * If `value` is `Some(v)`, stores `Some(v.clone())` in the cache
+    /// * If `value` is `None`, does nothing
+    pub fn insert_option(&self, key: &str, value: &Option<T>) {
+        if let Some(v) = value {
+            self.insert(key, Some(v.clone()));
+        }
+    }
+}
+
+/// Implementation for `Option` types WITH MemoryEstimator support.
+impl<'a, T: Clone + 'static + crate::MemoryEstimator> GlobalCache<'a, Option<T>> {
+    /// Inserts an `Option` into the cache with memory limit support, only if it's `Some`.
+    pub fn insert_option_with_memory(&self, key: &str, value: &Option<T>) {
+        if let Some(v) = value {
+            self.insert_with_memory(key, Some(v.clone()));
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use once_cell::sync::Lazy;
     use std::thread;
     use std::time::Duration;
 
@@ -967,7 +1407,7 @@ mod tests {
             Lazy::new(|| RwLock::new(HashMap::new()));
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -991,7 +1431,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1014,7 +1454,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1039,7 +1479,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1068,7 +1508,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1098,7 +1538,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1135,7 +1575,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let handles: Vec<_> = (0..10)
             .map(|i| {
@@ -1171,7 +1611,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1195,13 +1635,14 @@ mod tests {
         assert_eq!(cache.get("expires"), None);
     }
 
+    type ResultMap = HashMap<String, CacheEntry<Result<i32, String>>>;
+
     #[test]
     fn test_global_result_ok() {
-        static RES_MAP: Lazy<RwLock<HashMap<String, CacheEntry<Result<i32, String>>>>> =
-            Lazy::new(|| RwLock::new(HashMap::new()));
+        static RES_MAP: Lazy<RwLock<ResultMap>> = Lazy::new(|| RwLock::new(HashMap::new()));
         static RES_ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &RES_MAP,
@@ -1221,11 +1662,10 @@ mod tests {
 
     #[test]
     fn test_global_result_err() {
-        static RES_MAP: Lazy<RwLock<HashMap<String, CacheEntry<Result<i32, String>>>>> =
-            Lazy::new(|| RwLock::new(HashMap::new()));
+        static RES_MAP: Lazy<RwLock<ResultMap>> = Lazy::new(|| RwLock::new(HashMap::new()));
         static RES_ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &RES_MAP,
@@ -1250,7 +1690,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1305,7 +1745,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1337,7 +1777,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         // max_memory allows only a single i32 (size 4)
         let cache = GlobalCache::new(
@@ -1385,7 +1825,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1445,7 +1885,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1516,7 +1956,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1551,7 +1991,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1588,7 +2028,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1622,7 +2062,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1681,7 +2121,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1717,7 +2157,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -1751,7 +2191,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         // Low frequency_weight (0.3) - emphasizes recency over frequency
         let cache = GlobalCache::new(
@@ -1794,7 +2234,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         // High frequency_weight (1.5) - emphasizes frequency over recency
         let cache = GlobalCache::new(
@@ -1838,7 +2278,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         // Default frequency_weight (None = 1.0) - balanced approach
         let cache = GlobalCache::new(
@@ -1883,9 +2323,9 @@ mod tests {
             Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS_LOW: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS_LOW: Lazy<CacheStats> = Lazy::new(CacheStats::new);
         #[cfg(feature = "stats")]
-        static STATS_HIGH: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS_HIGH: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache_low = GlobalCache::new(
             &MAP_LOW,
@@ -1941,7 +2381,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         // TLRU without TTL (behaves like ARC but with frequency_weight)
         let cache = GlobalCache::new(
@@ -1980,7 +2420,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         let cache = GlobalCache::new(
             &MAP,
@@ -2040,7 +2480,7 @@ mod tests {
         static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
 
         #[cfg(feature = "stats")]
-        static STATS: Lazy<CacheStats> = Lazy::new(|| CacheStats::new());
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
 
         // Test with very low weight (close to 0)
         let cache = GlobalCache::new(
@@ -2071,4 +2511,64 @@ mod tests {
         // The cache should still work correctly
         assert!(cache.get("k3").is_some());
     }
+
+    #[test]
+    fn test_result_caching_err_uses_err_ttl() {
+        type Value = Result<i32, String>;
+        static MAP: Lazy<RwLock<HashMap<String, CacheEntry<Value>>>> =
+            Lazy::new(|| RwLock::new(HashMap::new()));
+        static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+        #[cfg(feature = "stats")]
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
+
+        let cache = GlobalCache::new(
+            &MAP,
+            &ORDER,
+            None,
+            None,
+            EvictionPolicy::FIFO,
+            Some(60),
+            None,
+            #[cfg(feature = "stats")]
+            &STATS,
+        );
+
+        cache.insert_result("plain_err", &Err("boom".to_string()));
+        assert_eq!(cache.get("plain_err"), None);
+
+        cache.insert_result_caching_err("ok", &Ok(1), Some(1));
+        cache.insert_result_caching_err("err", &Err("boom".to_string()), Some(1));
+        assert_eq!(cache.get("err"), Some(Err("boom".to_string())));
+
+        thread::sleep(Duration::from_millis(1100));
+        assert_eq!(cache.get("err"), None);
+        assert_eq!(cache.get("ok"), Some(Ok(1)));
+    }
+
+    #[test]
+    fn test_insert_option_skips_none() {
+        static MAP: Lazy<RwLock<HashMap<String, CacheEntry<Option<i32>>>>> =
+            Lazy::new(|| RwLock::new(HashMap::new()));
+        static ORDER: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+        #[cfg(feature = "stats")]
+        static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
+
+        let cache = GlobalCache::new(
+            &MAP,
+            &ORDER,
+            None,
+            None,
+            EvictionPolicy::FIFO,
+            None,
+            None,
+            #[cfg(feature = "stats")]
+            &STATS,
+        );
+
+        cache.insert_option("some", &Some(1));
+        cache.insert_option("none", &None);
+        assert_eq!(cache.get("some"), Some(Some(1)));
+        assert_eq!(cache.get("none"), None);
+        assert_eq!(cache.len(), 1);
+    }
 }
@@ -0,0 +1,219 @@
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+
+/// Loads in progress for an [`AsyncGlobalCache`](crate::AsyncGlobalCache).
+///
+/// Like the cache storage, it lives in a `static` (or any place outliving the cache
+/// views) and is attached with
+/// [`with_in_flight_loads`](crate::AsyncGlobalCache::with_in_flight_loads). While a
+/// [`get_or_try_load`](crate::AsyncGlobalCache::get_or_try_load) call is loading a
+/// key, other calls for the same key wait for its result instead of running their
+/// own loader.
+///
+/// If the load fails, or the loading task is cancelled, one of the waiting calls
+/// runs its own loader instead.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::InFlightLoads;
+/// use once_cell::sync::Lazy;
+///
+/// static USER_LOADS: Lazy<InFlightLoads<String>> = Lazy::new(InFlightLoads::new);
+/// assert!(USER_LOADS.is_empty());
+/// ```
+pub struct InFlightLoads<R> {
+    loads: Mutex<HashMap<String, Arc<Flight<R>>>>,
+}
+
+/// A single load, shared by the call running it and the calls waiting for it.
+struct Flight<R> {
+    state: Mutex<FlightState<R>>,
+}
+
+enum FlightState<R> {
+    /// The load is running; the wakers belong to the waiting calls
+    Loading(Vec<Waker>),
+    /// The load is over, with its value unless it failed or was cancelled
+    Done(Option<R>),
+}
+
+/// Role of a call joining the loads of a key.
+pub(crate) enum Join<'l, R> {
+    /// No load was in progress: this call loads, and reports through the guard
+    Leader(FlightGuard<'l, R>),
+    /// Another call is loading: wait for its result
+    Waiter(WaitFlight<R>),
+}
+
+impl<R: Clone> InFlightLoads<R> {
+    /// Creates an empty set of loads.
+    pub fn new() -> Self {
+        Self {
+            loads: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the number of keys currently being loaded.
+    pub fn len(&self) -> usize {
+        self.loads.lock().len()
+    }
+
+    /// Returns `true` if no key is being loaded.
+    pub fn is_empty(&self) -> bool {
+        self.loads.lock().is_empty()
+    }
+
+    /// Joins the load of `key`, starting it if none is in progress.
+    pub(crate) fn join(&self, key: &str) -> Join<'_, R> {
+        let mut loads = self.loads.lock();
+        if let Some(flight) = loads.get(key) {
+            return Join::Waiter(WaitFlight {
+                flight: flight.clone(),
+            });
+        }
+
+        let flight = Arc::new(Flight {
+            state: Mutex::new(FlightState::Loading(Vec::new())),
+        });
+        loads.insert(key.to_string(), flight.clone());
+        Join::Leader(FlightGuard {
+            loads: self,
+            key: key.to_string(),
+            flight,
+            completed: false,
+        })
+    }
+}
+
+impl<R: Clone> Default for InFlightLoads<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Held by the call running a load; completes the load when dropped.
+pub(crate) struct FlightGuard<'l, R> {
+    loads: &'l InFlightLoads<R>,
+    key: String,
+    flight: Arc<Flight<R>>,
+    completed: bool,
+}
+
+impl<'l, R> FlightGuard<'l, R> {
+    /// Publishes the loaded value (`None` if the load failed) and wakes the waiting calls.
+    pub(crate) fn complete(mut self, value: Option<R>) {
+        self.finish(value);
+    }
+
+    fn finish(&mut self, value: Option<R>) {
+        self.completed = true;
+        {
+            let mut loads = self.loads.loads.lock();
+            if loads
+                .get(&self.key)
+                .is_some_and(|flight| Arc::ptr_eq(flight, &self.flight))
+            {
+                loads.remove(&self.key);
+            }
+        }
+
+        let state = std::mem::replace(&mut *self.flight.state.lock(), FlightState::Done(value));
+        if let FlightState::Loading(wakers) = state {
+            for waker in wakers {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<'l, R> Drop for FlightGuard<'l, R> {
+    fn drop(&mut self) {
+        // Cancelled or failed: let a waiting call take over
+        if !self.completed {
+            self.finish(None);
+        }
+    }
+}
+
+/// Future resolving to the value of another call's load, or `None` if it failed.
+pub(crate) struct WaitFlight<R> {
+    flight: Arc<Flight<R>>,
+}
+
+impl<R: Clone> Future for WaitFlight<R> {
+    type Output = Option<R>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.flight.state.lock();
+        match &mut *state {
+            FlightState::Done(value) => Poll::Ready(value.clone()),
+            FlightState::Loading(wakers) => {
+                if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn poll_once(wait: &mut WaitFlight<u32>, waker: &Arc<CountingWaker>) -> Poll<Option<u32>> {
+        let waker = Waker::from(waker.clone());
+        Pin::new(wait).poll(&mut Context::from_waker(&waker))
+    }
+
+    #[test]
+    fn test_waiters_receive_the_loaded_value() {
+        let loads = InFlightLoads::<u32>::new();
+        let Join::Leader(guard) = loads.join("k") else {
+            panic!("first call must load");
+        };
+        let Join::Waiter(mut wait) = loads.join("k") else {
+            panic!("second call must wait");
+        };
+        assert_eq!(loads.len(), 1);
+
+        let waker = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        assert_eq!(poll_once(&mut wait, &waker), Poll::Pending);
+
+        guard.complete(Some(7));
+        assert_eq!(waker.0.load(Ordering::SeqCst), 1);
+        assert_eq!(poll_once(&mut wait, &waker), Poll::Ready(Some(7)));
+        assert!(loads.is_empty());
+    }
+
+    #[test]
+    fn test_dropped_leader_releases_waiters() {
+        let loads = InFlightLoads::<u32>::new();
+        let Join::Leader(guard) = loads.join("k") else {
+            panic!("first call must load");
+        };
+        let Join::Waiter(mut wait) = loads.join("k") else {
+            panic!("second call must wait");
+        };
+
+        drop(guard);
+        let waker = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        assert_eq!(poll_once(&mut wait, &waker), Poll::Ready(None));
+        assert!(matches!(loads.join("k"), Join::Leader(_)));
+    }
+}
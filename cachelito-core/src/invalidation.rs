@@ -7,6 +7,7 @@
 //! - **Event-driven invalidation**: Trigger invalidation based on events
 //! - **Dependency-based invalidation**: Cascade invalidation to dependent caches
 //! - **Conditional invalidation**: Custom check functions for invalidation logic (v0.13.0)
+//! - **Expiration purging**: Removal of expired entries that are never read again
 //!
 //! # Examples
 //!
@@ -68,6 +69,15 @@ impl InvalidationMetadata {
     }
 }
 
+/// Callback that invalidates the entries whose key matches the given check function
+type InvalidationCheckCallback = Arc<dyn Fn(&dyn Fn(&str) -> bool) + Send + Sync>;
+
+/// Callback that removes the expired entries of a cache and returns how many were removed
+type PurgeCallback = Arc<dyn Fn() -> usize + Send + Sync>;
+
+/// Callback that evicts the given fraction of the entries of a cache and returns how many were evicted
+type ShrinkCallback = Arc<dyn Fn(f64) -> usize + Send + Sync>;
+
 /// Registry for managing cache invalidation
 ///
 /// This struct maintains mappings between tags/events/dependencies and cache names,
@@ -85,8 +95,11 @@ pub struct InvalidationRegistry {
     clear_callbacks: RwLock<HashMap<String, Arc<dyn Fn() + Send + Sync>>>,
     /// Callbacks for selective invalidation checks (cache_name -> check function)
     /// These callbacks receive a check function and invalidate entries that match it
-    invalidation_check_callbacks:
-        RwLock<HashMap<String, Arc<dyn Fn(&dyn Fn(&str) -> bool) + Send + Sync>>>,
+    invalidation_check_callbacks: RwLock<HashMap<String, InvalidationCheckCallback>>,
+    /// Callbacks removing expired entries (cache_name -> purge function)
+    purge_callbacks: RwLock<HashMap<String, PurgeCallback>>,
+    /// Callbacks evicting a fraction of the entries (cache_name -> shrink function)
+    shrink_callbacks: RwLock<HashMap<String, ShrinkCallback>>,
 }
 
 impl InvalidationRegistry {
@@ -99,6 +112,8 @@ impl InvalidationRegistry {
             cache_metadata: RwLock::new(HashMap::new()),
             clear_callbacks: RwLock::new(HashMap::new()),
             invalidation_check_callbacks: RwLock::new(HashMap::new()),
+            purge_callbacks: RwLock::new(HashMap::new()),
+            shrink_callbacks: RwLock::new(HashMap::new()),
         }
     }
 
@@ -121,7 +136,7 @@ impl InvalidationRegistry {
             for tag in &metadata.tags {
                 tag_map
                     .entry(tag.clone())
-                    .or_insert_with(HashSet::new)
+                    .or_default()
                     .insert(cache_name.to_string());
             }
         }
@@ -132,7 +147,7 @@ impl InvalidationRegistry {
             for event in &metadata.events {
                 event_map
                     .entry(event.clone())
-                    .or_insert_with(HashSet::new)
+                    .or_default()
                     .insert(cache_name.to_string());
             }
         }
@@ -143,7 +158,7 @@ impl InvalidationRegistry {
             for dep in &metadata.dependencies {
                 dep_map
                     .entry(dep.clone())
-                    .or_insert_with(HashSet::new)
+                    .or_default()
                     .insert(cache_name.to_string());
             }
         }
@@ -190,6 +205,42 @@ impl InvalidationRegistry {
             .insert(cache_name.to_string(), Arc::new(callback));
     }
 
+    /// Register a purge callback for a cache
+    ///
+    /// This callback will be invoked to remove the expired entries of the cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_name` - Name of the cache
+    /// * `callback` - Function that removes expired entries and returns how many it removed
+    pub fn register_purge_callback<F>(&self, cache_name: &str, callback: F)
+    where
+        F: Fn() -> usize + Send + Sync + 'static,
+    {
+        self.purge_callbacks
+            .write()
+            .insert(cache_name.to_string(), Arc::new(callback));
+    }
+
+    /// Register a shrink callback for a cache
+    ///
+    /// This callback will be invoked to evict a fraction of the entries of the cache
+    /// under memory pressure.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_name` - Name of the cache
+    /// * `callback` - Function that evicts the given fraction (0.0 to 1.0) of the entries
+    ///   and returns how many it evicted
+    pub fn register_shrink_callback<F>(&self, cache_name: &str, callback: F)
+    where
+        F: Fn(f64) -> usize + Send + Sync + 'static,
+    {
+        self.shrink_callbacks
+            .write()
+            .insert(cache_name.to_string(), Arc::new(callback));
+    }
+
     /// Invalidate all caches associated with a tag
     ///
     /// # Arguments
@@ -268,6 +319,47 @@ impl InvalidationRegistry {
         }
     }
 
+    /// Remove the expired entries of a specific cache
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_name` - Name of the cache to purge
+    ///
+    /// # Returns
+    ///
+    /// Number of entries removed, or `None` if no cache is registered under that name
+    pub fn purge_expired(&self, cache_name: &str) -> Option<usize> {
+        let callback = self.purge_callbacks.read().get(cache_name).cloned()?;
+        Some(callback())
+    }
+
+    /// Remove the expired entries of every registered cache
+    ///
+    /// # Returns
+    ///
+    /// Total number of entries removed
+    pub fn purge_all_expired(&self) -> usize {
+        // Release the registry lock first, so that caches can register while purging
+        let callbacks: Vec<PurgeCallback> = self.purge_callbacks.read().values().cloned().collect();
+        callbacks.iter().map(|callback| callback()).sum()
+    }
+
+    /// Evict a fraction of the entries of every registered cache
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - Share of the entries of each cache to evict, from 0.0 to 1.0
+    ///
+    /// # Returns
+    ///
+    /// Total number of entries evicted
+    pub fn shrink_all(&self, fraction: f64) -> usize {
+        // Release the registry lock first, so that caches can register while shrinking
+        let callbacks: Vec<ShrinkCallback> =
+            self.shrink_callbacks.read().values().cloned().collect();
+        callbacks.iter().map(|callback| callback(fraction)).sum()
+    }
+
     /// Invalidate multiple caches
     ///
     /// # Arguments
@@ -399,6 +491,8 @@ impl InvalidationRegistry {
         self.cache_metadata.write().clear();
         self.clear_callbacks.write().clear();
         self.invalidation_check_callbacks.write().clear();
+        self.purge_callbacks.write().clear();
+        self.shrink_callbacks.write().clear();
     }
 }
 
@@ -498,6 +592,79 @@ pub fn invalidate_cache(cache_name: &str) -> bool {
     InvalidationRegistry::global().invalidate_cache(cache_name)
 }
 
+/// Remove the expired entries of a specific cache by its name
+///
+/// Expired entries are otherwise only removed when their key is read again. Global and
+/// async caches generated by the macros are registered on their first call; thread-local
+/// caches cannot be purged from another thread and are never registered.
+///
+/// # Arguments
+///
+/// * `cache_name` - The name of the cache to purge
+///
+/// # Returns
+///
+/// Number of entries removed, or `None` if no cache is registered under that name
+///
+/// # Examples
+///
+/// ```ignore
+/// use cachelito_core::purge_expired;
+///
+/// if let Some(count) = purge_expired("get_user_profile") {
+///     println!("Purged {} expired entries", count);
+/// }
+/// ```
+pub fn purge_expired(cache_name: &str) -> Option<usize> {
+    InvalidationRegistry::global().purge_expired(cache_name)
+}
+
+/// Remove the expired entries of every registered cache
+///
+/// This is what [`ExpirationSweeper`](crate::ExpirationSweeper) runs periodically.
+///
+/// # Returns
+///
+/// Total number of entries removed
+///
+/// # Examples
+///
+/// ```ignore
+/// use cachelito_core::purge_all_expired;
+///
+/// let count = purge_all_expired();
+/// println!("Purged {} expired entries", count);
+/// ```
+pub fn purge_all_expired() -> usize {
+    InvalidationRegistry::global().purge_all_expired()
+}
+
+/// Evict a fraction of the entries of every registered cache
+///
+/// Each cache evicts according to its own eviction policy. This is what
+/// [`MemoryPressureMonitor`](crate::MemoryPressureMonitor) runs above its watermark.
+/// Thread-local caches cannot be shrunk from another thread and are never registered.
+///
+/// # Arguments
+///
+/// * `fraction` - Share of the entries of each cache to evict, from 0.0 to 1.0
+///
+/// # Returns
+///
+/// Total number of entries evicted
+///
+/// # Examples
+///
+/// ```ignore
+/// use cachelito_core::shrink_all_caches;
+///
+/// // Give back a quarter of every cache
+/// let count = shrink_all_caches(0.25);
+/// ```
+pub fn shrink_all_caches(fraction: f64) -> usize {
+    InvalidationRegistry::global().shrink_all(fraction)
+}
+
 /// Invalidate entries in a specific cache based on a check function
 ///
 /// This function allows conditional invalidation of cache entries based on their keys.
@@ -829,4 +996,30 @@ mod tests {
         assert!(!removed.contains(&"user_10".to_string()));
         assert!(!removed.contains(&"user_30".to_string()));
     }
+
+    #[test]
+    fn test_purge_expired_callbacks() {
+        let registry = InvalidationRegistry::new();
+        registry.register_purge_callback("cache1", || 2);
+        registry.register_purge_callback("cache2", || 3);
+
+        assert_eq!(registry.purge_expired("cache1"), Some(2));
+        assert_eq!(registry.purge_expired("missing"), None);
+        assert_eq!(registry.purge_all_expired(), 5);
+
+        registry.clear();
+        assert_eq!(registry.purge_all_expired(), 0);
+    }
+
+    #[test]
+    fn test_shrink_callbacks() {
+        let registry = InvalidationRegistry::new();
+        registry.register_shrink_callback("cache1", |fraction| (fraction * 8.0) as usize);
+        registry.register_shrink_callback("cache2", |fraction| (fraction * 4.0) as usize);
+
+        assert_eq!(registry.shrink_all(0.5), 6);
+
+        registry.clear();
+        assert_eq!(registry.shrink_all(0.5), 0);
+    }
 }
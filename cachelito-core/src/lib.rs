@@ -12,6 +12,8 @@
 //! - **Thread-Local Storage**: Safe, lock-free caching using `thread_local!`
 //! - **Global Cache**: Thread-safe cache shared across all threads using `parking_lot::RwLock`
 //! - **Async Cache**: Lock-free async cache using `DashMap` for concurrent async operations
+//! - **Batch Loading**: `get_many` serves hits and loads all missing keys in a single call
+//! - **Async Loading**: `get_or_try_load` with coalescing of concurrent loads via [`InFlightLoads`]
 //! - **Eviction Policies**: Support for FIFO, LRU (default), LFU, ARC, Random, and TLRU
 //!   - **FIFO**: First In, First Out - simple and predictable
 //!   - **LRU**: Least Recently Used - evicts least recently accessed entries
@@ -23,29 +25,62 @@
 //!     - Formula: `score = frequency^weight × position × age_factor`
 //!     - `frequency_weight < 1.0`: Emphasize recency (good for time-sensitive data)
 //!     - `frequency_weight > 1.0`: Emphasize frequency (good for popular content)
+//! - **Custom Eviction**: Implement [`EvictionStrategy`] to choose the evicted entries yourself
 //! - **Cache Limits**: Control cache size with entry count limits (`limit`) or memory limits (`max_memory`)
 //! - **Memory Estimation**: `MemoryEstimator` trait for accurate memory usage tracking
+//!   - Sizes are estimated once per entry, and summed incrementally in a [`MemoryUsage`] counter
+//! - **Global Memory Budget**: A process-wide [`MemoryBudget`] shared by all memory-aware caches
+//! - **Memory Pressure**: Shrink the caches when cgroup or PSI signals cross a [`MemoryPressure`] watermark
+//! - **Compression**: Store large values as [`Compressed`] bytes (`lz4` or `zstd` feature)
 //! - **TTL Support**: Time-to-live expiration for automatic cache invalidation
-//! - **Result-Aware Caching**: Smart handling of `Result<T, E>` types
+//! - **Expiration Sweeping**: Purge expired entries on demand or from a background [`ExpirationSweeper`]
+//!   - A [`TimerWheel`] makes purging cost O(expired) instead of O(entries)
+//! - **Result-Aware Caching**: Smart handling of `Result<T, E>` types, and of custom types via [`CacheableOutcome`]
 //! - **Smart Invalidation**: Tag-based, event-driven, and dependency-based cache invalidation
 //! - **Conditional Invalidation**: Runtime invalidation with custom check functions
 //! - **Statistics Tracking**: Optional hit/miss rate monitoring (requires `stats` feature)
+//! - **Removal Listeners**: Get notified with the key, value and [`RemovalCause`] when entries leave a cache
+//! - **Cache Handles**: Typed per-function access to single entries via [`CacheHandle`]
+//! - **Generic Functions**: One cache per monomorphization via [`TypeCacheRegistry`]
+//! - **Instance Caches**: Per-object method caches stored in a [`CacheSlot`] field
+//! - **Buffered Access**: Record hits in an [`AccessBuffer`] and replay them into the policy in batches
+//! - **Sharding**: Partition global caches into independently locked [`CacheShards`]
+//! - **Two-Tier Caching**: Evicted entries are demoted to a [`SecondaryStore`] such as [`FileStore`]
+//! - **Persistence**: Save caches to disk and warm them up on restart (requires `persist` feature)
 //!
 //! ## Module Organization
 //!
 //! The library is organized into focused modules:
 //!
+//! - [`cache_handle`] - Typed handle to the cache of a `#[cache]` function
+//! - [`cache_slot`] - Cache storage owned by a struct instance
 //! - [`cache_entry`] - Entry wrapper with timestamp and frequency tracking for TTL and LFU support
 //! - [`eviction_policy`] - Eviction strategies: FIFO, LRU, LFU, ARC, and Random
+//! - [`eviction_strategy`] - Trait implementing the eviction policies, for custom ones
 //! - [`keys`] - Cache key generation traits and implementations
 //! - [`thread_local_cache`] - Thread-local caching with zero synchronization overhead
 //! - [`global_cache`] - Thread-safe global cache with `parking_lot::RwLock` for concurrent reads
+//! - [`access_buffer`] - Lossy buffer batching the hit bookkeeping of eviction policies
 //! - [`async_global_cache`] - Lock-free async cache using `DashMap`
+//! - [`budget`] - Process-wide memory budget coordinating eviction across caches
+//! - [`batch`] - Batch lookups shared by the `get_many` methods of the caches
+//! - [`in_flight`] - Coalescing of concurrent async loads of the same key
 //! - [`memory_estimator`] - Trait for estimating memory usage of cached values
+//! - [`compression`] - Compression of large cached values
 //! - [`invalidation`] - Cache invalidation registry and strategies
+//! - [`outcome`] - Trait deciding which computed values are cached
+//! - [`policy_engine`] - Eviction logic shared by the thread-local, global and async caches
+//! - [`pressure`] - Shrinking of the caches under cgroup or PSI memory pressure
+//! - [`type_registry`] - Type-keyed registry backing the caches of generic functions
+//! - [`removal`] - Removal causes and listener type for eviction notifications
+//! - [`secondary`] - Secondary (L2) tier receiving entries evicted from memory
+//! - [`shards`] - Partitioned storage for global caches under high contention
+//! - [`sweeper`] - Background removal of expired entries
+//! - [`timer_wheel`] - Hierarchical timing wheel tracking when entries expire
 //! - [`utils`] - Common utility functions for cache operations
 //! - [`stats`] - Cache statistics tracking (optional, requires `stats` feature)
 //! - [`stats_registry`] - Global statistics registry for querying cache metrics
+//! - [`persist`] - Disk snapshots for warm starts (optional, requires `persist` feature)
 //!
 //! ## Invalidation Strategies
 //!
@@ -57,6 +92,7 @@
 //! - **Manual**: `invalidate_cache("cache_name")` - Direct cache invalidation
 //! - **Conditional**: `invalidate_with("cache_name", |key| predicate)` - Selective invalidation with custom logic
 //! - **Global conditional**: `invalidate_all_with(|cache_name, key| predicate)` - Apply check function across all caches
+//! - **Expired entries**: `purge_expired("cache_name")` / `purge_all_expired()` - Remove entries whose TTL has elapsed
 //!
 //! ## Memory Management
 //!
@@ -65,6 +101,9 @@
 //! - **Entry limit**: `limit = 1000` - Maximum number of entries
 //! - **Memory limit**: `max_memory = "100MB"` - Maximum memory usage
 //! - **Custom estimators**: Implement `MemoryEstimator` for user-defined types
+//! - **Global budget**: `set_global_memory_budget("512MB")` - Maximum memory of all caches together
+//! - **Memory pressure**: `MemoryPressureMonitor` - Shrink all caches by a fraction above a watermark
+//! - **Compression**: `compress = "lz4"` - Store large values compressed, counted at their compressed size
 //!
 //! ## Statistics (Optional)
 //!
@@ -75,13 +114,30 @@
 //! - Total access count
 //! - Per-cache statistics via `stats_registry::get("cache_name")`
 //!
+mod access_buffer;
 mod async_global_cache;
+mod batch;
+mod budget;
 mod cache_entry;
+mod cache_handle;
+mod cache_slot;
+mod compression;
 mod eviction_policy;
+mod eviction_strategy;
 mod global_cache;
+mod in_flight;
 mod keys;
 mod memory_estimator;
+mod outcome;
+mod policy_engine;
+mod pressure;
+mod removal;
+mod secondary;
+mod shards;
+mod sweeper;
 mod thread_local_cache;
+mod timer_wheel;
+mod type_registry;
 
 pub mod invalidation;
 pub mod utils;
@@ -92,30 +148,58 @@ mod stats;
 #[cfg(feature = "stats")]
 pub mod stats_registry;
 
+#[cfg(feature = "persist")]
+pub mod persist;
+
+pub use access_buffer::AccessBuffer;
 pub use async_global_cache::AsyncGlobalCache;
-pub use cache_entry::CacheEntry;
+pub use budget::{
+    clear_global_memory_budget, global_memory_usage, set_global_memory_budget, MemoryBudget,
+};
+pub use cache_entry::{with_compute_cost, CacheEntry};
+pub use cache_handle::{CacheBackend, CacheHandle};
+pub use cache_slot::CacheSlot;
+pub use compression::{Compressed, Compressible, Compression};
 pub use eviction_policy::EvictionPolicy;
+pub use eviction_strategy::{
+    ArcStrategy, EvictionCandidates, EvictionState, EvictionStrategy, FifoStrategy, GdsfStrategy,
+    LfuStrategy, LruStrategy, RandomStrategy, S3FifoStrategy, SieveStrategy, TlruStrategy,
+};
 pub use global_cache::GlobalCache;
+pub use in_flight::InFlightLoads;
 pub use invalidation::{
     invalidate_all_with, invalidate_by_dependency, invalidate_by_event, invalidate_by_tag,
-    invalidate_cache, invalidate_with, InvalidationMetadata, InvalidationRegistry,
-    InvalidationStrategy,
+    invalidate_cache, invalidate_with, purge_all_expired, purge_expired, shrink_all_caches,
+    InvalidationMetadata, InvalidationRegistry, InvalidationStrategy,
 };
 pub use keys::{CacheableKey, DefaultCacheableKey};
-pub use memory_estimator::MemoryEstimator;
+pub use memory_estimator::{MemoryEstimator, MemoryUsage};
+pub use outcome::CacheableOutcome;
+pub use pressure::{MemoryPressure, MemoryPressureMonitor, PressureSource};
+pub use removal::{RemovalCause, RemovalListener};
+pub use secondary::{FileStore, SecondaryCache, SecondaryStore, ValueSerializer};
+pub use shards::CacheShards;
+pub use sweeper::{ExpirationSweeper, SweepTask};
 pub use thread_local_cache::ThreadLocalCache;
+pub use timer_wheel::TimerWheel;
+pub use type_registry::TypeCacheRegistry;
 
 #[cfg(feature = "stats")]
 pub use stats::CacheStats;
-/// Cache scope: thread-local or global
+
+#[cfg(feature = "persist")]
+pub use secondary::JsonSerializer;
+/// Cache scope: thread-local, global or per instance
 ///
-/// This enum determines whether a cache is stored in thread-local storage
-/// or in global static storage accessible by all threads.
+/// This enum determines whether a cache is stored in thread-local storage,
+/// in global static storage accessible by all threads, or in a [`CacheSlot`]
+/// field of the object a method is called on.
 ///
 /// # Variants
 ///
 /// * `ThreadLocal` - Each thread has its own independent cache
 /// * `Global` - Cache is shared across all threads with mutex protection
+/// * `Instance` - Each instance owns its cache, dropped together with it
 ///
 /// # Examples
 ///
@@ -132,4 +216,5 @@ pub use stats::CacheStats;
 pub enum CacheScope {
     ThreadLocal,
     Global,
+    Instance,
 }
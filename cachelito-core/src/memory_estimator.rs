@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 /// Trait for estimating the memory size of cached values.
 ///
 /// This trait is used by memory-based cache limits to determine how much
@@ -231,11 +233,68 @@ where
     }
 }
 
+/// Running total of the estimated memory of the entries of a cache, in bytes.
+///
+/// Caches with `max_memory` estimate each value once, when it is inserted, and store the
+/// size in its [`CacheEntry`](crate::CacheEntry). With a counter attached (see
+/// [`GlobalCache::with_memory_usage`](crate::GlobalCache::with_memory_usage),
+/// [`ThreadLocalCache::with_memory_usage`](crate::ThreadLocalCache::with_memory_usage) and
+/// [`AsyncGlobalCache::with_memory_usage`](crate::AsyncGlobalCache::with_memory_usage)),
+/// the sizes are added and subtracted as entries are inserted, replaced, evicted and
+/// invalidated, so checking the limit no longer walks every cached value.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::MemoryUsage;
+///
+/// static MEMORY: MemoryUsage = MemoryUsage::new();
+/// assert_eq!(MEMORY.bytes(), 0);
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoryUsage {
+    bytes: AtomicUsize,
+}
+
+impl MemoryUsage {
+    /// Creates a counter at zero bytes.
+    pub const fn new() -> Self {
+        Self {
+            bytes: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the estimated memory of the cached entries, in bytes.
+    pub fn bytes(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Adds the size of an entry entering the cache.
+    pub(crate) fn add(&self, size: usize) {
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+    }
+
+    /// Subtracts the size of an entry leaving the cache.
+    pub(crate) fn sub(&self, size: usize) {
+        let _ = self
+            .bytes
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bytes| {
+                Some(bytes.saturating_sub(size))
+            });
+    }
+
+    /// Resets the counter once the cache is empty.
+    pub(crate) fn reset(&self) {
+        self.bytes.store(0, Ordering::Relaxed);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_primitive_types() {
         assert_eq!(42i32.estimate_memory(), size_of::<i32>());
         assert_eq!(true.estimate_memory(), size_of::<bool>());
@@ -373,4 +432,17 @@ mod tests {
             + tuple3.2.capacity() * size_of::<u8>();
         assert_eq!(tuple3.estimate_memory(), expected);
     }
+
+    #[test]
+    fn test_memory_usage_never_underflows() {
+        let usage = MemoryUsage::new();
+        usage.add(100);
+        usage.sub(40);
+        assert_eq!(usage.bytes(), 60);
+        usage.sub(100);
+        assert_eq!(usage.bytes(), 0);
+        usage.add(10);
+        usage.reset();
+        assert_eq!(usage.bytes(), 0);
+    }
 }
@@ -0,0 +1,51 @@
+/// Decides whether a computed value should be stored in the cache.
+///
+/// Functions returning `Result` are detected by the cache macros, which then only
+/// cache `Ok` values. For other types (custom enums, or `Result` aliases the macro
+/// cannot recognize), annotate the function with `result = true` and implement this
+/// trait: values for which [`is_cacheable`](CacheableOutcome::is_cacheable) returns
+/// `false` are returned to the caller but not cached (or cached with `err_ttl` when
+/// `cache_err = true`).
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::CacheableOutcome;
+///
+/// #[derive(Clone)]
+/// enum Lookup {
+///     Found(String),
+///     NotFound,
+///     Unavailable,
+/// }
+///
+/// impl CacheableOutcome for Lookup {
+///     fn is_cacheable(&self) -> bool {
+///         // A missing entry is a real answer, an outage is not
+///         !matches!(self, Lookup::Unavailable)
+///     }
+/// }
+///
+/// assert!(Lookup::Found("x".to_string()).is_cacheable());
+/// assert!(Lookup::NotFound.is_cacheable());
+/// assert!(!Lookup::Unavailable.is_cacheable());
+/// assert!(!Err::<u32, String>("boom".to_string()).is_cacheable());
+/// ```
+pub trait CacheableOutcome {
+    /// Returns `true` if this value should be cached.
+    fn is_cacheable(&self) -> bool;
+}
+
+/// `Ok` values are cacheable, `Err` values are not.
+impl<T, E> CacheableOutcome for Result<T, E> {
+    fn is_cacheable(&self) -> bool {
+        self.is_ok()
+    }
+}
+
+/// `Some` values are cacheable, `None` is not.
+impl<T> CacheableOutcome for Option<T> {
+    fn is_cacheable(&self) -> bool {
+        self.is_some()
+    }
+}
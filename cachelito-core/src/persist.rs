@@ -0,0 +1,634 @@
+//! # Cache Persistence
+//!
+//! Disk snapshots of global caches, for warm starts across process restarts.
+//!
+//! Caches declared with `#[cache(persist = true)]` register themselves here (by name)
+//! the first time they are used. [`save_all`] writes one snapshot file per registered
+//! cache, and [`load_all`] reads them back. Every entry keeps its key, value, remaining
+//! time-to-live and access frequency; entries that expire while the process is down are
+//! dropped on load.
+//!
+//! Snapshot files start with a header holding the format version and a checksum of the
+//! payload. Files with an unknown version, a bad checksum or undecodable values are
+//! skipped and reported in the [`LoadReport`], never causing a panic.
+//!
+//! Loading usually happens at startup, before the cached functions run. Snapshots of
+//! caches that are not registered yet are kept in memory and applied when the cache
+//! registers itself.
+//!
+//! Requires the `persist` feature, and values implementing `serde::Serialize` and
+//! `serde::de::DeserializeOwned`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use cachelito_core::persist;
+//!
+//! // At startup
+//! let report = persist::load_all("cache-snapshots").unwrap();
+//! for (path, err) in &report.skipped {
+//!     eprintln!("ignoring snapshot {}: {}", path.display(), err);
+//! }
+//!
+//! // Before shutting down
+//! persist::save_all("cache-snapshots").unwrap();
+//! ```
+
+use crate::utils::fnv1a_64;
+use crate::{CacheEntry, GlobalCache, MemoryEstimator};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Version of the snapshot format written by [`save_all`].
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// First word of the header of every snapshot file.
+const SNAPSHOT_MAGIC: &str = "cachelito-snapshot";
+
+/// File extension of snapshot files.
+const SNAPSHOT_EXTENSION: &str = "snapshot";
+
+/// Registered caches, by name.
+static PERSIST_REGISTRY: Lazy<RwLock<HashMap<String, Arc<dyn PersistentCache>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Verified snapshot payloads of caches that were not registered when they were loaded.
+static PENDING_SNAPSHOTS: Lazy<Mutex<HashMap<String, Vec<u8>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Error raised while saving or loading a snapshot.
+#[derive(Debug)]
+pub enum PersistError {
+    /// Reading or writing a snapshot file failed
+    Io(io::Error),
+    /// Cached values could not be serialized or deserialized
+    Serialization(String),
+    /// The file is not a valid snapshot, or its checksum does not match its content
+    Corrupted(String),
+    /// The snapshot was written with another version of the format
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::Io(err) => write!(f, "I/O error: {}", err),
+            PersistError::Serialization(msg) => write!(f, "serialization error: {}", msg),
+            PersistError::Corrupted(msg) => write!(f, "corrupted snapshot: {}", msg),
+            PersistError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported snapshot version {} (expected {})",
+                version, SNAPSHOT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PersistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PersistError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PersistError {
+    fn from(err: io::Error) -> Self {
+        PersistError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for PersistError {
+    fn from(err: serde_json::Error) -> Self {
+        PersistError::Serialization(err.to_string())
+    }
+}
+
+/// Outcome of [`load_all`].
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    /// Caches whose snapshot was restored
+    pub loaded: Vec<String>,
+    /// Caches not registered yet; their snapshot is restored when they register
+    pub deferred: Vec<String>,
+    /// Snapshot files that could not be used, and why
+    pub skipped: Vec<(PathBuf, PersistError)>,
+}
+
+/// Type-erased view of a [`GlobalCache`] with serializable values.
+trait PersistentCache: Send + Sync {
+    fn snapshot(&self, name: &str) -> Result<Vec<u8>, PersistError>;
+    fn restore(&self, payload: &[u8]) -> Result<usize, PersistError>;
+}
+
+#[derive(Serialize)]
+struct SnapshotRef<'a, R> {
+    name: &'a str,
+    saved_at: u64,
+    entries: Vec<EntryRef<'a, R>>,
+}
+
+#[derive(Serialize)]
+struct EntryRef<'a, R> {
+    key: &'a str,
+    value: &'a R,
+    remaining_ttl_ms: Option<u64>,
+    ttl: Option<u64>,
+    frequency: u64,
+}
+
+#[derive(Deserialize)]
+struct Snapshot<R> {
+    saved_at: u64,
+    entries: Vec<SnapshotEntry<R>>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotEntry<R> {
+    key: String,
+    value: R,
+    remaining_ttl_ms: Option<u64>,
+    ttl: Option<u64>,
+    frequency: u64,
+}
+
+/// Reads only the cache name of a payload, whatever its value type.
+#[derive(Deserialize)]
+struct SnapshotName {
+    name: String,
+}
+
+/// A registered cache, and how restored entries are stored into it.
+struct Registered<R: 'static> {
+    cache: GlobalCache<'static, R>,
+    /// Stores an entry, measuring its value first for caches with `max_memory`
+    insert: fn(&GlobalCache<'static, R>, &str, CacheEntry<R>),
+}
+
+impl<R> PersistentCache for Registered<R>
+where
+    R: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    fn snapshot(&self, name: &str) -> Result<Vec<u8>, PersistError> {
+        // Entries are written in eviction order (shard by shard), so that restoring them keeps it
+        let shards = self.cache.shard_views();
+        let locked: Vec<_> = shards
+            .iter()
+            .map(|shard| {
+                let order: Vec<String> = shard.order.lock().iter().cloned().collect();
+                (order, shard.map.read())
+            })
+            .collect();
+
+        let entries = locked
+            .iter()
+            .flat_map(|(order, map)| order.iter().map(move |key| (key, map)))
+            .filter_map(|(key, map)| {
+                let (key, entry) = map.get_key_value(key)?;
+                let remaining_ttl_ms = match entry.ttl.or(self.cache.ttl) {
+                    Some(ttl) => {
+                        let remaining = Duration::from_secs(ttl)
+                            .checked_sub(entry.inserted_at.elapsed())
+                            .filter(|remaining| !remaining.is_zero())?;
+                        Some(remaining.as_millis() as u64)
+                    }
+                    None => None,
+                };
+                Some(EntryRef {
+                    key,
+                    value: &entry.value,
+                    remaining_ttl_ms,
+                    ttl: entry.ttl,
+                    frequency: entry.frequency,
+                })
+            })
+            .collect();
+
+        let snapshot = SnapshotRef {
+            name,
+            saved_at: unix_millis(),
+            entries,
+        };
+        Ok(serde_json::to_vec(&snapshot)?)
+    }
+
+    fn restore(&self, payload: &[u8]) -> Result<usize, PersistError> {
+        let snapshot: Snapshot<R> = serde_json::from_slice(payload)?;
+        let downtime = unix_millis().saturating_sub(snapshot.saved_at);
+        let now = Instant::now();
+        let mut restored = 0;
+
+        for entry in snapshot.entries {
+            let mut inserted_at = now;
+            if let Some(remaining) = entry.remaining_ttl_ms {
+                // Time spent on disk counts towards the TTL
+                let Some(remaining) = remaining.checked_sub(downtime).filter(|r| *r > 0) else {
+                    continue;
+                };
+                // Backdate the entry so that it expires after its remaining TTL
+                if let Some(ttl) = entry.ttl.or(self.cache.ttl) {
+                    let age =
+                        Duration::from_secs(ttl).saturating_sub(Duration::from_millis(remaining));
+                    inserted_at = now.checked_sub(age).unwrap_or(now);
+                }
+            }
+
+            (self.insert)(
+                &self.cache,
+                &entry.key,
+                CacheEntry {
+                    value: entry.value,
+                    inserted_at,
+                    frequency: entry.frequency,
+                    ttl: entry.ttl,
+                    size: 0,
+                    cost: Duration::ZERO,
+                },
+            );
+            restored += 1;
+        }
+
+        Ok(restored)
+    }
+}
+
+/// Register a cache for persistence under a given name.
+///
+/// This is called automatically by the `#[cache]` macro for functions declared with
+/// `persist = true`. If [`load_all`] already read a snapshot for this name, it is
+/// restored into the cache now; a snapshot that cannot be decoded is discarded.
+///
+/// # Parameters
+///
+/// * `name` - The name to register the cache under (typically the function name)
+/// * `cache` - The cache to save and restore
+pub fn register<R>(name: &str, cache: GlobalCache<'static, R>)
+where
+    R: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    register_cache(
+        name,
+        Arc::new(Registered {
+            cache,
+            insert: GlobalCache::insert_entry,
+        }),
+    );
+}
+
+/// Register a cache with `max_memory` for persistence under a given name.
+///
+/// Like [`register`], but restored values are measured, so that they count against
+/// the `max_memory` of the cache and the global [`MemoryBudget`](crate::MemoryBudget).
+pub fn register_with_memory<R>(name: &str, cache: GlobalCache<'static, R>)
+where
+    R: Serialize + DeserializeOwned + Clone + Send + Sync + MemoryEstimator + 'static,
+{
+    register_cache(
+        name,
+        Arc::new(Registered {
+            cache,
+            insert: GlobalCache::insert_entry_with_memory,
+        }),
+    );
+}
+
+fn register_cache(name: &str, cache: Arc<dyn PersistentCache>) {
+    let pending = {
+        let mut pending = PENDING_SNAPSHOTS.lock();
+        PERSIST_REGISTRY
+            .write()
+            .insert(name.to_string(), cache.clone());
+        pending.remove(name)
+    };
+
+    // Restore outside the locks: it may notify removal listeners
+    if let Some(payload) = pending {
+        let _ = cache.restore(&payload);
+    }
+}
+
+/// List the names of all registered caches.
+pub fn list() -> Vec<String> {
+    PERSIST_REGISTRY.read().keys().cloned().collect()
+}
+
+/// Write a snapshot of every registered cache into `dir`.
+///
+/// The directory is created if needed, and each cache is written to its own
+/// `<name>.snapshot` file, replacing the previous snapshot atomically.
+///
+/// # Returns
+///
+/// The names of the saved caches, or the first error encountered.
+pub fn save_all(dir: impl AsRef<Path>) -> Result<Vec<String>, PersistError> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    let caches: Vec<(String, Arc<dyn PersistentCache>)> = PERSIST_REGISTRY
+        .read()
+        .iter()
+        .map(|(name, cache)| (name.clone(), cache.clone()))
+        .collect();
+
+    let mut saved = Vec::with_capacity(caches.len());
+    for (name, cache) in caches {
+        let payload = cache.snapshot(&name)?;
+        let path = snapshot_path(dir, &name);
+        // Write next to the target first, so that a crash never leaves a truncated snapshot
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, encode(&payload))?;
+        fs::rename(&tmp_path, &path)?;
+        saved.push(name);
+    }
+
+    Ok(saved)
+}
+
+/// Load every snapshot found in `dir`.
+///
+/// Snapshots of registered caches are restored immediately, merging with their
+/// current entries; the others are kept until their cache registers. Invalid files
+/// are skipped and listed in [`LoadReport::skipped`].
+///
+/// A missing directory is treated as empty.
+///
+/// # Returns
+///
+/// A [`LoadReport`], or an error if the directory cannot be read.
+pub fn load_all(dir: impl AsRef<Path>) -> Result<LoadReport, PersistError> {
+    let mut report = LoadReport::default();
+    let entries = match fs::read_dir(dir.as_ref()) {
+        Ok(entries) => entries,
+        // Nothing has been saved yet: a cold start, not an error
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(report),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == SNAPSHOT_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        match load_file(&path) {
+            Ok(LoadOutcome::Restored(name)) => report.loaded.push(name),
+            Ok(LoadOutcome::Deferred(name)) => report.deferred.push(name),
+            Err(err) => report.skipped.push((path, err)),
+        }
+    }
+
+    Ok(report)
+}
+
+enum LoadOutcome {
+    Restored(String),
+    Deferred(String),
+}
+
+fn load_file(path: &Path) -> Result<LoadOutcome, PersistError> {
+    let data = fs::read(path)?;
+    let payload = decode(&data)?;
+    let SnapshotName { name } = serde_json::from_slice(payload)?;
+
+    let cache = {
+        let mut pending = PENDING_SNAPSHOTS.lock();
+        let cache = PERSIST_REGISTRY.read().get(&name).cloned();
+        if cache.is_none() {
+            pending.insert(name.clone(), payload.to_vec());
+        }
+        cache
+    };
+
+    match cache {
+        Some(cache) => {
+            cache.restore(payload)?;
+            Ok(LoadOutcome::Restored(name))
+        }
+        None => Ok(LoadOutcome::Deferred(name)),
+    }
+}
+
+/// Path of the snapshot file of a cache; characters unsafe in file names are replaced.
+fn snapshot_path(dir: &Path, name: &str) -> PathBuf {
+    let file_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{}.{}", file_name, SNAPSHOT_EXTENSION))
+}
+
+/// Prefix a payload with the snapshot header: magic, version and checksum.
+fn encode(payload: &[u8]) -> Vec<u8> {
+    let mut data = format!(
+        "{} {} {:016x}\n",
+        SNAPSHOT_MAGIC,
+        SNAPSHOT_VERSION,
+        checksum(payload)
+    )
+    .into_bytes();
+    data.extend_from_slice(payload);
+    data
+}
+
+/// Validate the snapshot header and return the payload.
+fn decode(data: &[u8]) -> Result<&[u8], PersistError> {
+    let corrupted = |msg: &str| PersistError::Corrupted(msg.to_string());
+
+    let newline = data
+        .iter()
+        .position(|b| *b == b'\n')
+        .ok_or_else(|| corrupted("missing header"))?;
+    let header = std::str::from_utf8(&data[..newline]).map_err(|_| corrupted("invalid header"))?;
+    let payload = &data[newline + 1..];
+
+    let mut parts = header.split(' ');
+    if parts.next() != Some(SNAPSHOT_MAGIC) {
+        return Err(corrupted("not a cachelito snapshot"));
+    }
+    let version: u32 = parts
+        .next()
+        .and_then(|version| version.parse().ok())
+        .ok_or_else(|| corrupted("invalid version"))?;
+    if version != SNAPSHOT_VERSION {
+        return Err(PersistError::UnsupportedVersion(version));
+    }
+    let expected = parts
+        .next()
+        .and_then(|sum| u64::from_str_radix(sum, 16).ok())
+        .ok_or_else(|| corrupted("invalid checksum"))?;
+    if checksum(payload) != expected {
+        return Err(corrupted("checksum mismatch"));
+    }
+
+    Ok(payload)
+}
+
+/// Checksum of a payload, enough to detect truncated or partially written files.
+fn checksum(data: &[u8]) -> u64 {
+    fnv1a_64(data)
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EvictionPolicy;
+    use parking_lot::Mutex;
+    use std::collections::VecDeque;
+
+    #[cfg(feature = "stats")]
+    use crate::CacheStats;
+
+    /// A standalone cache with leaked storage, as the macro would create with statics
+    fn leaked_cache<R: Clone + 'static>(ttl: Option<u64>) -> GlobalCache<'static, R> {
+        GlobalCache::new(
+            Box::leak(Box::new(RwLock::new(HashMap::new()))),
+            Box::leak(Box::new(Mutex::new(VecDeque::new()))),
+            None,
+            None,
+            EvictionPolicy::LRU,
+            ttl,
+            None,
+            #[cfg(feature = "stats")]
+            Box::leak(Box::new(CacheStats::new())),
+        )
+    }
+
+    /// The payload [`save_all`] would write for `cache`
+    fn snapshot_of<R>(cache: GlobalCache<'static, R>, name: &str) -> Vec<u8>
+    where
+        R: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let registered = Registered {
+            cache,
+            insert: GlobalCache::insert_entry,
+        };
+        registered.snapshot(name).unwrap()
+    }
+
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("cachelito-persist-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_round_trip_keeps_ttl_and_frequency() {
+        let dir = temp_dir("round_trip");
+        let cache = leaked_cache::<String>(Some(60));
+        cache.insert("a", "alpha".to_string());
+        cache.insert_with_ttl("b", "beta".to_string(), Some(30));
+        cache.map.write().get_mut("a").unwrap().frequency = 7;
+        register("persist_round_trip", cache.clone());
+
+        let saved = save_all(&dir).unwrap();
+        assert!(saved.contains(&"persist_round_trip".to_string()));
+
+        cache.clear();
+        let report = load_all(&dir).unwrap();
+        assert!(report.loaded.contains(&"persist_round_trip".to_string()));
+        assert!(report.skipped.is_empty());
+
+        let map = cache.map.read();
+        assert_eq!(map["a"].value, "alpha");
+        assert_eq!(map["a"].frequency, 7);
+        assert_eq!(map["b"].ttl, Some(30));
+        // The remaining TTL is kept rather than restarted
+        assert!(map["b"].inserted_at.elapsed() < Duration::from_secs(1));
+        assert!(!map["b"].is_expired(Some(60)));
+        assert_eq!(*cache.order.lock(), vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_snapshot_is_restored_on_late_registration() {
+        let dir = temp_dir("deferred");
+        let cache = leaked_cache::<u32>(None);
+        cache.insert("x", 1);
+        fs::create_dir_all(&dir).unwrap();
+        let payload = snapshot_of(cache, "persist_deferred");
+        fs::write(snapshot_path(&dir, "persist_deferred"), encode(&payload)).unwrap();
+
+        let report = load_all(&dir).unwrap();
+        assert_eq!(report.deferred, vec!["persist_deferred".to_string()]);
+
+        let late = leaked_cache::<u32>(None);
+        register("persist_deferred", late.clone());
+        assert_eq!(late.get("x"), Some(1));
+    }
+
+    #[test]
+    fn test_corrupted_snapshots_are_skipped() {
+        let dir = temp_dir("corrupted");
+        fs::create_dir_all(&dir).unwrap();
+
+        let payload = br#"{"name":"persist_corrupted","saved_at":0,"entries":[]}"#;
+        let mut tampered = encode(payload);
+        let last = tampered.len() - 2;
+        tampered[last] = b'X';
+        fs::write(dir.join("tampered.snapshot"), tampered).unwrap();
+        fs::write(dir.join("garbage.snapshot"), b"not a snapshot").unwrap();
+        let future = String::from_utf8(encode(payload))
+            .unwrap()
+            .replacen(" 1 ", " 99 ", 1);
+        fs::write(dir.join("future.snapshot"), future).unwrap();
+
+        let report = load_all(&dir).unwrap();
+        assert!(report.loaded.is_empty());
+        assert_eq!(report.skipped.len(), 3);
+        assert!(report
+            .skipped
+            .iter()
+            .any(|(_, err)| matches!(err, PersistError::UnsupportedVersion(99))));
+        assert!(report
+            .skipped
+            .iter()
+            .all(|(_, err)| !matches!(err, PersistError::Io(_))));
+    }
+
+    #[test]
+    fn test_missing_directory_is_empty() {
+        let report = load_all(temp_dir("missing")).unwrap();
+        assert!(
+            report.loaded.is_empty() && report.deferred.is_empty() && report.skipped.is_empty()
+        );
+    }
+
+    #[test]
+    fn test_expired_entries_are_not_saved() {
+        let cache = leaked_cache::<u32>(Some(60));
+        cache.insert_with_ttl("gone", 1, Some(0));
+        cache.insert("kept", 2);
+
+        let payload = snapshot_of(cache, "persist_expired");
+        let snapshot: Snapshot<u32> = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(snapshot.entries.len(), 1);
+        assert_eq!(snapshot.entries[0].key, "kept");
+    }
+}
@@ -0,0 +1,937 @@
+//! # Policy Engine
+//!
+//! The eviction logic shared by [`ThreadLocalCache`](crate::ThreadLocalCache),
+//! [`GlobalCache`](crate::GlobalCache) and [`AsyncGlobalCache`](crate::AsyncGlobalCache).
+//!
+//! The caches only differ in how they synchronize their storage (`RefCell`, `RwLock` or
+//! `DashMap`). Once they have locked it, they hand it to the [`PolicyEngine`] as an
+//! [`EntryStore`], together with their order queue, and the engine does the rest:
+//!
+//! - Lookups, telling expired entries apart
+//! - Insertions, enforcing `limit` and `max_memory`, and scheduling expirations
+//! - Removals, purges of expired entries and invalidations
+//! - Memory accounting, with or without a [`MemoryUsage`] counter
+//!
+//! For the eviction policy, it asks the [`EvictionStrategy`] of the cache (custom, or the
+//! one of its [`EvictionPolicy`]):
+//!
+//! - What a hit changes: the entry (e.g. its frequency counter), the position of its key
+//! - Which entry is evicted next, skipping the keys of entries removed elsewhere
+//!
+//! Removed entries are collected into [`PendingRemovals`], for the caches to notify once
+//! they released their locks.
+
+use crate::cache_entry::compute_cost;
+use crate::eviction_strategy::{
+    ArcStrategy, FifoStrategy, GdsfStrategy, LfuStrategy, LruStrategy, RandomStrategy,
+    S3FifoStrategy, SieveStrategy, TlruStrategy,
+};
+use crate::removal::PendingRemovals;
+use crate::timer_wheel::{now_tick, tick_at, Timer};
+use crate::{
+    CacheEntry, EvictionCandidates, EvictionPolicy, EvictionState, EvictionStrategy, MemoryUsage,
+    RemovalCause, TimerWheel,
+};
+use dashmap::DashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The entries of a cache, as seen by the [`PolicyEngine`].
+///
+/// Implemented by the maps of the caches, once locked (or borrowed) by them.
+pub(crate) trait EntryStore<R> {
+    /// Calls `f` with the entry stored under `key`, returning `false` if there is none.
+    fn inspect(&self, key: &str, f: &mut dyn FnMut(&CacheEntry<R>)) -> bool;
+
+    /// Calls `f` with the mutable entry stored under `key`, returning `false` if there is none.
+    fn update(&mut self, key: &str, f: &mut dyn FnMut(&mut CacheEntry<R>)) -> bool;
+
+    /// Removes and returns the entry stored under `key`.
+    fn take(&mut self, key: &str) -> Option<CacheEntry<R>>;
+
+    /// Stores `entry` under `key`, returning the entry it replaces.
+    fn put(&mut self, key: String, entry: CacheEntry<R>) -> Option<CacheEntry<R>>;
+
+    /// Returns the number of entries.
+    fn len(&self) -> usize;
+
+    /// Calls `f` with every entry and its key.
+    fn for_each(&self, f: &mut dyn FnMut(&str, &CacheEntry<R>));
+}
+
+impl<R> EntryStore<R> for HashMap<String, CacheEntry<R>> {
+    fn inspect(&self, key: &str, f: &mut dyn FnMut(&CacheEntry<R>)) -> bool {
+        self.get(key).map(f).is_some()
+    }
+
+    fn update(&mut self, key: &str, f: &mut dyn FnMut(&mut CacheEntry<R>)) -> bool {
+        self.get_mut(key).map(f).is_some()
+    }
+
+    fn take(&mut self, key: &str) -> Option<CacheEntry<R>> {
+        self.remove(key)
+    }
+
+    fn put(&mut self, key: String, entry: CacheEntry<R>) -> Option<CacheEntry<R>> {
+        self.insert(key, entry)
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn for_each(&self, f: &mut dyn FnMut(&str, &CacheEntry<R>)) {
+        for (key, entry) in self {
+            f(key, entry);
+        }
+    }
+}
+
+/// Entries are read one shard lock at a time, never while another is held.
+impl<R> EntryStore<R> for &DashMap<String, CacheEntry<R>> {
+    fn inspect(&self, key: &str, f: &mut dyn FnMut(&CacheEntry<R>)) -> bool {
+        self.get(key).map(|entry| f(&entry)).is_some()
+    }
+
+    fn update(&mut self, key: &str, f: &mut dyn FnMut(&mut CacheEntry<R>)) -> bool {
+        self.get_mut(key).map(|mut entry| f(&mut entry)).is_some()
+    }
+
+    fn take(&mut self, key: &str) -> Option<CacheEntry<R>> {
+        self.remove(key).map(|(_, entry)| entry)
+    }
+
+    fn put(&mut self, key: String, entry: CacheEntry<R>) -> Option<CacheEntry<R>> {
+        self.insert(key, entry)
+    }
+
+    fn len(&self) -> usize {
+        DashMap::len(self)
+    }
+
+    /// Only one shard is read-locked at a time: `f` must not write to the map.
+    fn for_each(&self, f: &mut dyn FnMut(&str, &CacheEntry<R>)) {
+        for entry in self.iter() {
+            f(entry.key(), entry.value());
+        }
+    }
+}
+
+/// What a lookup found under a key.
+pub(crate) enum Lookup<R> {
+    /// A live entry, with a clone of its value
+    Hit(R),
+    /// An entry past its TTL, still stored
+    Expired,
+    /// No entry
+    Miss,
+}
+
+/// Applies the limits, TTL and eviction strategy of a cache to its entries and order queue.
+///
+/// Cheap to copy: the caches build one from their configuration when they need it.
+pub(crate) struct PolicyEngine<'s, R> {
+    policy: EvictionPolicy,
+    ttl: Option<u64>,
+    frequency_weight: Option<f64>,
+    strategy: Option<&'s dyn EvictionStrategy<R>>,
+    limit: Option<usize>,
+    max_memory: Option<usize>,
+    timers: Option<&'s TimerWheel>,
+    memory: Option<&'s MemoryUsage>,
+    state: Option<&'s EvictionState>,
+}
+
+impl<R> Clone for PolicyEngine<'_, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for PolicyEngine<'_, R> {}
+
+impl<'s, R> PolicyEngine<'s, R> {
+    /// Creates the engine of a cache with the given policy, TTL and TLRU frequency weight.
+    ///
+    /// A custom `strategy` takes precedence over `policy`.
+    pub(crate) fn new(
+        policy: EvictionPolicy,
+        ttl: Option<u64>,
+        frequency_weight: Option<f64>,
+        strategy: Option<&'s dyn EvictionStrategy<R>>,
+    ) -> Self {
+        Self {
+            policy,
+            ttl,
+            frequency_weight,
+            strategy,
+            limit: None,
+            max_memory: None,
+            timers: None,
+            memory: None,
+            state: None,
+        }
+    }
+
+    /// Sets the entry-count and memory limits enforced on insertions.
+    pub(crate) fn with_limits(mut self, limit: Option<usize>, max_memory: Option<usize>) -> Self {
+        self.limit = limit;
+        self.max_memory = max_memory;
+        self
+    }
+
+    /// Sets the timer wheel scheduling the expirations of the entries.
+    pub(crate) fn with_timer_wheel(mut self, timers: Option<&'s TimerWheel>) -> Self {
+        self.timers = timers;
+        self
+    }
+
+    /// Sets the counter keeping the total size of the entries.
+    ///
+    /// Without one, the sizes of the entries are summed when needed.
+    pub(crate) fn with_memory_usage(mut self, memory: Option<&'s MemoryUsage>) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Sets the state of the built-in strategies that keep some, such as the GDSF clock.
+    ///
+    /// Without one, these strategies start from a fresh state on every call.
+    pub(crate) fn with_eviction_state(mut self, state: Option<&'s EvictionState>) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Returns the strategy to tell about removals: the custom one, or the built-in one
+    /// keeping its state in the [`EvictionState`] of the cache.
+    pub(crate) fn removal_strategy(&self) -> Option<&'s dyn EvictionStrategy<R>> {
+        if self.strategy.is_some() {
+            return self.strategy;
+        }
+        let state = self.state?;
+        match self.policy {
+            EvictionPolicy::GDSF => Some(&state.gdsf),
+            EvictionPolicy::SIEVE => Some(&state.sieve),
+            EvictionPolicy::S3FIFO => Some(&state.s3fifo),
+            _ => None,
+        }
+    }
+
+    /// Calls `f` with the strategy of the cache.
+    fn with_strategy<T>(&self, f: impl FnOnce(&dyn EvictionStrategy<R>) -> T) -> T {
+        if let Some(strategy) = self.strategy {
+            return f(strategy);
+        }
+        match self.policy {
+            EvictionPolicy::FIFO => f(&FifoStrategy),
+            EvictionPolicy::LRU => f(&LruStrategy),
+            EvictionPolicy::LFU => f(&LfuStrategy),
+            EvictionPolicy::ARC => f(&ArcStrategy),
+            EvictionPolicy::Random => f(&RandomStrategy),
+            EvictionPolicy::TLRU => f(&TlruStrategy {
+                ttl: self.ttl,
+                frequency_weight: self.frequency_weight,
+            }),
+            EvictionPolicy::GDSF => match self.state {
+                Some(state) => f(&state.gdsf),
+                None => f(&GdsfStrategy::default()),
+            },
+            EvictionPolicy::SIEVE => match self.state {
+                Some(state) => f(&state.sieve),
+                None => f(&SieveStrategy::default()),
+            },
+            EvictionPolicy::S3FIFO => match self.state {
+                Some(state) => f(&state.s3fifo),
+                None => f(&S3FifoStrategy::default()),
+            },
+        }
+    }
+
+    /// Returns `true` if hits move their key to the back of the order queue.
+    pub(crate) fn promotes_on_access(&self) -> bool {
+        self.with_strategy(|strategy| strategy.promotes_on_access())
+    }
+
+    /// Returns `true` if hits update their entry, through [`on_access`](Self::on_access).
+    pub(crate) fn updates_on_access(&self) -> bool {
+        self.with_strategy(|strategy| strategy.updates_on_access())
+    }
+
+    /// Returns `true` if hits need any bookkeeping at all.
+    pub(crate) fn tracks_accesses(&self) -> bool {
+        self.promotes_on_access() || self.updates_on_access()
+    }
+
+    /// Records the insertion of `entry` under `key`.
+    ///
+    /// Inside [`with_compute_cost`](crate::with_compute_cost), the entry gets the cost
+    /// given to it.
+    pub(crate) fn on_insert(&self, key: &str, entry: &mut CacheEntry<R>) {
+        if let Some(cost) = compute_cost() {
+            entry.cost = cost;
+        }
+        self.with_strategy(|strategy| strategy.on_insert(key, entry));
+    }
+
+    /// Records a hit on `entry`.
+    pub(crate) fn on_access(&self, key: &str, entry: &mut CacheEntry<R>) {
+        if self.updates_on_access() {
+            self.with_strategy(|strategy| strategy.on_access(key, entry));
+        }
+    }
+
+    /// Records a hit on the entry stored under `key`, without locking the entries.
+    ///
+    /// Called on every hit, before the (possibly buffered) [`on_access`](Self::on_access).
+    pub(crate) fn on_hit(&self, key: &str) {
+        self.with_strategy(|strategy| strategy.on_hit(key));
+    }
+
+    /// Returns the position in `order` of the key to evict next.
+    ///
+    /// The strategy may update entries and move keys to the back of `order` meanwhile.
+    pub(crate) fn choose_victim(
+        &self,
+        store: &mut dyn EntryStore<R>,
+        order: &mut VecDeque<String>,
+    ) -> Option<usize> {
+        let mut candidates = EvictionCandidates::new(store, order);
+        self.with_strategy(|strategy| strategy.choose_victim(&mut candidates))
+    }
+
+    /// Evicts the entry chosen by the strategy, removing its key from `order`.
+    ///
+    /// Keys whose entry was already removed (orphaned keys) are dropped from `order`
+    /// along the way, so that they do not count against the entry limit.
+    ///
+    /// # Returns
+    ///
+    /// The key and entry evicted, or `None` if there was nothing left to evict.
+    pub(crate) fn evict(
+        &self,
+        store: &mut impl EntryStore<R>,
+        order: &mut VecDeque<String>,
+    ) -> Option<(String, CacheEntry<R>)> {
+        while !order.is_empty() {
+            // Without a victim, only orphaned keys are left (or the strategy has no opinion)
+            let position = self
+                .choose_victim(store, order)
+                .filter(|&position| position < order.len())
+                .unwrap_or(0);
+            let key = order.remove(position)?;
+            if let Some(entry) = store.take(&key) {
+                return Some((key, entry));
+            }
+        }
+        None
+    }
+
+    /// Evicts the entry chosen by the strategy into `removed`, with the given `cause`.
+    ///
+    /// # Returns
+    ///
+    /// `false` if there was nothing left to evict.
+    pub(crate) fn evict_into(
+        &self,
+        store: &mut impl EntryStore<R>,
+        order: &mut VecDeque<String>,
+        removed: &mut PendingRemovals<'_, R>,
+        cause: RemovalCause,
+    ) -> bool {
+        match self.evict(store, order) {
+            Some((key, entry)) => {
+                self.release(&entry);
+                removed.push_entry(key, entry, cause);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Looks `key` up, without recording the hit.
+    pub(crate) fn lookup(&self, store: &impl EntryStore<R>, key: &str) -> Lookup<R>
+    where
+        R: Clone,
+    {
+        let mut found = Lookup::Miss;
+        store.inspect(key, &mut |entry| {
+            found = if entry.is_expired(self.ttl) {
+                Lookup::Expired
+            } else {
+                Lookup::Hit(entry.value.clone())
+            };
+        });
+        found
+    }
+
+    /// Records a hit on the entry stored under `key`, such as its frequency counter.
+    pub(crate) fn record_access(&self, store: &mut impl EntryStore<R>, key: &str) {
+        store.update(key, &mut |entry| self.on_access(key, entry));
+    }
+
+    /// Returns the total size of the entries of `store`.
+    pub(crate) fn memory_in_use(&self, store: &impl EntryStore<R>) -> usize {
+        match self.memory {
+            Some(memory) => memory.bytes(),
+            None => {
+                let mut bytes = 0;
+                store.for_each(&mut |_, entry| bytes += entry.size);
+                bytes
+            }
+        }
+    }
+
+    /// Adds the size of an entry entering the cache to the memory counter.
+    fn charge(&self, entry: &CacheEntry<R>) {
+        if let Some(memory) = self.memory {
+            memory.add(entry.size);
+        }
+    }
+
+    /// Subtracts the size of an entry leaving the cache from the memory counter.
+    fn release(&self, entry: &CacheEntry<R>) {
+        if let Some(memory) = self.memory {
+            memory.sub(entry.size);
+        }
+    }
+
+    /// Returns the timer wheel tick at which `entry` expires, if it expires at all.
+    fn expiry_tick(&self, entry: &CacheEntry<R>) -> Option<u64> {
+        entry.expires_at(self.ttl).map(tick_at)
+    }
+
+    /// Returns the timer wheel tick at which the entry stored under `key` expires.
+    fn expiry_tick_of(&self, store: &impl EntryStore<R>, key: &str) -> Option<u64> {
+        let mut at = None;
+        store.inspect(key, &mut |entry| at = self.expiry_tick(entry));
+        at
+    }
+
+    /// Schedules the expiration of the entry stored under `key` in the timer wheel.
+    fn schedule_expiry(&self, store: &impl EntryStore<R>, key: &str) {
+        let Some(timers) = self.timers else {
+            return;
+        };
+        if let Some(at) = self.expiry_tick_of(store, key) {
+            timers.schedule(key, at, now_tick());
+        }
+        // Timers of removed or replaced entries are dropped once they pile up
+        if timers.needs_compaction(store.len()) {
+            timers.retain(|key, at| self.expiry_tick_of(store, key) == Some(at));
+        }
+    }
+
+    /// Stores `entry` under `key` and moves the key to the back of `order`, without
+    /// enforcing the limits.
+    ///
+    /// The entry replaced, if any, is recorded in `removed` with [`RemovalCause::Replaced`].
+    pub(crate) fn put(
+        &self,
+        store: &mut impl EntryStore<R>,
+        order: &mut VecDeque<String>,
+        key: &str,
+        mut entry: CacheEntry<R>,
+        removed: &mut PendingRemovals<'_, R>,
+    ) {
+        self.on_insert(key, &mut entry);
+        self.charge(&entry);
+        if let Some(old) = store.put(key.to_string(), entry) {
+            self.release(&old);
+            removed.push(key.to_string(), old.value, RemovalCause::Replaced);
+            if let Some(position) = order.iter().position(|k| k == key) {
+                order.remove(position);
+            }
+        }
+        order.push_back(key.to_string());
+        self.schedule_expiry(store, key);
+    }
+
+    /// Stores `entry` under `key`, then evicts entries until the cache is within its
+    /// `max_memory` and `limit` again.
+    ///
+    /// An entry larger than `max_memory` by itself is removed again, with
+    /// [`RemovalCause::Memory`], instead of emptying the cache for it.
+    pub(crate) fn insert(
+        &self,
+        store: &mut impl EntryStore<R>,
+        order: &mut VecDeque<String>,
+        key: &str,
+        entry: CacheEntry<R>,
+        removed: &mut PendingRemovals<'_, R>,
+    ) {
+        let size = entry.size;
+        self.put(store, order, key, entry, removed);
+
+        if let Some(max_memory) = self.max_memory {
+            if size > max_memory {
+                if let Some(entry) = store.take(key) {
+                    self.release(&entry);
+                    removed.push_entry(key.to_string(), entry, RemovalCause::Memory);
+                }
+                order.retain(|k| k != key);
+                return;
+            }
+            while self.memory_in_use(store) > max_memory {
+                if !self.evict_into(store, order, removed, RemovalCause::Memory) {
+                    break;
+                }
+            }
+        }
+        if self.limit.is_some_and(|limit| order.len() > limit) {
+            self.evict_into(store, order, removed, RemovalCause::Capacity);
+        }
+    }
+
+    /// Removes the entry stored under `key` into `removed`, with the given `cause`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was an entry.
+    pub(crate) fn remove(
+        &self,
+        store: &mut impl EntryStore<R>,
+        order: &mut VecDeque<String>,
+        key: &str,
+        removed: &mut PendingRemovals<'_, R>,
+        cause: RemovalCause,
+    ) -> bool {
+        order.retain(|k| k != key);
+        match store.take(key) {
+            Some(entry) => {
+                self.release(&entry);
+                removed.push(key.to_string(), entry.value, cause);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the entries whose key matches `check` into `removed`, with the given `cause`.
+    pub(crate) fn remove_where(
+        &self,
+        store: &mut impl EntryStore<R>,
+        order: &mut VecDeque<String>,
+        check: impl Fn(&str) -> bool,
+        removed: &mut PendingRemovals<'_, R>,
+        cause: RemovalCause,
+    ) {
+        order.retain(|k| !check(k));
+        // Collect first: the store cannot be changed while it is iterated
+        let mut keys = Vec::new();
+        store.for_each(&mut |key, _| {
+            if check(key) {
+                keys.push(key.to_string());
+            }
+        });
+        for key in keys {
+            if let Some(entry) = store.take(&key) {
+                self.release(&entry);
+                removed.push(key, entry.value, cause);
+            }
+        }
+        // Every timer left is stale once the cache is empty
+        if let Some(timers) = self.timers.filter(|_| store.len() == 0) {
+            timers.clear();
+        }
+    }
+
+    /// Returns the timers due now, or `None` without a timer wheel.
+    ///
+    /// Taken before locking the cache, so that purges with nothing due lock nothing.
+    pub(crate) fn due_timers(&self) -> Option<Vec<Timer>> {
+        self.timers.map(|timers| timers.advance(now_tick()))
+    }
+
+    /// Removes the expired entries into `removed`, returning how many were removed.
+    ///
+    /// With a timer wheel, only the entries of the `due` timers are visited; entries
+    /// expiring later within the current tick are scheduled again. Without one, every
+    /// entry is.
+    pub(crate) fn purge_expired(
+        &self,
+        store: &mut impl EntryStore<R>,
+        order: &mut VecDeque<String>,
+        due: Option<Vec<Timer>>,
+        removed: &mut PendingRemovals<'_, R>,
+    ) -> usize {
+        let mut keys = HashSet::new();
+        match (due, self.timers) {
+            (Some(due), Some(timers)) => {
+                let now = now_tick();
+                for (key, at) in due {
+                    let mut state = None;
+                    store.inspect(&key, &mut |entry| {
+                        state = Some((self.expiry_tick(entry), entry.is_expired(self.ttl)));
+                    });
+                    match state {
+                        // Timers of removed or replaced entries are dropped
+                        Some((tick, _)) if tick != Some(at) => {}
+                        Some((_, true)) => {
+                            keys.insert(key);
+                        }
+                        // Expires later within the current tick
+                        Some((_, false)) => timers.schedule(&key, at, now),
+                        None => {}
+                    }
+                }
+            }
+            _ => store.for_each(&mut |key, entry| {
+                if entry.is_expired(self.ttl) {
+                    keys.insert(key.to_string());
+                }
+            }),
+        }
+        if keys.is_empty() {
+            return 0;
+        }
+
+        order.retain(|k| !keys.contains(k));
+        let mut purged = 0;
+        for key in keys {
+            if let Some(entry) = store.take(&key) {
+                self.release(&entry);
+                removed.push(key, entry.value, RemovalCause::Expired);
+                purged += 1;
+            }
+        }
+        purged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn store(frequencies: &[(&str, u64)]) -> (HashMap<String, CacheEntry<u64>>, VecDeque<String>) {
+        let mut map = HashMap::new();
+        let mut order = VecDeque::new();
+        for (key, frequency) in frequencies {
+            let mut entry = CacheEntry::new(*frequency);
+            entry.frequency = *frequency;
+            map.insert(key.to_string(), entry);
+            order.push_back(key.to_string());
+        }
+        (map, order)
+    }
+
+    #[test]
+    fn test_victims_by_policy() {
+        let (map, order) = store(&[("a", 5), ("b", 1), ("c", 3)]);
+        let victim = |policy| {
+            PolicyEngine::new(policy, None, None, None)
+                .choose_victim(&mut map.clone(), &mut order.clone())
+        };
+
+        assert_eq!(victim(EvictionPolicy::FIFO), Some(0));
+        assert_eq!(victim(EvictionPolicy::LRU), Some(0));
+        assert_eq!(victim(EvictionPolicy::LFU), Some(1));
+        // Scores: a = 5 × 3, b = 1 × 2, c = 3 × 1
+        assert_eq!(victim(EvictionPolicy::ARC), Some(1));
+        assert_eq!(victim(EvictionPolicy::TLRU), Some(1));
+        assert_eq!(victim(EvictionPolicy::GDSF), Some(1));
+        assert!(victim(EvictionPolicy::Random).is_some_and(|position| position < 3));
+    }
+
+    #[test]
+    fn test_evict_skips_orphaned_keys() {
+        for policy in [
+            EvictionPolicy::FIFO,
+            EvictionPolicy::LRU,
+            EvictionPolicy::LFU,
+            EvictionPolicy::ARC,
+            EvictionPolicy::Random,
+            EvictionPolicy::TLRU,
+            EvictionPolicy::GDSF,
+            EvictionPolicy::SIEVE,
+            EvictionPolicy::S3FIFO,
+        ] {
+            let (mut map, mut order) = store(&[("a", 0), ("b", 0)]);
+            map.remove("a");
+            order.push_front("gone".to_string());
+
+            let engine = PolicyEngine::new(policy, None, None, None);
+            let (key, _) = engine.evict(&mut map, &mut order).unwrap();
+            assert_eq!(key, "b", "{:?}", policy);
+            assert!(map.is_empty());
+            assert_eq!(engine.evict(&mut map, &mut order).map(|(key, _)| key), None);
+            assert!(order.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_access_bookkeeping() {
+        let mut entry = CacheEntry::new(1);
+        PolicyEngine::new(EvictionPolicy::LRU, None, None, None).on_access("a", &mut entry);
+        assert_eq!(entry.frequency, 0);
+        PolicyEngine::new(EvictionPolicy::LFU, None, None, None).on_access("a", &mut entry);
+        assert_eq!(entry.frequency, 1);
+
+        let fifo = PolicyEngine::<u64>::new(EvictionPolicy::FIFO, None, None, None);
+        assert!(!fifo.tracks_accesses());
+        let tlru = PolicyEngine::<u64>::new(EvictionPolicy::TLRU, None, None, None);
+        assert!(tlru.promotes_on_access() && tlru.updates_on_access());
+
+        // SIEVE and S3-FIFO track hits in their own state, without locking the entries
+        let state = EvictionState::new();
+        for policy in [EvictionPolicy::SIEVE, EvictionPolicy::S3FIFO] {
+            let engine =
+                PolicyEngine::new(policy, None, None, None).with_eviction_state(Some(&state));
+            assert!(!engine.tracks_accesses(), "{:?}", policy);
+            engine.on_insert("a", &mut entry);
+            engine.on_hit("a");
+            assert_eq!(entry.frequency, 1, "{:?}", policy);
+        }
+    }
+
+    fn evicted_key(
+        engine: &PolicyEngine<'_, u64>,
+        map: &mut HashMap<String, CacheEntry<u64>>,
+        order: &mut VecDeque<String>,
+    ) -> Option<String> {
+        engine.evict(map, order).map(|(key, _)| key)
+    }
+
+    /// Inserts `keys` through the engine, as the caches do.
+    fn put_keys(
+        engine: &PolicyEngine<'_, u64>,
+        map: &mut HashMap<String, CacheEntry<u64>>,
+        order: &mut VecDeque<String>,
+        keys: &[&str],
+    ) {
+        let mut removed = PendingRemovals::new(None);
+        for key in keys {
+            engine.put(map, order, key, CacheEntry::new(0), &mut removed);
+        }
+    }
+
+    /// Evicts an entry for capacity, telling the strategy as the caches do.
+    fn evict_for_capacity(
+        engine: &PolicyEngine<'_, u64>,
+        map: &mut HashMap<String, CacheEntry<u64>>,
+        order: &mut VecDeque<String>,
+    ) -> String {
+        let mut removed = PendingRemovals::new(None).with_strategy(engine.removal_strategy());
+        let (key, entry) = engine.evict(map, order).unwrap();
+        removed.push_entry(key.clone(), entry, RemovalCause::Capacity);
+        key
+    }
+
+    #[test]
+    fn test_sieve_hand() {
+        let state = EvictionState::new();
+        let engine = PolicyEngine::new(EvictionPolicy::SIEVE, None, None, None)
+            .with_eviction_state(Some(&state));
+        let (mut map, mut order) = store(&[]);
+        put_keys(&engine, &mut map, &mut order, &["a", "b", "c", "d", "e"]);
+        engine.on_hit("a");
+        engine.on_hit("b");
+
+        // The hand passes a and b, clearing their visited bits, and stops at c
+        assert_eq!(evict_for_capacity(&engine, &mut map, &mut order), "c");
+        // It resumes at d: a and b survive until the next pass
+        assert_eq!(evict_for_capacity(&engine, &mut map, &mut order), "d");
+        assert_eq!(order, ["a", "b", "e"]);
+
+        // The hand resumes at e, then starts over from the front, where b was visited again
+        put_keys(&engine, &mut map, &mut order, &["f"]);
+        engine.on_hit("b");
+        assert_eq!(evict_for_capacity(&engine, &mut map, &mut order), "e");
+        assert_eq!(evict_for_capacity(&engine, &mut map, &mut order), "a");
+        assert_eq!(order, ["b", "f"]);
+    }
+
+    #[test]
+    fn test_s3fifo_queues() {
+        let state = EvictionState::new();
+        let engine = PolicyEngine::new(EvictionPolicy::S3FIFO, None, None, None)
+            .with_eviction_state(Some(&state));
+        let (mut map, mut order) = store(&[]);
+        put_keys(&engine, &mut map, &mut order, &["a", "b", "c", "d"]);
+        engine.on_hit("a");
+        engine.on_hit("a");
+        engine.on_hit("b");
+
+        // a was hit twice in the small queue: it moves to the main queue. b was hit once
+        // only, which is not enough
+        assert_eq!(evict_for_capacity(&engine, &mut map, &mut order), "b");
+        assert_eq!(order, ["c", "d", "a"]);
+
+        // b is remembered by the ghost queue: inserted again, it enters the main queue,
+        // and outlives z (small queue) and a (older in the main queue)
+        put_keys(&engine, &mut map, &mut order, &["b", "z", "w"]);
+        for evicted in ["c", "d", "z", "a"] {
+            assert_eq!(evict_for_capacity(&engine, &mut map, &mut order), evicted);
+        }
+        assert_eq!(order, ["b", "w"]);
+    }
+
+    #[test]
+    fn test_gdsf_weighs_cost_and_size() {
+        let engine = PolicyEngine::new(EvictionPolicy::GDSF, None, None, None);
+        let (mut map, mut order) = store(&[("hot", 0), ("huge", 0), ("cheap", 0)]);
+        let mut insert = |key: &str, cost_ms: u64, size: usize| {
+            let mut entry = CacheEntry::new(0).with_cost(Duration::from_secs(1));
+            entry.size = size;
+            crate::with_compute_cost(Duration::from_millis(cost_ms), || {
+                engine.on_insert(key, &mut entry)
+            });
+            map.insert(key.to_string(), entry);
+        };
+        insert("hot", 10, 8);
+        insert("huge", 10, 8_000);
+        insert("cheap", 1, 8);
+        assert_eq!(map["cheap"].cost, Duration::from_millis(1));
+
+        // Scores: hot = 10 / 8, huge = 10 / 8000, cheap = 1 / 8
+        assert_eq!(engine.choose_victim(&mut map, &mut order), Some(1));
+        for _ in 0..2_000 {
+            engine.on_access("huge", map.get_mut("huge").unwrap());
+        }
+        assert_eq!(engine.choose_victim(&mut map, &mut order), Some(2));
+    }
+
+    #[test]
+    fn test_gdsf_ages_formerly_hot_entries() {
+        let state = EvictionState::new();
+        let engine = PolicyEngine::new(EvictionPolicy::GDSF, None, None, None)
+            .with_eviction_state(Some(&state));
+        let (mut map, mut order) = store(&[]);
+        let mut removed = PendingRemovals::new(None);
+        let mut put = |map: &mut HashMap<_, _>, order: &mut VecDeque<_>, key: &str, hits| {
+            engine.put(map, order, key, CacheEntry::new(0), &mut removed);
+            for _ in 0..hits {
+                engine.record_access(map, key);
+            }
+        };
+
+        // a was hot (priority 10), then the workload moved to keys hit twice (priority
+        // L + 3): each eviction raises L, until the new keys outrank a
+        put(&mut map, &mut order, "a", 9);
+        for (round, key) in ["b1", "b2", "b3"].into_iter().enumerate() {
+            put(&mut map, &mut order, key, 2);
+            assert_eq!(evicted_key(&engine, &mut map, &mut order).unwrap(), key);
+            assert_eq!(state.gdsf.state.lock().clock, 3.0 * (round + 1) as f64);
+        }
+        put(&mut map, &mut order, "b4", 2);
+        assert_eq!(evicted_key(&engine, &mut map, &mut order).unwrap(), "a");
+        assert_eq!(state.gdsf.state.lock().clock, 10.0);
+
+        // Without a clock, a outranks the new keys forever
+        let engine = PolicyEngine::new(EvictionPolicy::GDSF, None, None, None);
+        let (mut map, mut order) = store(&[("a", 9)]);
+        for key in ["b1", "b2", "b3", "b4"] {
+            map.extend(store(&[(key, 2)]).0);
+            order.push_back(key.to_string());
+            assert_eq!(evicted_key(&engine, &mut map, &mut order).unwrap(), key);
+        }
+    }
+
+    fn sized(size: usize) -> CacheEntry<u64> {
+        let mut entry = CacheEntry::new(0);
+        entry.size = size;
+        entry
+    }
+
+    #[test]
+    fn test_insert_enforces_limits() {
+        let memory = MemoryUsage::new();
+        let engine = PolicyEngine::new(EvictionPolicy::FIFO, None, None, None)
+            .with_limits(Some(2), Some(100))
+            .with_memory_usage(Some(&memory));
+        let (mut map, mut order) = store(&[]);
+        let mut removed = PendingRemovals::new(None);
+
+        engine.insert(&mut map, &mut order, "a", sized(40), &mut removed);
+        engine.insert(&mut map, &mut order, "b", sized(40), &mut removed);
+        assert_eq!(memory.bytes(), 80);
+
+        // Over max_memory, then over limit
+        engine.insert(&mut map, &mut order, "c", sized(40), &mut removed);
+        assert_eq!(order, ["b", "c"]);
+        assert_eq!(memory.bytes(), 80);
+        engine.insert(&mut map, &mut order, "d", sized(0), &mut removed);
+        assert_eq!(order, ["c", "d"]);
+
+        // Entries larger than max_memory by themselves are not kept
+        engine.insert(&mut map, &mut order, "e", sized(200), &mut removed);
+        assert_eq!(order, ["c", "d"]);
+        assert_eq!(memory.bytes(), engine.memory_in_use(&map));
+    }
+
+    #[test]
+    fn test_purge_expired() {
+        for timers in [None, Some(TimerWheel::new())] {
+            let engine = PolicyEngine::new(EvictionPolicy::FIFO, Some(5), None, None)
+                .with_timer_wheel(timers.as_ref());
+            let (mut map, mut order) = store(&[]);
+            let mut removed = PendingRemovals::new(None);
+
+            let mut stale = CacheEntry::new(1);
+            stale.inserted_at -= Duration::from_secs(10);
+            engine.insert(&mut map, &mut order, "stale", stale, &mut removed);
+            engine.insert(
+                &mut map,
+                &mut order,
+                "fresh",
+                CacheEntry::new(2),
+                &mut removed,
+            );
+            assert!(matches!(engine.lookup(&map, "stale"), Lookup::Expired));
+            assert!(matches!(engine.lookup(&map, "fresh"), Lookup::Hit(2)));
+
+            let due = engine.due_timers();
+            assert_eq!(
+                engine.purge_expired(&mut map, &mut order, due, &mut removed),
+                1
+            );
+            assert_eq!(order, ["fresh"]);
+            assert!(matches!(engine.lookup(&map, "stale"), Lookup::Miss));
+        }
+    }
+
+    /// Evicts the largest value, and counts the hooks called
+    #[derive(Default)]
+    struct Largest {
+        inserts: std::sync::atomic::AtomicUsize,
+    }
+
+    impl EvictionStrategy<u64> for Largest {
+        fn on_insert(&self, _key: &str, _entry: &mut CacheEntry<u64>) {
+            self.inserts
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, u64>) -> Option<usize> {
+            candidates.lowest_score(|entry, _| -(entry.value as f64))
+        }
+    }
+
+    struct Confused;
+
+    impl EvictionStrategy<u64> for Confused {
+        fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, u64>) -> Option<usize> {
+            Some(candidates.len())
+        }
+    }
+
+    #[test]
+    fn test_custom_strategy_takes_precedence() {
+        let (mut map, mut order) = store(&[("a", 5), ("b", 9), ("c", 3)]);
+        let largest = Largest::default();
+        let engine = PolicyEngine::new(EvictionPolicy::FIFO, None, None, Some(&largest));
+        assert!(engine.tracks_accesses());
+        engine.on_insert("d", &mut CacheEntry::new(1));
+        assert_eq!(
+            largest.inserts.load(std::sync::atomic::Ordering::Relaxed),
+            1
+        );
+
+        let (key, entry) = engine.evict(&mut map, &mut order).unwrap();
+        assert_eq!((key.as_str(), entry.value), ("b", 9));
+
+        // Positions out of range evict the front of the queue
+        let engine = PolicyEngine::new(EvictionPolicy::LFU, None, None, Some(&Confused));
+        assert_eq!(engine.evict(&mut map, &mut order).unwrap().0, "a");
+        assert_eq!(order, ["c"]);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::invalidation::shrink_all_caches;
+use crate::sweeper::{Periodic, SweepTask};
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where the memory pressure of the process is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PressureSource {
+    /// A cgroup v2 directory: pressure is `memory.current / memory.max`
+    Cgroup(PathBuf),
+    /// A PSI file such as `/proc/pressure/memory`: pressure is the `some avg10` share
+    /// of time stalled on memory
+    Psi(PathBuf),
+}
+
+/// Reads the memory pressure of the process, and shrinks the caches above a watermark.
+///
+/// Both sources report pressure as a fraction from 0.0 to 1.0: the share of the cgroup
+/// limit in use, or the share of the last 10 seconds some task was stalled on memory.
+/// Paths are configurable, so that tests can point them at fixture files.
+///
+/// At or above the high watermark, [`relieve`](Self::relieve) evicts the configured
+/// fraction of every registered cache with [`shrink_all_caches`], each cache using its
+/// own eviction policy. Use a [`MemoryPressureMonitor`] to check periodically.
+///
+/// # Examples
+///
+/// ```no_run
+/// use cachelito_core::MemoryPressure;
+///
+/// let pressure = MemoryPressure::cgroup("/sys/fs/cgroup")
+///     .with_high_watermark(0.85)
+///     .with_shrink_fraction(0.25);
+/// if let Ok(evicted) = pressure.relieve() {
+///     println!("Evicted {} entries", evicted);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MemoryPressure {
+    source: PressureSource,
+    high_watermark: f64,
+    shrink_fraction: f64,
+}
+
+impl MemoryPressure {
+    /// Reads `memory.current` and `memory.max` from the cgroup v2 directory `dir`.
+    ///
+    /// The high watermark defaults to 0.9 (90% of `memory.max`). A cgroup without a
+    /// limit (`max`) is never under pressure.
+    pub fn cgroup(dir: impl Into<PathBuf>) -> Self {
+        Self::new(PressureSource::Cgroup(dir.into()), 0.9)
+    }
+
+    /// Reads the PSI file `path`, usually `/proc/pressure/memory` or the
+    /// `memory.pressure` file of a cgroup.
+    ///
+    /// The high watermark defaults to 0.1 (stalled 10% of the time).
+    pub fn psi(path: impl Into<PathBuf>) -> Self {
+        Self::new(PressureSource::Psi(path.into()), 0.1)
+    }
+
+    fn new(source: PressureSource, high_watermark: f64) -> Self {
+        Self {
+            source,
+            high_watermark,
+            shrink_fraction: 0.25,
+        }
+    }
+
+    /// Sets the pressure (0.0 to 1.0) at or above which the caches are shrunk.
+    pub fn with_high_watermark(mut self, high_watermark: f64) -> Self {
+        self.high_watermark = high_watermark;
+        self
+    }
+
+    /// Sets the share of the entries (0.0 to 1.0) evicted from every cache under
+    /// pressure. Defaults to 0.25.
+    pub fn with_shrink_fraction(mut self, shrink_fraction: f64) -> Self {
+        self.shrink_fraction = shrink_fraction;
+        self
+    }
+
+    /// Returns where the pressure is read from.
+    pub fn source(&self) -> &PressureSource {
+        &self.source
+    }
+
+    /// Reads the current pressure, from 0.0 to 1.0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read, or has an unexpected format.
+    pub fn read(&self) -> io::Result<f64> {
+        match &self.source {
+            PressureSource::Cgroup(dir) => {
+                let max = read_trimmed(&dir.join("memory.max"))?;
+                if max == "max" {
+                    return Ok(0.0);
+                }
+                let max: u64 = parse(&max)?;
+                let current: u64 = parse(&read_trimmed(&dir.join("memory.current"))?)?;
+                Ok(if max == 0 {
+                    1.0
+                } else {
+                    current as f64 / max as f64
+                })
+            }
+            PressureSource::Psi(path) => {
+                Ok(parse_psi_some_avg10(&fs::read_to_string(path)?)? / 100.0)
+            }
+        }
+    }
+
+    /// Returns `true` if the current pressure is at or above the high watermark.
+    ///
+    /// # Errors
+    ///
+    /// See [`read`](Self::read).
+    pub fn is_high(&self) -> io::Result<bool> {
+        Ok(self.read()? >= self.high_watermark)
+    }
+
+    /// Shrinks every registered cache if the pressure is at or above the high watermark.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries evicted, 0 below the watermark.
+    ///
+    /// # Errors
+    ///
+    /// See [`read`](Self::read).
+    pub fn relieve(&self) -> io::Result<usize> {
+        if !self.is_high()? {
+            return Ok(0);
+        }
+        Ok(shrink_all_caches(self.shrink_fraction))
+    }
+}
+
+/// Background monitor shrinking the caches while memory is under pressure.
+///
+/// Calls [`MemoryPressure::relieve`] every `interval`, either from a dedicated thread
+/// ([`start`](Self::start)) or from a task of an async runtime ([`spawn`](Self::spawn)).
+/// Read errors are ignored, so that a missing file does not stop the monitor.
+///
+/// Global and async caches are registered on the first call of their function;
+/// thread-local caches are never shrunk. Dropping the monitor (or calling
+/// [`stop`](Self::stop)) stops it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use cachelito_core::{MemoryPressure, MemoryPressureMonitor};
+/// use std::time::Duration;
+///
+/// let pressure = MemoryPressure::psi("/proc/pressure/memory");
+/// let monitor = MemoryPressureMonitor::start(pressure, Duration::from_secs(5));
+/// // ... the caches are shrunk by 25% whenever the pressure reaches 10% ...
+/// monitor.stop();
+/// ```
+pub struct MemoryPressureMonitor {
+    job: Periodic,
+}
+
+impl MemoryPressureMonitor {
+    /// Starts checking `pressure` every `interval` from a dedicated background thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread cannot be spawned.
+    pub fn start(pressure: MemoryPressure, interval: Duration) -> Self {
+        Self {
+            job: Periodic::start("cachelito-pressure", interval, move || {
+                let _ = pressure.relieve();
+            }),
+        }
+    }
+
+    /// Starts checking `pressure` every `interval` from a task of an async runtime.
+    ///
+    /// The runtime is plugged in as for [`ExpirationSweeper::spawn`](crate::ExpirationSweeper::spawn).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let monitor = MemoryPressureMonitor::spawn(
+    ///     MemoryPressure::cgroup("/sys/fs/cgroup"),
+    ///     Duration::from_secs(5),
+    ///     tokio::time::sleep,
+    ///     |task| {
+    ///         tokio::spawn(task);
+    ///     },
+    /// );
+    /// ```
+    pub fn spawn<S, Fut>(
+        pressure: MemoryPressure,
+        interval: Duration,
+        sleep: S,
+        spawner: impl FnOnce(SweepTask),
+    ) -> Self
+    where
+        S: Fn(Duration) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            job: Periodic::spawn(interval, sleep, spawner, move || {
+                let _ = pressure.relieve();
+            }),
+        }
+    }
+
+    /// Returns `true` until the monitor is stopped.
+    pub fn is_running(&self) -> bool {
+        self.job.is_running()
+    }
+
+    /// Stops the monitor, waiting for its thread to finish if it has one.
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+fn read_trimmed(path: &Path) -> io::Result<String> {
+    Ok(fs::read_to_string(path)?.trim().to_string())
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> io::Result<T> {
+    value.parse().map_err(|_| invalid_data(value))
+}
+
+fn invalid_data(value: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("unexpected memory pressure value: {:?}", value),
+    )
+}
+
+/// Extracts `avg10` from the `some` line of a PSI file, in percent.
+///
+/// ```text
+/// some avg10=0.12 avg60=0.05 avg300=0.01 total=123456
+/// full avg10=0.00 avg60=0.00 avg300=0.00 total=6543
+/// ```
+fn parse_psi_some_avg10(contents: &str) -> io::Result<f64> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("some "))
+        .and_then(|fields| {
+            fields
+                .split_whitespace()
+                .find_map(|field| field.strip_prefix("avg10="))
+        })
+        .ok_or_else(|| invalid_data(contents.trim()))
+        .and_then(parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_psi_some_avg10() {
+        let contents = "some avg10=12.50 avg60=3.00 avg300=1.00 total=1234\n\
+                        full avg10=2.00 avg60=1.00 avg300=0.50 total=567\n";
+        assert_eq!(parse_psi_some_avg10(contents).unwrap(), 12.5);
+        assert!(parse_psi_some_avg10("full avg10=2.00").is_err());
+        assert!(parse_psi_some_avg10("some avg10=lots").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{CacheEntry, EvictionStrategy, SecondaryCache};
+
+/// The reason why an entry left the cache.
+///
+/// A `RemovalCause` is passed to the removal listener configured on a cache
+/// (`on_evict = path` in the macros, or `with_removal_listener()` on the cache
+/// structs) every time an entry is removed.
+///
+/// # Variants
+///
+/// * `Capacity` - Evicted by the eviction policy because the entry `limit` was reached
+/// * `Memory` - Evicted by the eviction policy because `max_memory` was exceeded
+/// * `Expired` - The entry's TTL elapsed and it was removed on access
+/// * `Invalidated` - Removed through the invalidation registry (tags, events, dependencies, checks)
+/// * `Replaced` - Overwritten by a new value for the same key
+/// * `Explicit` - Removed manually via `remove()`, `clear()` or a [`CacheHandle`](crate::CacheHandle)
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::RemovalCause;
+///
+/// assert!(RemovalCause::Capacity.was_evicted());
+/// assert!(RemovalCause::Expired.was_evicted());
+/// assert!(!RemovalCause::Replaced.was_evicted());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RemovalCause {
+    Capacity,
+    Memory,
+    Expired,
+    Invalidated,
+    Replaced,
+    Explicit,
+}
+
+impl RemovalCause {
+    /// Returns `true` if the entry was removed automatically by the cache
+    /// (`Capacity`, `Memory` or `Expired`) rather than by the user.
+    pub fn was_evicted(&self) -> bool {
+        matches!(
+            self,
+            RemovalCause::Capacity | RemovalCause::Memory | RemovalCause::Expired
+        )
+    }
+}
+
+/// Listener invoked with `(key, value, cause)` whenever an entry leaves a cache.
+///
+/// Listeners are always called after the cache locks (or `RefCell` borrows) have
+/// been released, so they may safely perform I/O or access the cache again.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::{RemovalCause, RemovalListener};
+///
+/// fn log_removal(key: &str, value: &i32, cause: RemovalCause) {
+///     println!("{key} -> {value} removed ({cause:?})");
+/// }
+///
+/// let listener: &'static RemovalListener<i32> = &log_removal;
+/// listener("answer", &42, RemovalCause::Explicit);
+/// ```
+pub type RemovalListener<R> = dyn Fn(&str, &R, RemovalCause) + Send + Sync;
+
+/// Removed entries collected while the cache is locked, to be reported once
+/// the locks are released.
+///
+/// Nothing is collected when neither a listener nor a secondary cache is
+/// configured, so plain caches pay no extra cost. The eviction strategy is told
+/// about removals right away, while the cache is still locked.
+pub(crate) struct PendingRemovals<'l, R> {
+    listener: Option<&'l RemovalListener<R>>,
+    secondary: Option<&'l SecondaryCache<R>>,
+    strategy: Option<&'l dyn EvictionStrategy<R>>,
+    removed: Vec<(String, CacheEntry<R>, RemovalCause)>,
+}
+
+impl<'l, R> PendingRemovals<'l, R> {
+    pub(crate) fn new(listener: Option<&'l RemovalListener<R>>) -> Self {
+        Self {
+            listener,
+            secondary: None,
+            strategy: None,
+            removed: Vec::new(),
+        }
+    }
+
+    /// Sets the secondary cache that receives evicted entries.
+    pub(crate) fn with_secondary(mut self, secondary: Option<&'l SecondaryCache<R>>) -> Self {
+        self.secondary = secondary;
+        self
+    }
+
+    /// Sets the eviction strategy whose `on_remove` hook sees the removals.
+    pub(crate) fn with_strategy(mut self, strategy: Option<&'l dyn EvictionStrategy<R>>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Records a removed value if a listener or a secondary cache is configured.
+    pub(crate) fn push(&mut self, key: String, value: R, cause: RemovalCause) {
+        self.report(&key, &value, cause);
+        if self.is_collecting() {
+            self.removed.push((key, CacheEntry::new(value), cause));
+        }
+    }
+
+    /// Records a removed entry, keeping its age and frequency for the secondary cache.
+    pub(crate) fn push_entry(&mut self, key: String, entry: CacheEntry<R>, cause: RemovalCause) {
+        self.report(&key, &entry.value, cause);
+        if self.is_collecting() {
+            self.removed.push((key, entry, cause));
+        }
+    }
+
+    /// Calls the `on_remove` hook of the strategy; replaced entries get `on_insert` instead.
+    fn report(&self, key: &str, value: &R, cause: RemovalCause) {
+        if let Some(strategy) = self.strategy {
+            if cause != RemovalCause::Replaced {
+                strategy.on_remove(key, value, cause);
+            }
+        }
+    }
+
+    fn is_collecting(&self) -> bool {
+        self.listener.is_some() || self.secondary.is_some()
+    }
+
+    /// Demotes entries evicted by a limit to the secondary cache, drops the other
+    /// removed keys from it, and invokes the listener for every recorded entry.
+    ///
+    /// Must be called after all cache locks have been released.
+    pub(crate) fn notify(self) {
+        for (key, entry, cause) in &self.removed {
+            if let Some(secondary) = self.secondary {
+                match cause {
+                    RemovalCause::Capacity | RemovalCause::Memory => secondary.demote(key, entry),
+                    // The new value is in the cache, an older demoted copy is overwritten on eviction
+                    RemovalCause::Replaced => {}
+                    _ => {
+                        secondary.remove(key);
+                    }
+                }
+            }
+            if let Some(listener) = self.listener {
+                listener(key, &entry.value, *cause);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    static SEEN: Mutex<Vec<(String, i32, RemovalCause)>> = Mutex::new(Vec::new());
+
+    fn record(key: &str, value: &i32, cause: RemovalCause) {
+        SEEN.lock().unwrap().push((key.to_string(), *value, cause));
+    }
+
+    #[test]
+    fn test_was_evicted() {
+        assert!(RemovalCause::Capacity.was_evicted());
+        assert!(RemovalCause::Memory.was_evicted());
+        assert!(RemovalCause::Expired.was_evicted());
+        assert!(!RemovalCause::Invalidated.was_evicted());
+        assert!(!RemovalCause::Replaced.was_evicted());
+        assert!(!RemovalCause::Explicit.was_evicted());
+    }
+
+    #[test]
+    fn test_pending_removals_without_listener_collects_nothing() {
+        let mut pending = PendingRemovals::<i32>::new(None);
+        pending.push("a".to_string(), 1, RemovalCause::Capacity);
+        assert!(pending.removed.is_empty());
+        pending.notify();
+    }
+
+    #[test]
+    fn test_pending_removals_notifies_in_order() {
+        let mut pending = PendingRemovals::new(Some(&record));
+        pending.push("a".to_string(), 1, RemovalCause::Capacity);
+        pending.push("b".to_string(), 2, RemovalCause::Expired);
+        assert!(SEEN.lock().unwrap().is_empty());
+
+        pending.notify();
+
+        let seen = SEEN.lock().unwrap();
+        assert_eq!(
+            *seen,
+            vec![
+                ("a".to_string(), 1, RemovalCause::Capacity),
+                ("b".to_string(), 2, RemovalCause::Expired),
+            ]
+        );
+    }
+}
@@ -0,0 +1,394 @@
+//! # Two-Tier Caching
+//!
+//! A secondary (L2) tier for [`GlobalCache`](crate::GlobalCache): entries evicted from
+//! memory by `limit` or `max_memory` are serialized and demoted to a [`SecondaryStore`]
+//! instead of being lost, and an in-memory miss checks the store before the value is
+//! recomputed. A hit promotes the entry back into memory.
+//!
+//! - [`SecondaryStore`] - Byte storage of the secondary tier (files, a remote cache, ...)
+//! - [`ValueSerializer`] - Converts cached values to and from bytes
+//! - [`SecondaryCache`] - Combines both and tracks which keys were demoted
+//! - [`FileStore`] - A store keeping one file per entry in a directory
+//!
+//! # Examples
+//!
+//! ```
+//! use cachelito_core::{FileStore, SecondaryCache, ValueSerializer};
+//!
+//! struct Utf8;
+//!
+//! impl ValueSerializer<String> for Utf8 {
+//!     fn serialize(&self, value: &String) -> Option<Vec<u8>> {
+//!         Some(value.as_bytes().to_vec())
+//!     }
+//!
+//!     fn deserialize(&self, bytes: &[u8]) -> Option<String> {
+//!         String::from_utf8(bytes.to_vec()).ok()
+//!     }
+//! }
+//!
+//! let dir = std::env::temp_dir().join("cachelito-secondary-doc");
+//! let l2 = SecondaryCache::new(FileStore::new(&dir).unwrap(), Utf8);
+//! assert!(l2.is_empty());
+//! ```
+
+use crate::utils::fnv1a_64;
+use crate::CacheEntry;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "persist")]
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Byte storage backing a [`SecondaryCache`].
+///
+/// Implementations are expected to be slower than memory (disk, network), and may
+/// drop entries at any time: a failed `put` or a missing value on `get` only causes
+/// the value to be recomputed. Errors are therefore not reported.
+pub trait SecondaryStore: Send + Sync {
+    /// Returns the bytes stored under `key`, if any.
+    fn get(&self, key: &str) -> Option<Vec<u8>>;
+
+    /// Stores `value` under `key`, replacing any previous value.
+    fn put(&self, key: &str, value: &[u8]);
+
+    /// Removes the value stored under `key`, if any.
+    fn remove(&self, key: &str);
+}
+
+/// Converts cached values to and from the bytes kept by a [`SecondaryStore`].
+///
+/// Returning `None` from `serialize` keeps the value out of the secondary tier;
+/// returning `None` from `deserialize` turns the lookup into a miss.
+pub trait ValueSerializer<R>: Send + Sync {
+    /// Encodes a value.
+    fn serialize(&self, value: &R) -> Option<Vec<u8>>;
+
+    /// Decodes a value previously encoded with [`serialize`](Self::serialize).
+    fn deserialize(&self, bytes: &[u8]) -> Option<R>;
+}
+
+/// JSON serializer for values implementing `serde::Serialize` and
+/// `serde::de::DeserializeOwned` (requires the `persist` feature).
+#[cfg(feature = "persist")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonSerializer;
+
+#[cfg(feature = "persist")]
+impl<R: Serialize + DeserializeOwned> ValueSerializer<R> for JsonSerializer {
+    fn serialize(&self, value: &R) -> Option<Vec<u8>> {
+        serde_json::to_vec(value).ok()
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> Option<R> {
+        serde_json::from_slice(bytes).ok()
+    }
+}
+
+/// The secondary tier of a cache: a [`SecondaryStore`] and a [`ValueSerializer`].
+///
+/// Attach it to a [`GlobalCache`](crate::GlobalCache) with
+/// [`with_secondary_cache`](crate::GlobalCache::with_secondary_cache), or with
+/// `#[cache(l2 = PATH)]` where `PATH` names a `static` holding the `SecondaryCache`
+/// (typically in a `once_cell::sync::Lazy`).
+///
+/// The keys of demoted entries, with their age and access frequency, are kept in
+/// memory. Only those keys are looked up in the store, so a miss on a key that was
+/// never demoted costs no I/O, and values left in the store by a previous process
+/// are never served. Expired entries are dropped on lookup.
+///
+/// Store operations run under a lock, which keeps the tracked keys consistent with
+/// the store. Each cache needs its own `SecondaryCache`: keys of different functions
+/// would collide.
+pub struct SecondaryCache<R> {
+    store: Box<dyn SecondaryStore>,
+    serializer: Box<dyn ValueSerializer<R>>,
+    entries: Mutex<HashMap<String, CacheEntry<()>>>,
+}
+
+impl<R> SecondaryCache<R> {
+    /// Creates a secondary tier storing values in `store`, encoded by `serializer`.
+    pub fn new(
+        store: impl SecondaryStore + 'static,
+        serializer: impl ValueSerializer<R> + 'static,
+    ) -> Self {
+        Self {
+            store: Box::new(store),
+            serializer: Box::new(serializer),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the number of demoted entries.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Returns `true` if no entry has been demoted.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// Returns `true` if an entry was demoted under `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.lock().contains_key(key)
+    }
+
+    /// Removes the entry stored under `key`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if an entry was demoted under `key`.
+    pub fn remove(&self, key: &str) -> bool {
+        let mut entries = self.entries.lock();
+        if entries.remove(key).is_some() {
+            self.store.remove(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes the entries whose key matches `check`.
+    pub fn remove_where(&self, check: &dyn Fn(&str) -> bool) {
+        let mut entries = self.entries.lock();
+        entries.retain(|key, _| {
+            if check(key) {
+                self.store.remove(key);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) {
+        self.remove_where(&|_| true);
+    }
+
+    /// Serializes an evicted entry into the store.
+    pub(crate) fn demote(&self, key: &str, entry: &CacheEntry<R>) {
+        let mut entries = self.entries.lock();
+        match self.serializer.serialize(&entry.value) {
+            Some(bytes) => {
+                self.store.put(key, &bytes);
+                entries.insert(
+                    key.to_string(),
+                    CacheEntry {
+                        value: (),
+                        inserted_at: entry.inserted_at,
+                        frequency: entry.frequency,
+                        ttl: entry.ttl,
+                        size: 0,
+                        cost: entry.cost,
+                    },
+                );
+            }
+            // Never serve an older value for this key
+            None => {
+                if entries.remove(key).is_some() {
+                    self.store.remove(key);
+                }
+            }
+        }
+    }
+
+    /// Takes the entry stored under `key` out of the store, unless it has expired.
+    ///
+    /// The entry keeps the age, frequency and cost it had when it was demoted.
+    pub(crate) fn promote(&self, key: &str, ttl: Option<u64>) -> Option<CacheEntry<R>> {
+        let (meta, bytes) = {
+            let mut entries = self.entries.lock();
+            let meta = entries.remove(key)?;
+            let bytes = self.store.get(key);
+            self.store.remove(key);
+            (meta, bytes)
+        };
+
+        if meta.is_expired(ttl) {
+            return None;
+        }
+        let value = self.serializer.deserialize(&bytes?)?;
+        Some(CacheEntry {
+            value,
+            inserted_at: meta.inserted_at,
+            frequency: meta.frequency,
+            ttl: meta.ttl,
+            size: 0,
+            cost: meta.cost,
+        })
+    }
+}
+
+/// A [`SecondaryStore`] keeping one file per entry in a directory.
+///
+/// File names are derived from a hash of the key, and each file records its key so
+/// that hash collisions are detected. Creating a store removes the entry files left
+/// in the directory by a previous process.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::{FileStore, SecondaryStore};
+///
+/// let store = FileStore::new(std::env::temp_dir().join("cachelito-file-store-doc")).unwrap();
+/// store.put("answer", b"42");
+/// assert_eq!(store.get("answer"), Some(b"42".to_vec()));
+/// store.remove("answer");
+/// assert_eq!(store.get("answer"), None);
+/// ```
+#[derive(Debug)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+/// File extension of the entry files of a [`FileStore`].
+const FILE_STORE_EXTENSION: &str = "l2";
+
+impl FileStore {
+    /// Creates a store in `dir`, creating the directory if needed.
+    pub fn new(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|ext| ext == FILE_STORE_EXTENSION)
+            {
+                let _ = fs::remove_file(path);
+            }
+        }
+        Ok(Self { dir })
+    }
+
+    /// Returns the directory of the store.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!(
+            "{:016x}.{}",
+            fnv1a_64(key.as_bytes()),
+            FILE_STORE_EXTENSION
+        ))
+    }
+}
+
+impl SecondaryStore for FileStore {
+    fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let data = fs::read(self.path(key)).ok()?;
+        // Layout: key length (u32, little endian), key, value
+        let key_len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+        let stored_key = data.get(4..4 + key_len)?;
+        if stored_key != key.as_bytes() {
+            return None;
+        }
+        Some(data[4 + key_len..].to_vec())
+    }
+
+    fn put(&self, key: &str, value: &[u8]) {
+        let mut data = Vec::with_capacity(4 + key.len() + value.len());
+        data.extend_from_slice(&(key.len() as u32).to_le_bytes());
+        data.extend_from_slice(key.as_bytes());
+        data.extend_from_slice(value);
+        let _ = fs::write(self.path(key), data);
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Decimal;
+
+    impl ValueSerializer<u64> for Decimal {
+        fn serialize(&self, value: &u64) -> Option<Vec<u8>> {
+            (*value != 13).then(|| value.to_string().into_bytes())
+        }
+
+        fn deserialize(&self, bytes: &[u8]) -> Option<u64> {
+            std::str::from_utf8(bytes).ok()?.parse().ok()
+        }
+    }
+
+    fn temp_dir(test: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "cachelito-secondary-{}-{}",
+            test,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let store = FileStore::new(temp_dir("file_store")).unwrap();
+        assert_eq!(store.get("a"), None);
+
+        store.put("a", b"alpha");
+        store.put("b", b"");
+        assert_eq!(store.get("a"), Some(b"alpha".to_vec()));
+        assert_eq!(store.get("b"), Some(Vec::new()));
+
+        store.remove("a");
+        assert_eq!(store.get("a"), None);
+
+        // A new store starts empty
+        let store = FileStore::new(store.dir()).unwrap();
+        assert_eq!(store.get("b"), None);
+    }
+
+    #[test]
+    fn test_demote_and_promote() {
+        let l2 = SecondaryCache::new(FileStore::new(temp_dir("promote")).unwrap(), Decimal);
+        let mut entry = CacheEntry::new(42);
+        entry.frequency = 3;
+        l2.demote("k", &entry);
+        assert!(l2.contains_key("k"));
+
+        let promoted = l2.promote("k", None).unwrap();
+        assert_eq!(promoted.value, 42);
+        assert_eq!(promoted.frequency, 3);
+        assert_eq!(promoted.inserted_at, entry.inserted_at);
+        assert!(l2.is_empty());
+        assert!(l2.promote("k", None).is_none());
+    }
+
+    #[test]
+    fn test_expired_and_unserializable_entries() {
+        let l2 = SecondaryCache::new(FileStore::new(temp_dir("expired")).unwrap(), Decimal);
+        l2.demote("old", &CacheEntry::new(1).with_ttl(Some(0)));
+        assert!(l2.promote("old", None).is_none());
+
+        l2.demote("n", &CacheEntry::new(12));
+        l2.demote("n", &CacheEntry::new(13));
+        assert!(!l2.contains_key("n"));
+        assert!(l2.promote("n", None).is_none());
+    }
+
+    #[test]
+    fn test_remove_where_and_clear() {
+        let l2 = SecondaryCache::new(FileStore::new(temp_dir("remove")).unwrap(), Decimal);
+        l2.demote("user:1", &CacheEntry::new(1));
+        l2.demote("user:2", &CacheEntry::new(2));
+        l2.demote("post:1", &CacheEntry::new(3));
+
+        l2.remove_where(&|key| key.starts_with("user:"));
+        assert_eq!(l2.len(), 1);
+        assert!(l2.remove("post:1"));
+        assert!(!l2.remove("post:1"));
+
+        l2.demote("x", &CacheEntry::new(4));
+        l2.clear();
+        assert!(l2.is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+use crate::utils::fnv1a_64;
+use crate::{AccessBuffer, CacheEntry, EvictionState, MemoryUsage, TimerWheel};
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// Storage of a [`GlobalCache`](crate::GlobalCache) partitioned into independently locked shards.
+///
+/// A plain global cache keeps all its entries behind one `RwLock` and one order
+/// `Mutex`, so every write and every LRU hit serializes on them. Attached with
+/// [`with_shards`](crate::GlobalCache::with_shards), the cache instead routes each
+/// key to a shard picked by its hash, and only locks that shard.
+///
+/// Each shard enforces its share of `limit` and `max_memory` (the shares sum to the
+/// configured values), and runs the eviction policy on its own entries only. The
+/// policy is therefore approximate: the entry evicted is the least recently used
+/// (or least frequently used, ...) of its shard, not of the whole cache. Keys are not
+/// spread perfectly evenly either, so a shard may start evicting before the cache
+/// holds `limit` entries. With a `limit` lower than the number of shards, only `limit`
+/// shards are used.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::{CacheShards, EvictionPolicy, GlobalCache};
+/// # #[cfg(feature = "stats")]
+/// # use cachelito_core::CacheStats;
+/// use once_cell::sync::Lazy;
+///
+/// static SHARDS: Lazy<CacheShards<u32>> = Lazy::new(|| CacheShards::new(8));
+/// # #[cfg(feature = "stats")]
+/// # static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
+///
+/// let cache = GlobalCache::new(
+///     SHARDS.map(0),
+///     SHARDS.order(0),
+///     Some(1000),
+///     None,
+///     EvictionPolicy::LRU,
+///     None,
+///     None,
+///     # #[cfg(feature = "stats")]
+///     # &STATS,
+/// )
+/// .with_shards(&SHARDS);
+///
+/// cache.insert("answer", 42);
+/// assert_eq!(cache.get("answer"), Some(42));
+/// assert_eq!(SHARDS.len(), 1);
+/// ```
+pub struct CacheShards<R: 'static> {
+    shards: Box<[Shard<R>]>,
+}
+
+/// Entries, eviction order, buffered accesses, expiration timers, memory usage and eviction
+/// state of a single shard.
+struct Shard<R: 'static> {
+    map: RwLock<HashMap<String, CacheEntry<R>>>,
+    order: Mutex<VecDeque<String>>,
+    accesses: AccessBuffer,
+    timers: TimerWheel,
+    memory: MemoryUsage,
+    eviction_state: EvictionState,
+}
+
+impl<R: 'static> CacheShards<R> {
+    /// Creates `count` empty shards (at least one).
+    pub fn new(count: usize) -> Self {
+        let shards = (0..count.max(1))
+            .map(|_| Shard {
+                map: RwLock::new(HashMap::new()),
+                order: Mutex::new(VecDeque::new()),
+                accesses: AccessBuffer::new(),
+                timers: TimerWheel::new(),
+                memory: MemoryUsage::new(),
+                eviction_state: EvictionState::new(),
+            })
+            .collect();
+        Self { shards }
+    }
+
+    /// Returns the number of shards.
+    pub fn count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the entry map of shard `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not lower than [`count`](Self::count).
+    pub fn map(&self, index: usize) -> &RwLock<HashMap<String, CacheEntry<R>>> {
+        &self.shards[index].map
+    }
+
+    /// Returns the eviction order queue of shard `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not lower than [`count`](Self::count).
+    pub fn order(&self, index: usize) -> &Mutex<VecDeque<String>> {
+        &self.shards[index].order
+    }
+
+    /// Returns the access buffer of shard `index`, used when the cache has one attached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not lower than [`count`](Self::count).
+    pub fn access_buffer(&self, index: usize) -> &AccessBuffer {
+        &self.shards[index].accesses
+    }
+
+    /// Returns the timer wheel of shard `index`, used when the cache has one attached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not lower than [`count`](Self::count).
+    pub fn timer_wheel(&self, index: usize) -> &TimerWheel {
+        &self.shards[index].timers
+    }
+
+    /// Returns the memory counter of shard `index`, used when the cache has one attached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not lower than [`count`](Self::count).
+    pub fn memory_usage(&self, index: usize) -> &MemoryUsage {
+        &self.shards[index].memory
+    }
+
+    /// Returns the eviction state of shard `index`, used when the cache has one attached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not lower than [`count`](Self::count).
+    pub fn eviction_state(&self, index: usize) -> &EvictionState {
+        &self.shards[index].eviction_state
+    }
+
+    /// Returns the number of entries across all shards.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.map.read().len()).sum()
+    }
+
+    /// Returns `true` if no shard contains entries.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.map.read().is_empty())
+    }
+
+    /// Returns the shard of `key` when only the first `active` shards are used.
+    pub(crate) fn index_of(&self, key: &str, active: usize) -> usize {
+        (fnv1a_64(key.as_bytes()) % active as u64) as usize
+    }
+}
+
+impl<R: 'static> fmt::Debug for CacheShards<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheShards")
+            .field("count", &self.count())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Returns the share of `total` given to shard `index` out of `count`.
+///
+/// The first `total % count` shards get one more, so that the shares sum to `total`.
+pub(crate) fn shard_share(total: usize, index: usize, count: usize) -> usize {
+    total / count + usize::from(index < total % count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shares_sum_to_total() {
+        for (total, count) in [(10, 3), (7, 8), (1024, 16), (0, 4)] {
+            let shares: Vec<usize> = (0..count).map(|i| shard_share(total, i, count)).collect();
+            assert_eq!(shares.iter().sum::<usize>(), total);
+            assert!(shares.iter().max().unwrap() - shares.iter().min().unwrap() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_keys_spread_over_active_shards() {
+        let shards = CacheShards::<u32>::new(4);
+        let mut used = [false; 4];
+        for i in 0..100 {
+            used[shards.index_of(&i.to_string(), 4)] = true;
+            assert!(shards.index_of(&i.to_string(), 2) < 2);
+        }
+        assert!(used.iter().all(|used| *used));
+        assert_eq!(CacheShards::<u32>::new(0).count(), 1);
+    }
+}
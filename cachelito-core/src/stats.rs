@@ -31,6 +31,8 @@ use std::sync::atomic::{AtomicU64, Ordering};
 pub struct CacheStats {
     hits: AtomicU64,
     misses: AtomicU64,
+    uncompressed_bytes: AtomicU64,
+    compressed_bytes: AtomicU64,
 }
 
 impl CacheStats {
@@ -49,6 +51,8 @@ impl CacheStats {
         Self {
             hits: AtomicU64::new(0),
             misses: AtomicU64::new(0),
+            uncompressed_bytes: AtomicU64::new(0),
+            compressed_bytes: AtomicU64::new(0),
         }
     }
 
@@ -199,6 +203,70 @@ impl CacheStats {
         }
     }
 
+    /// Records a value stored compressed (`compress` attribute).
+    ///
+    /// # Arguments
+    ///
+    /// * `original` - Size of the value before compression, in bytes
+    /// * `compressed` - Size of the compressed value, in bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cachelito_core::CacheStats;
+    ///
+    /// let stats = CacheStats::new();
+    /// stats.record_compression(4096, 1024);
+    /// assert_eq!(stats.compression_ratio(), 4.0);
+    /// ```
+    #[inline]
+    pub fn record_compression(&self, original: usize, compressed: usize) {
+        self.uncompressed_bytes
+            .fetch_add(original as u64, Ordering::Relaxed);
+        self.compressed_bytes
+            .fetch_add(compressed as u64, Ordering::Relaxed);
+    }
+
+    /// Returns the total size, before compression, of the values stored compressed.
+    #[inline]
+    pub fn uncompressed_bytes(&self) -> u64 {
+        self.uncompressed_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total compressed size of the values stored compressed.
+    #[inline]
+    pub fn compressed_bytes(&self) -> u64 {
+        self.compressed_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the compression ratio of the values stored compressed (original size
+    /// divided by compressed size).
+    ///
+    /// Returns 1.0 if no value has been compressed. Values too small to be compressed
+    /// are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cachelito_core::CacheStats;
+    ///
+    /// let stats = CacheStats::new();
+    /// assert_eq!(stats.compression_ratio(), 1.0);
+    ///
+    /// stats.record_compression(3000, 1000);
+    /// stats.record_compression(1000, 1000);
+    /// assert_eq!(stats.compression_ratio(), 2.0);
+    /// ```
+    #[inline]
+    pub fn compression_ratio(&self) -> f64 {
+        let compressed = self.compressed_bytes();
+        if compressed == 0 {
+            1.0
+        } else {
+            self.uncompressed_bytes() as f64 / compressed as f64
+        }
+    }
+
     /// Resets all statistics counters to zero.
     ///
     /// This can be useful for measuring statistics over specific time periods
@@ -222,6 +290,8 @@ impl CacheStats {
     pub fn reset(&self) {
         self.hits.store(0, Ordering::Relaxed);
         self.misses.store(0, Ordering::Relaxed);
+        self.uncompressed_bytes.store(0, Ordering::Relaxed);
+        self.compressed_bytes.store(0, Ordering::Relaxed);
     }
 }
 
@@ -236,6 +306,8 @@ impl Clone for CacheStats {
         Self {
             hits: AtomicU64::new(self.hits()),
             misses: AtomicU64::new(self.misses()),
+            uncompressed_bytes: AtomicU64::new(self.uncompressed_bytes()),
+            compressed_bytes: AtomicU64::new(self.compressed_bytes()),
         }
     }
 }
@@ -319,6 +391,21 @@ mod tests {
         assert_eq!(stats.total_accesses(), 0);
     }
 
+    #[test]
+    fn test_compression_ratio() {
+        let stats = CacheStats::new();
+        assert_eq!(stats.compression_ratio(), 1.0);
+
+        stats.record_compression(1000, 250);
+        stats.record_compression(1000, 250);
+        assert_eq!(stats.uncompressed_bytes(), 2000);
+        assert_eq!(stats.compressed_bytes(), 500);
+        assert_eq!(stats.compression_ratio(), 4.0);
+
+        stats.reset();
+        assert_eq!(stats.compression_ratio(), 1.0);
+    }
+
     #[test]
     fn test_default() {
         let stats = CacheStats::default();
@@ -184,6 +184,7 @@ pub fn reset(name: &str) -> bool {
 }
 
 #[cfg(test)]
+#[allow(clippy::redundant_closure)]
 mod tests {
     use super::*;
 
@@ -0,0 +1,213 @@
+use crate::invalidation::purge_all_expired;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Task running an async [`ExpirationSweeper`] or [`MemoryPressureMonitor`](crate::MemoryPressureMonitor),
+/// handed to the spawner of the async runtime.
+pub type SweepTask = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Background janitor removing expired entries from every registered cache.
+///
+/// Expired entries are otherwise only removed when their key is read again, so the
+/// entries of keys that are never read again stay in memory and keep counting against
+/// `limit` and `max_memory`. The sweeper calls [`purge_all_expired`] every `interval`,
+/// either from a dedicated thread ([`start`](Self::start)) or from a task of an async
+/// runtime ([`spawn`](Self::spawn)).
+///
+/// Global and async caches are registered on the first call of their function;
+/// thread-local caches are never swept. Dropping the sweeper (or calling
+/// [`stop`](Self::stop)) stops it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use cachelito_core::ExpirationSweeper;
+/// use std::time::Duration;
+///
+/// let sweeper = ExpirationSweeper::start(Duration::from_secs(30));
+/// // ... the application runs, expired entries are purged every 30 seconds ...
+/// sweeper.stop();
+/// ```
+pub struct ExpirationSweeper {
+    job: Periodic,
+}
+
+impl ExpirationSweeper {
+    /// Starts sweeping every `interval` from a dedicated background thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread cannot be spawned.
+    pub fn start(interval: Duration) -> Self {
+        Self {
+            job: Periodic::start("cachelito-sweeper", interval, || {
+                purge_all_expired();
+            }),
+        }
+    }
+
+    /// Starts sweeping every `interval` from a task of an async runtime.
+    ///
+    /// The core crate depends on no runtime, so the runtime is plugged in through two
+    /// hooks: `sleep` waits for the given duration, and `spawner` runs the sweeping
+    /// task in the background. Once stopped, the task ends after its current sleep.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let sweeper = ExpirationSweeper::spawn(
+    ///     Duration::from_secs(30),
+    ///     tokio::time::sleep,
+    ///     |task| {
+    ///         tokio::spawn(task);
+    ///     },
+    /// );
+    /// ```
+    pub fn spawn<S, Fut>(interval: Duration, sleep: S, spawner: impl FnOnce(SweepTask)) -> Self
+    where
+        S: Fn(Duration) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            job: Periodic::spawn(interval, sleep, spawner, || {
+                purge_all_expired();
+            }),
+        }
+    }
+
+    /// Returns `true` until the sweeper is stopped.
+    pub fn is_running(&self) -> bool {
+        self.job.is_running()
+    }
+
+    /// Stops the sweeper, waiting for its thread to finish if it has one.
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+/// Job run every `interval` until dropped, shared by the background helpers of the crate.
+pub(crate) struct Periodic {
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Periodic {
+    /// Runs `job` every `interval` from a dedicated thread called `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread cannot be spawned.
+    pub(crate) fn start(name: &str, interval: Duration, job: impl Fn() + Send + 'static) -> Self {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stopped);
+        let thread = thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || loop {
+                // Dropping unparks the thread, so that it does not wait for the interval
+                let deadline = Instant::now() + interval;
+                while !flag.load(Ordering::Acquire) {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break;
+                    }
+                    thread::park_timeout(deadline - now);
+                }
+                if flag.load(Ordering::Acquire) {
+                    return;
+                }
+                job();
+            })
+            .unwrap_or_else(|_| panic!("failed to spawn the {} thread", name));
+
+        Self {
+            stopped,
+            thread: Some(thread),
+        }
+    }
+
+    /// Runs `job` every `interval` from a task handed to `spawner`, waiting with `sleep`.
+    pub(crate) fn spawn<S, Fut>(
+        interval: Duration,
+        sleep: S,
+        spawner: impl FnOnce(SweepTask),
+        job: impl Fn() + Send + 'static,
+    ) -> Self
+    where
+        S: Fn(Duration) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stopped);
+        spawner(Box::pin(async move {
+            loop {
+                sleep(interval).await;
+                if flag.load(Ordering::Acquire) {
+                    return;
+                }
+                job();
+            }
+        }));
+
+        Self {
+            stopped,
+            thread: None,
+        }
+    }
+
+    /// Returns `true` until the job is dropped.
+    pub(crate) fn is_running(&self) -> bool {
+        !self.stopped.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for Periodic {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            // A panic in the background thread has already been reported
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_does_not_wait_for_interval() {
+        let started = Instant::now();
+        let sweeper = ExpirationSweeper::start(Duration::from_secs(3600));
+        assert!(sweeper.is_running());
+        sweeper.stop();
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_spawned_task_ends_once_stopped() {
+        let mut task = None;
+        let sweeper = ExpirationSweeper::spawn(
+            Duration::from_millis(1),
+            |_| std::future::ready(()),
+            |spawned| task = Some(spawned),
+        );
+        drop(sweeper);
+
+        struct NoopWake;
+        impl std::task::Wake for NoopWake {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        // The task returns after its first sleep
+        let waker = std::task::Waker::from(Arc::new(NoopWake));
+        let mut context = std::task::Context::from_waker(&waker);
+        let poll = task.unwrap().as_mut().poll(&mut context);
+        assert!(poll.is_ready());
+    }
+}
@@ -1,17 +1,21 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug;
+use std::hash::Hash;
 use std::thread::LocalKey;
 
-use crate::{CacheEntry, EvictionPolicy};
+use crate::batch::Batch;
+use crate::policy_engine::{Lookup, PolicyEngine};
+use crate::removal::PendingRemovals;
+use crate::{
+    CacheEntry, CacheableKey, EvictionPolicy, EvictionState, EvictionStrategy, MemoryUsage,
+    RemovalCause, RemovalListener, TimerWheel,
+};
 
 #[cfg(feature = "stats")]
 use crate::CacheStats;
 
-use crate::utils::{
-    find_arc_eviction_key, find_min_frequency_key, find_tlru_eviction_key, move_key_to_end,
-    remove_key_from_cache_local,
-};
+use crate::utils::move_key_to_end;
 
 /// Core cache abstraction that stores values in a thread-local HashMap with configurable limits.
 ///
@@ -43,6 +47,7 @@ use crate::utils::{
 /// - **Result-aware**: Special handling for `Result<T, E>` types
 /// - **Memory-based limits**: Optional maximum memory usage (requires `MemoryEstimator`)
 /// - **Statistics tracking**: Optional hit/miss monitoring (requires `stats` feature)
+/// - **Removal listener**: Optional callback notified with the [`RemovalCause`] of every removal
 ///
 /// # Thread Safety
 ///
@@ -153,6 +158,16 @@ pub struct ThreadLocalCache<R: 'static> {
     /// Cache statistics (when stats feature is enabled)
     #[cfg(feature = "stats")]
     pub stats: CacheStats,
+    /// Listener notified whenever an entry leaves the cache
+    pub on_evict: Option<&'static RemovalListener<R>>,
+    /// Custom eviction strategy, taking precedence over `policy`
+    pub strategy: Option<&'static dyn EvictionStrategy<R>>,
+    /// Timer wheel tracking when entries expire, one per thread
+    pub timers: Option<&'static LocalKey<TimerWheel>>,
+    /// Running total of the entry sizes, one per thread
+    pub memory: Option<&'static LocalKey<MemoryUsage>>,
+    /// State of the built-in eviction strategies, one per thread
+    pub eviction_state: Option<&'static LocalKey<EvictionState>>,
 }
 
 impl<R: Clone + 'static> ThreadLocalCache<R> {
@@ -201,9 +216,169 @@ impl<R: Clone + 'static> ThreadLocalCache<R> {
             frequency_weight,
             #[cfg(feature = "stats")]
             stats: CacheStats::new(),
+            on_evict: None,
+            strategy: None,
+            timers: None,
+            memory: None,
+            eviction_state: None,
         }
     }
 
+    /// Sets a listener that is called whenever an entry leaves the cache.
+    ///
+    /// The listener receives the key, the removed value and the [`RemovalCause`].
+    /// It is invoked after the thread-local `RefCell`s have been released, so it
+    /// may access the cache again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::collections::{HashMap, VecDeque};
+    /// # use cachelito_core::{ThreadLocalCache, EvictionPolicy, CacheEntry, RemovalCause};
+    /// # thread_local! {
+    /// #     static CACHE: RefCell<HashMap<String, CacheEntry<i32>>> = RefCell::new(HashMap::new());
+    /// #     static ORDER: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+    /// # }
+    /// fn on_evict(key: &str, value: &i32, cause: RemovalCause) {
+    ///     assert_eq!((key, *value, cause), ("a", 1, RemovalCause::Capacity));
+    /// }
+    ///
+    /// let cache = ThreadLocalCache::new(&CACHE, &ORDER, Some(1), None, EvictionPolicy::FIFO, None, None)
+    ///     .with_removal_listener(&on_evict);
+    /// cache.insert("a", 1);
+    /// cache.insert("b", 2); // Evicts "a" and calls on_evict
+    /// ```
+    pub fn with_removal_listener(mut self, listener: &'static RemovalListener<R>) -> Self {
+        self.on_evict = Some(listener);
+        self
+    }
+
+    /// Evicts entries with a custom [`EvictionStrategy`] instead of the policy of the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::collections::{HashMap, VecDeque};
+    /// # use cachelito_core::{ThreadLocalCache, EvictionPolicy, CacheEntry};
+    /// use cachelito_core::{EvictionCandidates, EvictionStrategy};
+    /// # thread_local! {
+    /// #     static CACHE: RefCell<HashMap<String, CacheEntry<i32>>> = RefCell::new(HashMap::new());
+    /// #     static ORDER: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+    /// # }
+    /// /// Evicts the newest entry
+    /// struct Newest;
+    ///
+    /// impl EvictionStrategy<i32> for Newest {
+    ///     fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, i32>) -> Option<usize> {
+    ///         candidates.len().checked_sub(2)
+    ///     }
+    /// }
+    ///
+    /// let cache = ThreadLocalCache::new(&CACHE, &ORDER, Some(2), None, EvictionPolicy::FIFO, None, None)
+    ///     .with_eviction_strategy(&Newest);
+    /// cache.insert("a", 1);
+    /// cache.insert("b", 2);
+    /// cache.insert("c", 3); // Evicts "b", the newest entry before "c"
+    /// assert_eq!(cache.get("a"), Some(1));
+    /// assert_eq!(cache.get("b"), None);
+    /// ```
+    pub fn with_eviction_strategy(mut self, strategy: &'static dyn EvictionStrategy<R>) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Tracks the expiration of entries in `timers`, so that
+    /// [`purge_expired`](Self::purge_expired) only visits the expired ones.
+    ///
+    /// See [`GlobalCache::with_timer_wheel`](crate::GlobalCache::with_timer_wheel).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::collections::{HashMap, VecDeque};
+    /// # use cachelito_core::{ThreadLocalCache, EvictionPolicy, CacheEntry, TimerWheel};
+    /// thread_local! {
+    /// #   static CACHE: RefCell<HashMap<String, CacheEntry<i32>>> = RefCell::new(HashMap::new());
+    /// #   static ORDER: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+    ///     static TIMERS: TimerWheel = TimerWheel::new();
+    /// }
+    ///
+    /// let cache = ThreadLocalCache::new(&CACHE, &ORDER, None, None, EvictionPolicy::FIFO, Some(60), None)
+    ///     .with_timer_wheel(&TIMERS);
+    /// cache.insert("a", 1);
+    /// assert_eq!(cache.purge_expired(), 0);
+    /// ```
+    pub fn with_timer_wheel(mut self, timers: &'static LocalKey<TimerWheel>) -> Self {
+        self.timers = Some(timers);
+        self
+    }
+
+    /// Keeps the total size of the entries in `memory`, so that enforcing `max_memory`
+    /// does not sum the sizes of every entry.
+    ///
+    /// See [`GlobalCache::with_memory_usage`](crate::GlobalCache::with_memory_usage).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::collections::{HashMap, VecDeque};
+    /// # use cachelito_core::{ThreadLocalCache, EvictionPolicy, CacheEntry, MemoryUsage};
+    /// thread_local! {
+    /// #   static CACHE: RefCell<HashMap<String, CacheEntry<String>>> = RefCell::new(HashMap::new());
+    /// #   static ORDER: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+    ///     static MEMORY: MemoryUsage = MemoryUsage::new();
+    /// }
+    ///
+    /// let cache = ThreadLocalCache::new(&CACHE, &ORDER, None, Some(1024), EvictionPolicy::LRU, None, None)
+    ///     .with_memory_usage(&MEMORY);
+    /// cache.insert_with_memory("a", "value".to_string());
+    /// assert_eq!(cache.memory_usage(), "value".to_string().estimate_memory());
+    /// # use cachelito_core::MemoryEstimator;
+    /// ```
+    pub fn with_memory_usage(mut self, memory: &'static LocalKey<MemoryUsage>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Keeps the state of the built-in eviction strategies that need one, such as the
+    /// GDSF clock, in `state`.
+    ///
+    /// See [`GlobalCache::with_eviction_state`](crate::GlobalCache::with_eviction_state).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::collections::{HashMap, VecDeque};
+    /// # use cachelito_core::{ThreadLocalCache, EvictionPolicy, CacheEntry, EvictionState};
+    /// thread_local! {
+    /// #   static CACHE: RefCell<HashMap<String, CacheEntry<i32>>> = RefCell::new(HashMap::new());
+    /// #   static ORDER: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+    ///     static STATE: EvictionState = EvictionState::new();
+    /// }
+    ///
+    /// let cache = ThreadLocalCache::new(&CACHE, &ORDER, Some(2), None, EvictionPolicy::GDSF, None, None)
+    ///     .with_eviction_state(&STATE);
+    /// cache.insert("a", 1);
+    /// assert_eq!(cache.get("a"), Some(1));
+    /// ```
+    pub fn with_eviction_state(mut self, state: &'static LocalKey<EvictionState>) -> Self {
+        self.eviction_state = Some(state);
+        self
+    }
+
+    /// Returns the estimated memory of the entries of this thread's cache, in bytes.
+    ///
+    /// Only entries inserted with `insert_with_memory` are measured; caches without
+    /// `max_memory` report 0.
+    pub fn memory_usage(&self) -> usize {
+        self.with_engine(|engine, map, _, _| engine.memory_in_use(map))
+    }
+
     /// Retrieves a value from the cache by key.
     ///
     /// # Arguments
@@ -231,28 +406,28 @@ impl<R: Clone + 'static> ThreadLocalCache<R> {
     /// assert_eq!(cache.get("missing"), None);
     /// ```
     pub fn get(&self, key: &str) -> Option<R> {
-        let mut expired = false;
-
-        let val = self.cache.with(|c| {
-            let c = c.borrow();
-            if let Some(entry) = c.get(key) {
-                if entry.is_expired(self.ttl) {
-                    expired = true;
-                    return None;
+        let val = self.with_engine(
+            |engine, map, order, removed| match engine.lookup(map, key) {
+                Lookup::Hit(value) => {
+                    // Update access patterns based on policy
+                    engine.on_hit(key);
+                    if engine.promotes_on_access() {
+                        // Move key to end of order queue (most recently used)
+                        move_key_to_end(order, key);
+                    }
+                    if engine.updates_on_access() {
+                        engine.record_access(map, key);
+                    }
+                    Some(value)
                 }
-                Some(entry.value.clone())
-            } else {
-                None
-            }
-        });
-
-        // If expired, remove key from cache and return None
-        if expired {
-            self.remove_key(key);
-            #[cfg(feature = "stats")]
-            self.stats.record_miss();
-            return None;
-        }
+                // If expired, remove key from cache and return None
+                Lookup::Expired => {
+                    engine.remove(map, order, key, removed, RemovalCause::Expired);
+                    None
+                }
+                Lookup::Miss => None,
+            },
+        );
 
         // Record stats
         #[cfg(feature = "stats")]
@@ -264,55 +439,56 @@ impl<R: Clone + 'static> ThreadLocalCache<R> {
             }
         }
 
-        // Update access patterns based on policy
-        if val.is_some() {
-            match self.policy {
-                EvictionPolicy::LRU => {
-                    // Move key to end of order queue (most recently used)
-                    self.move_to_end(key);
-                }
-                EvictionPolicy::LFU => {
-                    // Increment frequency counter
-                    self.increment_frequency(key);
-                }
-                EvictionPolicy::ARC => {
-                    // Adaptive Replacement: Update both recency and frequency
-                    // Update order (recency)
-                    self.move_to_end(key);
-                    // Increment frequency counter
-                    self.increment_frequency(key);
-                }
-                EvictionPolicy::TLRU => {
-                    // Time-aware LRU: Update both recency and frequency
-                    // Similar to ARC but considers age in eviction
-                    self.move_to_end(key);
-                    self.increment_frequency(key);
-                }
-                EvictionPolicy::FIFO | EvictionPolicy::Random => {
-                    // No update needed for FIFO or Random
-                }
-            }
-        }
-
         val
     }
 
-    /// Moves a key to the end of the order queue (marks as most recently used)
-    fn move_to_end(&self, key: &str) {
-        self.order.with(|o| {
-            let mut o = o.borrow_mut();
-            move_key_to_end(&mut o, key);
-        });
+    /// Returns the engine applying the limits and eviction policy of this cache.
+    ///
+    /// The timer wheel and memory counter are thread-local: see
+    /// [`with_engine`](Self::with_engine).
+    fn engine(&self) -> PolicyEngine<'static, R> {
+        PolicyEngine::new(self.policy, self.ttl, self.frequency_weight, self.strategy)
+            .with_limits(self.limit, self.max_memory)
     }
 
-    /// Increments the frequency counter for the specified key.
-    fn increment_frequency(&self, key: &str) {
-        self.cache.with(|c| {
-            let mut c = c.borrow_mut();
-            if let Some(entry) = c.get_mut(key) {
-                entry.increment_frequency();
-            }
-        });
+    /// Calls `f` with the engine of this thread's cache, its borrowed map and order queue,
+    /// and the removals to notify once they are released.
+    fn with_engine<T>(
+        &self,
+        f: impl FnOnce(
+            PolicyEngine<'_, R>,
+            &mut HashMap<String, CacheEntry<R>>,
+            &mut VecDeque<String>,
+            &mut PendingRemovals<'_, R>,
+        ) -> T,
+    ) -> T {
+        with_local(self.timers, |timers| {
+            with_local(self.memory, |memory| {
+                with_local(self.eviction_state, |state| {
+                    let engine = self
+                        .engine()
+                        .with_timer_wheel(timers)
+                        .with_memory_usage(memory)
+                        .with_eviction_state(state);
+                    let mut removed = PendingRemovals::new(self.on_evict)
+                        .with_strategy(engine.removal_strategy());
+                    let result = self.cache.with(|c| {
+                        self.order.with(|o| {
+                            f(
+                                engine,
+                                &mut c.borrow_mut(),
+                                &mut o.borrow_mut(),
+                                &mut removed,
+                            )
+                        })
+                    });
+
+                    // Notify the removal listener once the borrows are released
+                    removed.notify();
+                    result
+                })
+            })
+        })
     }
 
     /// Inserts a value into the cache with the specified key.
@@ -346,25 +522,100 @@ impl<R: Clone + 'static> ThreadLocalCache<R> {
     /// If `max_memory` is configured, use `insert_with_memory()` instead, which requires
     /// the type to implement `MemoryEstimator`.
     pub fn insert(&self, key: &str, value: R) {
-        let key = key.to_string();
-        let entry = CacheEntry::new(value);
+        self.insert_entry(key, CacheEntry::new(value));
+    }
 
-        self.cache.with(|c| {
-            c.borrow_mut().insert(key.clone(), entry);
+    /// Inserts a value with its own time-to-live, overriding the TTL of the cache.
+    ///
+    /// Used for negative caching, where errors expire sooner than successful results.
+    /// `None` keeps the TTL of the cache.
+    pub fn insert_with_ttl(&self, key: &str, value: R, ttl: Option<u64>) {
+        self.insert_entry(key, CacheEntry::new(value).with_ttl(ttl));
+    }
+
+    /// Returns the values of many keys at once, loading the missing ones in a single call.
+    ///
+    /// Hits are served from the cache; `load` is called once with the keys that missed
+    /// (without duplicates), and only if there are any. The values it returns are inserted
+    /// with the limits, TTL and eviction policy of the cache. Use `get_many_with_memory`
+    /// when `max_memory` is configured.
+    ///
+    /// # Returns
+    ///
+    /// One value per requested key, in request order. Keys the loader returned no value
+    /// for are `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let users = cache.get_many(&[1, 2, 3], |missing| db.fetch_users(&missing));
+    /// ```
+    pub fn get_many<K, F>(&self, keys: &[K], load: F) -> Vec<Option<R>>
+    where
+        K: CacheableKey + Clone + Eq + Hash,
+        F: FnOnce(Vec<K>) -> HashMap<K, R>,
+    {
+        let mut batch = Batch::lookup(keys, |key| self.get(key));
+        let missing = batch.take_missing();
+        let loaded = if missing.is_empty() {
+            HashMap::new()
+        } else {
+            load(missing)
+        };
+        batch.fill(loaded, |key, value| self.insert(key, value))
+    }
+
+    /// Stores a prepared entry, applying memory and entry-count limits.
+    fn insert_entry(&self, key: &str, entry: CacheEntry<R>) {
+        self.with_engine(|engine, map, order, removed| {
+            engine.insert(map, order, key, entry, removed)
         });
+    }
 
-        self.order.with(|o| {
-            let mut order = o.borrow_mut();
-            if let Some(pos) = order.iter().position(|k| *k == key) {
-                order.remove(pos);
-            }
-            order.push_back(key.clone());
+    /// Removes a single entry from the cache.
+    ///
+    /// The removal listener (if any) is notified with [`RemovalCause::Explicit`].
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was present in the cache.
+    pub fn remove(&self, key: &str) -> bool {
+        self.with_engine(|engine, map, order, removed| {
+            engine.remove(map, order, key, removed, RemovalCause::Explicit)
+        })
+    }
 
-            // Only handle entry-count limits (not memory limits)
-            self.handle_entry_limit_eviction(&mut order);
+    /// Clears all entries from this thread's cache.
+    ///
+    /// The removal listener (if any) is notified with [`RemovalCause::Explicit`].
+    pub fn clear(&self) {
+        self.with_engine(|engine, map, order, removed| {
+            engine.remove_where(map, order, |_| true, removed, RemovalCause::Explicit)
         });
     }
 
+    /// Removes every expired entry of this thread's cache, returning how many were removed.
+    ///
+    /// Expired entries are otherwise only removed when their key is read again. With a
+    /// [timer wheel](Self::with_timer_wheel), only the expired entries are visited.
+    /// The removal listener (if any) is notified with [`RemovalCause::Expired`].
+    pub fn purge_expired(&self) -> usize {
+        self.with_engine(|engine, map, order, removed| {
+            let due = engine.due_timers();
+            engine.purge_expired(map, order, due, removed)
+        })
+    }
+
+    /// Returns the number of entries in this thread's cache.
+    pub fn len(&self) -> usize {
+        self.cache.with(|c| c.borrow().len())
+    }
+
+    /// Returns `true` if this thread's cache contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.cache.with(|c| c.borrow().is_empty())
+    }
+
     /// Returns a reference to the cache statistics.
     ///
     /// This method is only available when the `stats` feature is enabled.
@@ -395,125 +646,16 @@ impl<R: Clone + 'static> ThreadLocalCache<R> {
     pub fn stats(&self) -> &CacheStats {
         &self.stats
     }
+}
 
-    /// Removes a key from the cache and its associated ordering.
-    fn remove_key(&self, key: &str) {
-        self.cache.with(|c| {
-            self.order.with(|o| {
-                remove_key_from_cache_local(&mut c.borrow_mut(), &mut o.borrow_mut(), key);
-            });
-        });
-    }
-
-    /// Handles the eviction of entries from a cache to enforce the entry limit based on the specified eviction policy.
-    ///
-    /// This method ensures that the number of entries in the cache does not exceed the configured limit by removing
-    /// entries based on the specified eviction policy: LFU (Least Frequently Used), ARC (Adaptive Replacement Cache),
-    /// FIFO (First In, First Out), or LRU (Least Recently Used).
-    ///
-    /// # Parameters
-    /// - `order`: A mutable reference to a `VecDeque<String>` representing the order of keys in the cache. The order
-    ///   is used differently depending on the eviction policy, e.g., for determining the least recently or most
-    ///   recently used key.
-    ///
-    /// # Behavior
-    /// If the cache's entry limit (`self.limit`) is exceeded:
-    /// - For `EvictionPolicy::LFU`: The key with the lowest usage frequency will be identified and evicted.
-    /// - For `EvictionPolicy::ARC`: The key to be evicted is determined adaptively using an ARC strategy.
-    /// - For `EvictionPolicy::FIFO`: The earliest inserted key (front of the `order` queue) is removed.
-    /// - For `EvictionPolicy::LRU`: The least recently used key (front of the `order` queue) is removed.
-    ///
-    /// The eviction process involves:
-    /// 1. Identifying the key to evict based on the eviction policy.
-    /// 2. Removing the key from both the `order` queue and the underlying cache storage (`self.cache`).
-    /// 3. Breaking the loop upon successfully removing an entry (for FIFO/LRU).
-    ///
-    /// # Notes
-    /// - This method assumes that the order of keys in the cache is maintained in the `order` deque.
-    /// - The actual eviction is accomplished via helper functions such as `find_min_frequency_key` and `find_arc_eviction_key`.
-    /// - The removal operation ensures consistency by simultaneously updating the `order` deque and the cache storage (`self.cache`).
-    ///
-    /// # Eviction Policy Details
-    /// - **LFU** (Least Frequently Used): Evicts the cache entry that has been accessed the least number of times.
-    ///   Relies on `find_min_frequency_key`, which finds the key with the minimum usage frequency in the cache.
-    /// - **ARC** (Adaptive Replacement Cache): Uses an adaptive replacement strategy to optimize for both recency
-    ///   and frequency of access. The key to evict is determined by `find_arc_eviction_key`, which takes into account
-    ///   both recent and frequent usage patterns.
-    /// - **FIFO** (First In, First Out): Evicts the oldest entry in the cache, as determined by the front of `order`.
-    /// - **LRU** (Least Recently Used): Evicts the least recently used entry, which is also at the front of `order`.
-    /// - **Random**: Evicts a randomly selected entry from the cache.
-    ///
-    /// # Edge Cases
-    /// - If the cache has no limit (`self.limit == None`), this method performs no action.
-    /// - If the `order` deque is empty when attempting to evict an entry, no action is taken.
-    /// - For FIFO and LRU policies, evictions will continue iteratively until a valid, non-removed key is found.
-    /// - If an eviction policy is misused or improperly implemented, it might lead to incomplete or inefficient evictions.
-    fn handle_entry_limit_eviction(&self, order: &mut VecDeque<String>) {
-        if let Some(limit) = self.limit {
-            if order.len() > limit {
-                match self.policy {
-                    EvictionPolicy::LFU => {
-                        let min_freq_key = self
-                            .cache
-                            .with(|c| find_min_frequency_key(&c.borrow(), order));
-
-                        if let Some(evict_key) = min_freq_key {
-                            self.remove_key(&evict_key);
-                        }
-                    }
-                    EvictionPolicy::ARC => {
-                        let evict_key = self
-                            .cache
-                            .with(|c| find_arc_eviction_key(&c.borrow(), order.iter().enumerate()));
-
-                        if let Some(key) = evict_key {
-                            self.remove_key(&key);
-                        }
-                    }
-                    EvictionPolicy::TLRU => {
-                        let evict_key = self.cache.with(|c| {
-                            find_tlru_eviction_key(
-                                &c.borrow(),
-                                order.iter().enumerate(),
-                                self.ttl,
-                                self.frequency_weight,
-                            )
-                        });
-
-                        if let Some(key) = evict_key {
-                            self.remove_key(&key);
-                        }
-                    }
-                    EvictionPolicy::Random => {
-                        // O(1) random eviction: select random position and remove directly
-                        if !order.is_empty() {
-                            let pos = fastrand::usize(..order.len());
-                            if let Some(evict_key) = order.remove(pos) {
-                                // Remove from cache
-                                self.cache.with(|c| {
-                                    c.borrow_mut().remove(&evict_key);
-                                });
-                            }
-                        }
-                    }
-                    EvictionPolicy::FIFO | EvictionPolicy::LRU => {
-                        while let Some(evict_key) = order.pop_front() {
-                            let mut removed = false;
-                            self.cache.with(|c| {
-                                let mut cache = c.borrow_mut();
-                                if cache.contains_key(&evict_key) {
-                                    cache.remove(&evict_key);
-                                    removed = true;
-                                }
-                            });
-                            if removed {
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+/// Calls `f` with this thread's value of `key`, if any.
+fn with_local<T: 'static, U>(
+    key: Option<&'static LocalKey<T>>,
+    f: impl FnOnce(Option<&T>) -> U,
+) -> U {
+    match key {
+        Some(key) => key.with(|value| f(Some(value))),
+        None => f(None),
     }
 }
 
@@ -527,132 +669,39 @@ impl<R: Clone + 'static + crate::MemoryEstimator> ThreadLocalCache<R> {
     ///
     /// Use this method when `max_memory` is configured in the cache.
     pub fn insert_with_memory(&self, key: &str, value: R) {
-        let key = key.to_string();
-        let entry = CacheEntry::new(value);
-
-        self.cache.with(|c| {
-            c.borrow_mut().insert(key.clone(), entry);
-        });
-
-        self.order.with(|o| {
-            let mut order = o.borrow_mut();
-            if let Some(pos) = order.iter().position(|k| *k == key) {
-                order.remove(pos);
-            }
-            order.push_back(key.clone());
-
-            // Check memory limit first (if specified)
-            if let Some(max_mem) = self.max_memory {
-                // First, check if the new value by itself exceeds max_mem
-                // This is a safety check to prevent infinite eviction loop
-                let new_value_size = self.cache.with(|c| {
-                    c.borrow()
-                        .get(&key)
-                        .map(|e| e.value.estimate_memory())
-                        .unwrap_or(0)
-                });
-
-                if new_value_size > max_mem {
-                    // The value itself is too large for the cache
-                    // Remove it and return early to respect memory limit
-                    self.cache.with(|c| {
-                        c.borrow_mut().remove(&key);
-                    });
-                    order.pop_back(); // Remove from order queue as well
-                    return;
-                }
-
-                loop {
-                    let current_mem = self.cache.with(|c| {
-                        let cache = c.borrow();
-                        cache
-                            .values()
-                            .map(|e| e.value.estimate_memory())
-                            .sum::<usize>()
-                    });
+        self.insert_entry_with_memory(key, CacheEntry::new(value));
+    }
 
-                    if current_mem <= max_mem {
-                        break;
-                    }
+    /// Inserts a value with its own time-to-live and memory limit support.
+    ///
+    /// See [`ThreadLocalCache::insert_with_ttl`] and [`ThreadLocalCache::insert_with_memory`].
+    pub fn insert_with_memory_and_ttl(&self, key: &str, value: R, ttl: Option<u64>) {
+        self.insert_entry_with_memory(key, CacheEntry::new(value).with_ttl(ttl));
+    }
 
-                    // Need to evict based on policy
-                    let evicted = match self.policy {
-                        EvictionPolicy::LFU => {
-                            let min_freq_key = self
-                                .cache
-                                .with(|c| find_min_frequency_key(&c.borrow(), &order));
-                            if let Some(evict_key) = min_freq_key {
-                                self.remove_key(&evict_key);
-                                true
-                            } else {
-                                false
-                            }
-                        }
-                        EvictionPolicy::ARC => {
-                            let evict_key = self.cache.with(|c| {
-                                find_arc_eviction_key(&c.borrow(), order.iter().enumerate())
-                            });
-                            if let Some(key) = evict_key {
-                                self.remove_key(&key);
-                                true
-                            } else {
-                                false
-                            }
-                        }
-                        EvictionPolicy::TLRU => {
-                            let evict_key = self.cache.with(|c| {
-                                find_tlru_eviction_key(
-                                    &c.borrow(),
-                                    order.iter().enumerate(),
-                                    self.ttl,
-                                    self.frequency_weight,
-                                )
-                            });
-                            if let Some(key) = evict_key {
-                                self.remove_key(&key);
-                                true
-                            } else {
-                                false
-                            }
-                        }
-                        EvictionPolicy::Random => {
-                            // O(1) random eviction: select random position and remove directly
-                            if !order.is_empty() {
-                                let pos = fastrand::usize(..order.len());
-                                if let Some(evict_key) = order.remove(pos) {
-                                    // Remove from cache
-                                    self.cache.with(|c| {
-                                        c.borrow_mut().remove(&evict_key);
-                                    });
-                                    true
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            }
-                        }
-                        EvictionPolicy::FIFO | EvictionPolicy::LRU => {
-                            if let Some(evict_key) = order.pop_front() {
-                                self.cache.with(|c| {
-                                    c.borrow_mut().remove(&evict_key);
-                                });
-                                true
-                            } else {
-                                false
-                            }
-                        }
-                    };
-
-                    if !evicted {
-                        break; // Nothing left to evict
-                    }
-                }
-            }
+    /// Returns the values of many keys at once, loading the missing ones in a single call
+    /// and applying memory limits.
+    ///
+    /// See [`ThreadLocalCache::get_many`].
+    pub fn get_many_with_memory<K, F>(&self, keys: &[K], load: F) -> Vec<Option<R>>
+    where
+        K: CacheableKey + Clone + Eq + Hash,
+        F: FnOnce(Vec<K>) -> HashMap<K, R>,
+    {
+        let mut batch = Batch::lookup(keys, |key| self.get(key));
+        let missing = batch.take_missing();
+        let loaded = if missing.is_empty() {
+            HashMap::new()
+        } else {
+            load(missing)
+        };
+        batch.fill(loaded, |key, value| self.insert_with_memory(key, value))
+    }
 
-            // Handle entry-count limits
-            self.handle_entry_limit_eviction(&mut order);
-        });
+    /// Stores a prepared entry, measuring its value first.
+    fn insert_entry_with_memory(&self, key: &str, mut entry: CacheEntry<R>) {
+        entry.size = entry.value.estimate_memory();
+        self.insert_entry(key, entry);
     }
 }
 
@@ -711,6 +760,23 @@ impl<T: Clone + Debug + 'static, E: Clone + Debug + 'static> ThreadLocalCache<Re
             self.insert(key, Ok(val.clone()));
         }
     }
+
+    /// Inserts a `Result` into the cache, including `Err` values (negative caching).
+    ///
+    /// Caching failures for a short time protects the underlying resource from retry
+    /// storms while it is unavailable.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The cache key
+    /// * `value` - The `Result` to cache
+    /// * `err_ttl` - Time-to-live in seconds for `Err` values. `None` uses the TTL of the cache.
+    pub fn insert_result_caching_err(&self, key: &str, value: &Result<T, E>, err_ttl: Option<u64>) {
+        match value {
+            Ok(val) => self.insert(key, Ok(val.clone())),
+            Err(err) => self.insert_with_ttl(key, Err(err.clone()), err_ttl),
+        }
+    }
 }
 
 /// Implementation for Result types WITH MemoryEstimator support.
@@ -728,6 +794,49 @@ impl<
             self.insert_with_memory(key, Ok(val.clone()));
         }
     }
+
+    /// Inserts a `Result` into the cache, including `Err` values, with memory limit support.
+    ///
+    /// See `insert_result_caching_err()`.
+    pub fn insert_result_caching_err_with_memory(
+        &self,
+        key: &str,
+        value: &Result<T, E>,
+        err_ttl: Option<u64>,
+    ) {
+        match value {
+            Ok(val) => self.insert_with_memory(key, Ok(val.clone())),
+            Err(err) => self.insert_with_memory_and_ttl(key, Err(err.clone()), err_ttl),
+        }
+    }
+}
+
+/// Specialized implementation for caching `Option<T>` return types.
+///
+/// Used with `cache_none = false`, so that a missing value is looked up again on
+/// the next call instead of being remembered.
+impl<T: Clone + 'static> ThreadLocalCache<Option<T>> {
+    /// Inserts an `Option` into the cache, but only if it's a `Some` value.
+    ///
+    /// # Behavior
+    ///
+    /// * If `value` is `Some(v)`, stores `Some(v.clone())` in the cache
+    /// * If `value` is `None`, does nothing
+    pub fn insert_option(&self, key: &str, value: &Option<T>) {
+        if let Some(v) = value {
+            self.insert(key, Some(v.clone()));
+        }
+    }
+}
+
+/// Implementation for `Option` types WITH MemoryEstimator support.
+impl<T: Clone + 'static + crate::MemoryEstimator> ThreadLocalCache<Option<T>> {
+    /// Inserts an `Option` into the cache with memory limit support, only if it's `Some`.
+    pub fn insert_option_with_memory(&self, key: &str, value: &Option<T>) {
+        if let Some(v) = value {
+            self.insert_with_memory(key, Some(v.clone()));
+        }
+    }
 }
 
 #[cfg(test)]
@@ -736,7 +845,7 @@ mod tests {
 
     thread_local! {
         static TEST_CACHE: RefCell<HashMap<String, CacheEntry<i32>>> = RefCell::new(HashMap::new());
-        static TEST_ORDER: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+        static TEST_ORDER: RefCell<VecDeque<String>> = const { RefCell::new(VecDeque::new()) };
     }
 
     fn setup_cache(
@@ -838,7 +947,7 @@ mod tests {
     fn test_result_caching_ok() {
         thread_local! {
             static RES_CACHE: RefCell<HashMap<String, CacheEntry<Result<i32, String>>>> = RefCell::new(HashMap::new());
-            static RES_ORDER: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+            static RES_ORDER: RefCell<VecDeque<String>> = const { RefCell::new(VecDeque::new()) };
         }
 
         let cache = ThreadLocalCache::new(
@@ -859,7 +968,7 @@ mod tests {
     fn test_result_caching_err() {
         thread_local! {
             static RES_CACHE: RefCell<HashMap<String, CacheEntry<Result<i32, String>>>> = RefCell::new(HashMap::new());
-            static RES_ORDER: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+            static RES_ORDER: RefCell<VecDeque<String>> = const { RefCell::new(VecDeque::new()) };
         }
 
         let cache = ThreadLocalCache::new(
@@ -1097,4 +1206,43 @@ mod tests {
         assert_eq!(cache_low.get("k1"), Some(1));
         assert_eq!(cache_high.get("k1"), Some(1));
     }
+
+    #[test]
+    fn test_result_caching_err_and_option() {
+        thread_local! {
+            static RESULTS: RefCell<HashMap<String, CacheEntry<Result<i32, String>>>> = RefCell::new(HashMap::new());
+            static RESULT_ORDER: RefCell<VecDeque<String>> = const { RefCell::new(VecDeque::new()) };
+            static OPTIONS: RefCell<HashMap<String, CacheEntry<Option<i32>>>> = RefCell::new(HashMap::new());
+            static OPTION_ORDER: RefCell<VecDeque<String>> = const { RefCell::new(VecDeque::new()) };
+        }
+
+        let results = ThreadLocalCache::new(
+            &RESULTS,
+            &RESULT_ORDER,
+            None,
+            None,
+            EvictionPolicy::FIFO,
+            None,
+            None,
+        );
+        results.insert_result_caching_err("err", &Err("boom".to_string()), Some(0));
+        results.insert_result_caching_err("ok", &Ok(1), Some(0));
+        // The error expired immediately, the success value has no TTL
+        assert_eq!(results.get("err"), None);
+        assert_eq!(results.get("ok"), Some(Ok(1)));
+
+        let options = ThreadLocalCache::new(
+            &OPTIONS,
+            &OPTION_ORDER,
+            None,
+            None,
+            EvictionPolicy::FIFO,
+            None,
+            None,
+        );
+        options.insert_option("some", &Some(1));
+        options.insert_option("none", &None);
+        assert_eq!(options.get("some"), Some(Some(1)));
+        assert_eq!(options.len(), 1);
+    }
 }
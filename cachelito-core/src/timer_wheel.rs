@@ -0,0 +1,304 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::fmt;
+use std::time::Instant;
+
+/// Bits of a tick resolved by one level of the wheel
+const SLOT_BITS: u32 = 6;
+
+/// Number of slots per level
+const SLOTS: usize = 1 << SLOT_BITS;
+
+/// Number of levels: the wheel spans 64^6 ticks, about 2000 years of seconds
+const LEVELS: usize = 6;
+
+/// Timers tolerated beyond twice the number of entries before stale ones are dropped
+const COMPACTION_SLACK: usize = 64;
+
+/// Key of an entry and the tick at which it expires.
+pub(crate) type Timer = (String, u64);
+
+/// Hierarchical timing wheel tracking when cache entries expire.
+///
+/// Without it, [`purge_expired`](crate::GlobalCache::purge_expired) scans every entry of
+/// the cache to find the expired ones. With a wheel attached (see
+/// [`GlobalCache::with_timer_wheel`](crate::GlobalCache::with_timer_wheel),
+/// [`ThreadLocalCache::with_timer_wheel`](crate::ThreadLocalCache::with_timer_wheel) and
+/// [`AsyncGlobalCache::with_timer_wheel`](crate::AsyncGlobalCache::with_timer_wheel)),
+/// every insertion with a TTL schedules a timer at the second the entry expires, and
+/// purging only visits the timers that are due.
+///
+/// Timers are sorted into 6 levels of 64 slots: level 0 holds the timers due within the
+/// next 64 seconds, one slot per second, level 1 the timers due within 64² seconds,
+/// one slot per 64 seconds, and so on. As time advances, the timers of a higher level
+/// slot are spread over the lower levels, so each timer is moved at most 6 times.
+///
+/// Timers are not cancelled when their entry is evicted, invalidated or replaced: the
+/// cache ignores them once due, and drops them in bulk when they outnumber its entries.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::TimerWheel;
+/// use once_cell::sync::Lazy;
+///
+/// static TIMERS: Lazy<TimerWheel> = Lazy::new(TimerWheel::new);
+/// assert!(TIMERS.is_empty());
+/// ```
+pub struct TimerWheel {
+    wheel: Mutex<Wheel>,
+}
+
+/// State of a [`TimerWheel`], behind its lock.
+#[derive(Default)]
+struct Wheel {
+    /// Tick the wheel has advanced to, set by the first timer
+    current: Option<u64>,
+    /// `LEVELS` levels of `SLOTS` slots, allocated with the first timer
+    levels: Vec<Vec<Vec<Timer>>>,
+    /// Timers already due when scheduled
+    ready: Vec<Timer>,
+    /// Number of timers in `levels`
+    scheduled: usize,
+}
+
+impl TimerWheel {
+    /// Creates an empty wheel.
+    pub fn new() -> Self {
+        Self {
+            wheel: Mutex::new(Wheel::default()),
+        }
+    }
+
+    /// Returns the number of timers, including the ones left by removed entries.
+    pub fn len(&self) -> usize {
+        let wheel = self.wheel.lock();
+        wheel.scheduled + wheel.ready.len()
+    }
+
+    /// Returns `true` if no timer is scheduled.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Schedules a timer for `key` at tick `at`, `now` being the current tick.
+    ///
+    /// Timers more than 64^6 ticks ahead are not tracked.
+    pub(crate) fn schedule(&self, key: &str, at: u64, now: u64) {
+        let mut wheel = self.wheel.lock();
+        let current = *wheel.current.get_or_insert(now);
+        if at > current && level_of(at, current) >= LEVELS {
+            return;
+        }
+        wheel.place((key.to_string(), at));
+    }
+
+    /// Advances the wheel to tick `now`, returning the timers due by then.
+    pub(crate) fn advance(&self, now: u64) -> Vec<Timer> {
+        let mut wheel = self.wheel.lock();
+        let Some(mut current) = wheel.current else {
+            return Vec::new();
+        };
+
+        let mut due = Vec::new();
+        while current < now {
+            if wheel.scheduled == 0 {
+                // Nothing left to cascade: jump straight to `now`
+                current = now;
+                break;
+            }
+            current += 1;
+            wheel.current = Some(current);
+
+            // Spread the slots reached by higher levels over the lower ones, top-down
+            for level in (1..LEVELS).rev() {
+                if current & level_mask(level) == 0 {
+                    let slot = slot_of(current, level);
+                    let timers = std::mem::take(&mut wheel.levels[level][slot]);
+                    wheel.scheduled -= timers.len();
+                    for timer in timers {
+                        wheel.place(timer);
+                    }
+                }
+            }
+
+            let slot = slot_of(current, 0);
+            let timers = std::mem::take(&mut wheel.levels[0][slot]);
+            wheel.scheduled -= timers.len();
+            due.extend(timers);
+        }
+        wheel.current = Some(current.max(now));
+
+        due.append(&mut wheel.ready);
+        due
+    }
+
+    /// Returns `true` if the timers outnumber twice the `live` entries, and should be compacted.
+    pub(crate) fn needs_compaction(&self, live: usize) -> bool {
+        self.len() > 2 * live + COMPACTION_SLACK
+    }
+
+    /// Keeps only the timers for which `keep` returns `true`.
+    pub(crate) fn retain(&self, mut keep: impl FnMut(&str, u64) -> bool) {
+        let mut wheel = self.wheel.lock();
+        let mut timers = std::mem::take(&mut wheel.ready);
+        for slot in wheel.levels.iter_mut().flatten() {
+            timers.append(slot);
+        }
+        wheel.scheduled = 0;
+
+        for timer in timers {
+            if keep(&timer.0, timer.1) {
+                wheel.place(timer);
+            }
+        }
+    }
+
+    /// Drops every timer.
+    pub(crate) fn clear(&self) {
+        let mut wheel = self.wheel.lock();
+        wheel.ready.clear();
+        for slot in wheel.levels.iter_mut().flatten() {
+            slot.clear();
+        }
+        wheel.scheduled = 0;
+    }
+}
+
+impl Wheel {
+    /// Stores `timer` in the slot of the level matching its distance to the current tick.
+    fn place(&mut self, timer: Timer) {
+        let current = self.current.unwrap_or_default();
+        if timer.1 <= current {
+            self.ready.push(timer);
+            return;
+        }
+        if self.levels.is_empty() {
+            self.levels = vec![vec![Vec::new(); SLOTS]; LEVELS];
+        }
+        let level = level_of(timer.1, current);
+        self.levels[level][slot_of(timer.1, level)].push(timer);
+        self.scheduled += 1;
+    }
+}
+
+impl Default for TimerWheel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for TimerWheel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimerWheel")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Returns the level of a timer at tick `at`: the highest group of bits where it differs from `current`.
+fn level_of(at: u64, current: u64) -> usize {
+    ((63 - (at ^ current).leading_zeros()) / SLOT_BITS) as usize
+}
+
+/// Returns the slot of tick `at` within `level`.
+fn slot_of(at: u64, level: usize) -> usize {
+    ((at >> (SLOT_BITS as usize * level)) as usize) & (SLOTS - 1)
+}
+
+/// Returns the mask of the ticks below one slot of `level`.
+fn level_mask(level: usize) -> u64 {
+    (1u64 << (SLOT_BITS as usize * level)) - 1
+}
+
+/// Origin of the ticks of the caches storing [`Instant`]s.
+static ORIGIN: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// Returns the tick (in whole seconds since the origin) containing `instant`.
+///
+/// An entry expiring at `instant` may still be fresh when its tick is due, so the caches
+/// schedule its timer again until it has expired.
+pub(crate) fn tick_at(instant: Instant) -> u64 {
+    instant.saturating_duration_since(*ORIGIN).as_secs()
+}
+
+/// Returns the current tick, in whole seconds since the origin.
+pub(crate) fn now_tick() -> u64 {
+    ORIGIN.elapsed().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(mut timers: Vec<Timer>) -> Vec<String> {
+        timers.sort_by_key(|timer| timer.1);
+        timers.into_iter().map(|(key, _)| key).collect()
+    }
+
+    #[test]
+    fn test_timers_are_due_at_their_tick() {
+        let wheel = TimerWheel::new();
+        for (key, at) in [("a", 5), ("b", 70), ("c", 5000), ("d", 300_000)] {
+            wheel.schedule(key, at, 0);
+        }
+        assert_eq!(wheel.len(), 4);
+
+        assert!(wheel.advance(4).is_empty());
+        assert_eq!(keys(wheel.advance(5)), vec!["a"]);
+        assert!(wheel.advance(69).is_empty());
+        assert_eq!(keys(wheel.advance(4999)), vec!["b"]);
+        assert_eq!(keys(wheel.advance(5000)), vec!["c"]);
+        assert!(wheel.advance(299_999).is_empty());
+        assert_eq!(keys(wheel.advance(1_000_000)), vec!["d"]);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn test_every_distance_cascades_to_its_tick() {
+        let wheel = TimerWheel::new();
+        let ticks: Vec<u64> = (0..14).map(|i| 1000 + (1u64 << i) + i).collect();
+        for at in &ticks {
+            wheel.schedule(&at.to_string(), *at, 1000);
+        }
+        for at in &ticks {
+            assert!(wheel.advance(at - 1).is_empty(), "{} due early", at);
+            assert_eq!(keys(wheel.advance(*at)), vec![at.to_string()]);
+        }
+    }
+
+    #[test]
+    fn test_past_timers_are_due_immediately() {
+        let wheel = TimerWheel::new();
+        wheel.schedule("now", 10, 10);
+        wheel.schedule("past", 3, 10);
+        wheel.schedule("far", u64::MAX, 10);
+        assert_eq!(wheel.len(), 2);
+        assert_eq!(keys(wheel.advance(10)), vec!["past", "now"]);
+    }
+
+    #[test]
+    fn test_retain_and_clear() {
+        let wheel = TimerWheel::new();
+        for i in 0..100u64 {
+            wheel.schedule(&i.to_string(), 10 + i, 0);
+        }
+        assert!(wheel.needs_compaction(10));
+        wheel.retain(|key, _| key.parse::<u64>().unwrap() % 2 == 0);
+        assert_eq!(wheel.len(), 50);
+        assert!(!wheel.needs_compaction(10));
+        assert_eq!(wheel.advance(20).len(), 6);
+
+        wheel.clear();
+        assert!(wheel.is_empty());
+        assert!(wheel.advance(1000).is_empty());
+    }
+
+    #[test]
+    fn test_ticks_round_down() {
+        let instant = *ORIGIN + std::time::Duration::from_millis(1500);
+        assert_eq!(tick_at(instant), 1);
+        assert_eq!(tick_at(*ORIGIN + std::time::Duration::from_secs(3)), 3);
+        assert!(now_tick() < 60);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{CacheEntry, EvictionState, GlobalCache};
+use parking_lot::{Mutex, RwLock};
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+
+/// Storage map of one monomorphization.
+type CacheMap<R> = RwLock<HashMap<String, CacheEntry<R>>>;
+
+/// Key order of one monomorphization.
+type CacheOrder = Mutex<VecDeque<String>>;
+
+/// Type-erased view of a [`GlobalCache`], used to invalidate every monomorphization.
+trait ErasedCache: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn len(&self) -> usize;
+    fn invalidate_all(&self);
+    fn invalidate_where(&self, check: &dyn Fn(&str) -> bool);
+    fn purge_expired(&self) -> usize;
+    fn shrink(&self, fraction: f64) -> usize;
+}
+
+impl<R: Clone + Send + Sync + 'static> ErasedCache for GlobalCache<'static, R> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        GlobalCache::len(self)
+    }
+
+    fn invalidate_all(&self) {
+        GlobalCache::invalidate_all(self);
+    }
+
+    fn invalidate_where(&self, check: &dyn Fn(&str) -> bool) {
+        GlobalCache::invalidate_where(self, check);
+    }
+
+    fn purge_expired(&self) -> usize {
+        GlobalCache::purge_expired(self)
+    }
+
+    fn shrink(&self, fraction: f64) -> usize {
+        GlobalCache::shrink(self, fraction)
+    }
+}
+
+/// Registry of the caches of a generic cached function, one per monomorphization.
+///
+/// Statics cannot depend on generic parameters, so a `#[cache]` function with type
+/// parameters cannot declare its storage as `static` items. Instead, the macro declares
+/// a single `TypeCacheRegistry` and looks up the cache of the current monomorphization
+/// by the [`TypeId`] of its type parameters. Storage is allocated on first use and
+/// lives for the rest of the program.
+///
+/// # Examples
+///
+/// ```
+/// use cachelito_core::{EvictionPolicy, GlobalCache, TypeCacheRegistry};
+/// use once_cell::sync::Lazy;
+/// # #[cfg(feature = "stats")]
+/// # use cachelito_core::CacheStats;
+///
+/// static REGISTRY: Lazy<TypeCacheRegistry> = Lazy::new(TypeCacheRegistry::new);
+/// # #[cfg(feature = "stats")]
+/// # static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
+///
+/// fn cache_for<T: 'static + Clone + Send + Sync>() -> GlobalCache<'static, T> {
+///     REGISTRY.get_or_init::<(T,), T>(|map, order| {
+///         # #[cfg(feature = "stats")]
+///         # return GlobalCache::new(map, order, Some(10), None, EvictionPolicy::LRU, None, None, &STATS);
+///         # #[cfg(not(feature = "stats"))]
+///         GlobalCache::new(map, order, Some(10), None, EvictionPolicy::LRU, None, None)
+///     })
+/// }
+///
+/// cache_for::<u32>().insert("a", 1);
+/// cache_for::<String>().insert("a", "one".to_string());
+///
+/// assert_eq!(cache_for::<u32>().get("a"), Some(1));
+/// assert_eq!(cache_for::<String>().get("a"), Some("one".to_string()));
+/// assert_eq!(REGISTRY.len(), 2);
+/// ```
+pub struct TypeCacheRegistry {
+    caches: RwLock<HashMap<TypeId, &'static dyn ErasedCache>>,
+}
+
+impl TypeCacheRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            caches: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cache of the monomorphization identified by `K`, creating it on first use.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `K` - Identifies the monomorphization, usually a tuple of the function's type parameters
+    /// * `R` - The cached value type
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - Builds the cache from freshly allocated storage. Only called once per `K`.
+    ///   The cache gets an [`EvictionState`] of its own.
+    pub fn get_or_init<K: 'static, R: Clone + Send + Sync + 'static>(
+        &self,
+        init: impl FnOnce(&'static CacheMap<R>, &'static CacheOrder) -> GlobalCache<'static, R>,
+    ) -> GlobalCache<'static, R> {
+        let type_id = TypeId::of::<K>();
+
+        if let Some(cache) = self.caches.read().get(&type_id) {
+            return Self::downcast(*cache);
+        }
+
+        let mut caches = self.caches.write();
+        let cache = *caches.entry(type_id).or_insert_with(|| {
+            let map: &'static CacheMap<R> = Box::leak(Box::new(RwLock::new(HashMap::new())));
+            let order: &'static CacheOrder = Box::leak(Box::new(Mutex::new(VecDeque::new())));
+            let state: &'static EvictionState = Box::leak(Box::new(EvictionState::new()));
+            Box::leak(Box::new(init(map, order).with_eviction_state(state)))
+        });
+        Self::downcast(cache)
+    }
+
+    /// Returns the number of monomorphizations that have a cache.
+    pub fn len(&self) -> usize {
+        self.caches.read().len()
+    }
+
+    /// Returns `true` if no monomorphization has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.caches.read().is_empty()
+    }
+
+    /// Returns the total number of entries across all monomorphizations.
+    pub fn entry_count(&self) -> usize {
+        self.caches.read().values().map(|cache| cache.len()).sum()
+    }
+
+    /// Invalidates every entry of every monomorphization.
+    pub fn invalidate_all(&self) {
+        for cache in self.snapshot() {
+            cache.invalidate_all();
+        }
+    }
+
+    /// Invalidates the entries whose key matches `check`, in every monomorphization.
+    pub fn invalidate_where(&self, check: &dyn Fn(&str) -> bool) {
+        for cache in self.snapshot() {
+            cache.invalidate_where(check);
+        }
+    }
+
+    /// Removes the expired entries of every monomorphization, returning how many were removed.
+    pub fn purge_expired(&self) -> usize {
+        self.snapshot()
+            .into_iter()
+            .map(|cache| cache.purge_expired())
+            .sum()
+    }
+
+    /// Evicts `fraction` of the entries of every monomorphization, returning how many were evicted.
+    pub fn shrink(&self, fraction: f64) -> usize {
+        self.snapshot()
+            .into_iter()
+            .map(|cache| cache.shrink(fraction))
+            .sum()
+    }
+
+    /// Copies the cache references so that no registry lock is held while invalidating.
+    fn snapshot(&self) -> Vec<&'static dyn ErasedCache> {
+        self.caches.read().values().copied().collect()
+    }
+
+    fn downcast<R: Clone + 'static>(cache: &'static dyn ErasedCache) -> GlobalCache<'static, R> {
+        cache
+            .as_any()
+            .downcast_ref::<GlobalCache<'static, R>>()
+            .expect("TypeCacheRegistry: cache type does not match its monomorphization key")
+            .clone()
+    }
+}
+
+impl Default for TypeCacheRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "stats")]
+    use crate::CacheStats;
+    use crate::EvictionPolicy;
+    use once_cell::sync::Lazy;
+
+    #[cfg(feature = "stats")]
+    static STATS: Lazy<CacheStats> = Lazy::new(CacheStats::new);
+
+    fn cache_for<K: 'static, R: Clone + Send + Sync + 'static>(
+        registry: &TypeCacheRegistry,
+    ) -> GlobalCache<'static, R> {
+        registry.get_or_init::<K, R>(|map, order| {
+            #[cfg(feature = "stats")]
+            return GlobalCache::new(
+                map,
+                order,
+                None,
+                None,
+                EvictionPolicy::FIFO,
+                None,
+                None,
+                &STATS,
+            );
+            #[cfg(not(feature = "stats"))]
+            GlobalCache::new(map, order, None, None, EvictionPolicy::FIFO, None, None)
+        })
+    }
+
+    #[test]
+    fn test_one_cache_per_key_type() {
+        let registry = TypeCacheRegistry::new();
+
+        cache_for::<(u8,), String>(&registry).insert("k", "u8".to_string());
+        cache_for::<(u16,), String>(&registry).insert("k", "u16".to_string());
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(
+            cache_for::<(u8,), String>(&registry).get("k"),
+            Some("u8".to_string())
+        );
+        assert_eq!(
+            cache_for::<(u16,), String>(&registry).get("k"),
+            Some("u16".to_string())
+        );
+        assert_eq!(registry.entry_count(), 2);
+    }
+
+    #[test]
+    fn test_invalidation_reaches_every_monomorphization() {
+        let registry = TypeCacheRegistry::new();
+
+        cache_for::<(u8,), u32>(&registry).insert("a", 1);
+        cache_for::<(u8,), u32>(&registry).insert("b", 2);
+        cache_for::<(i64,), i64>(&registry).insert("a", 3);
+
+        registry.invalidate_where(&|key| key == "a");
+        assert_eq!(cache_for::<(u8,), u32>(&registry).get("a"), None);
+        assert_eq!(cache_for::<(u8,), u32>(&registry).get("b"), Some(2));
+        assert_eq!(cache_for::<(i64,), i64>(&registry).get("a"), None);
+
+        registry.invalidate_all();
+        assert_eq!(registry.entry_count(), 0);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::eviction_strategy::tlru_score;
 use crate::CacheEntry;
 use parking_lot::RwLockWriteGuard;
 use std::collections::{HashMap, VecDeque};
@@ -92,23 +93,32 @@ pub fn move_key_to_end(order: &mut VecDeque<String>, key: &str) {
 /// ```
 /// use std::collections::{HashMap, VecDeque};
 /// use cachelito_core::{CacheEntry, utils::find_min_frequency_key};
-/// use std::time::Instant;
+/// use std::time::{Duration, Instant};
 ///
 /// let mut map = HashMap::new();
 /// map.insert("key1".to_string(), CacheEntry {
 ///     value: 100,
 ///     inserted_at: Instant::now(),
 ///     frequency: 5,
+///     ttl: None,
+///     size: 0,
+///     cost: Duration::ZERO,
 /// });
 /// map.insert("key2".to_string(), CacheEntry {
 ///     value: 200,
 ///     inserted_at: Instant::now(),
 ///     frequency: 2,  // Lowest frequency
+///     ttl: None,
+///     size: 0,
+///     cost: Duration::ZERO,
 /// });
 /// map.insert("key3".to_string(), CacheEntry {
 ///     value: 300,
 ///     inserted_at: Instant::now(),
 ///     frequency: 8,
+///     ttl: None,
+///     size: 0,
+///     cost: Duration::ZERO,
 /// });
 ///
 /// let order = VecDeque::from(vec!["key1".to_string(), "key2".to_string(), "key3".to_string()]);
@@ -182,7 +192,7 @@ pub fn find_min_frequency_key<R>(
 /// use std::collections::{HashMap, VecDeque};
 /// use cachelito_core::{CacheEntry, utils::remove_key_from_global_cache};
 /// use parking_lot::RwLock;
-/// use std::time::Instant;
+/// use std::time::{Duration, Instant};
 ///
 /// let cache = RwLock::new(HashMap::new());
 /// let mut order = VecDeque::new();
@@ -194,6 +204,9 @@ pub fn find_min_frequency_key<R>(
 ///         value: 42,
 ///         inserted_at: Instant::now(),
 ///         frequency: 1,
+///         ttl: None,
+///         size: 0,
+///         cost: Duration::ZERO,
 ///     });
 ///     order.push_back("key1".to_string());
 /// }
@@ -212,7 +225,43 @@ pub fn remove_key_from_global_cache<R>(
 ) -> bool {
     let (removed_from_map, removed_from_order) = remove_from_maps(map, order, key);
 
-    removed_from_map || removed_from_order
+    removed_from_map.is_some() || removed_from_order
+}
+
+/// Removes a key from both the cache map and order queue (global cache version),
+/// returning the removed entry.
+///
+/// Behaves like [`remove_key_from_global_cache`] but hands the removed entry back to
+/// the caller, so it can be reported to a removal listener once the locks are released.
+///
+/// # Returns
+///
+/// * `Some(CacheEntry<R>)` - The entry that was removed from the map
+/// * `None` - If the key was not present in the map (the order queue is still cleaned up)
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::{HashMap, VecDeque};
+/// use cachelito_core::{CacheEntry, utils::take_key_from_global_cache};
+/// use parking_lot::RwLock;
+///
+/// let cache = RwLock::new(HashMap::new());
+/// let mut order = VecDeque::new();
+/// cache.write().insert("key1".to_string(), CacheEntry::new(42));
+/// order.push_back("key1".to_string());
+///
+/// let mut map = cache.write();
+/// let entry = take_key_from_global_cache(&mut map, &mut order, "key1");
+/// assert_eq!(entry.map(|e| e.value), Some(42));
+/// assert!(order.is_empty());
+/// ```
+pub fn take_key_from_global_cache<R>(
+    map: &mut RwLockWriteGuard<HashMap<String, CacheEntry<R>>>,
+    order: &mut VecDeque<String>,
+    key: &str,
+) -> Option<CacheEntry<R>> {
+    remove_from_maps(map, order, key).0
 }
 
 /// Removes a key from both the cache map and order queue (thread-local cache version).
@@ -249,7 +298,7 @@ pub fn remove_key_from_global_cache<R>(
 /// ```
 /// use std::collections::{HashMap, VecDeque};
 /// use cachelito_core::{CacheEntry, utils::remove_key_from_cache_local};
-/// use std::time::Instant;
+/// use std::time::{Duration, Instant};
 ///
 /// let mut map = HashMap::new();
 /// let mut order = VecDeque::new();
@@ -259,6 +308,9 @@ pub fn remove_key_from_global_cache<R>(
 ///     value: 42,
 ///     inserted_at: Instant::now(),
 ///     frequency: 1,
+///     ttl: None,
+///     size: 0,
+///     cost: Duration::ZERO,
 /// });
 /// order.push_back("key1".to_string());
 ///
@@ -275,7 +327,41 @@ pub fn remove_key_from_cache_local<R>(
 ) -> bool {
     let (removed_from_map, removed_from_order) = remove_from_maps(map, order, key);
 
-    removed_from_map || removed_from_order
+    removed_from_map.is_some() || removed_from_order
+}
+
+/// Removes a key from both the cache map and order queue (thread-local cache version),
+/// returning the removed entry.
+///
+/// Behaves like [`remove_key_from_cache_local`] but hands the removed entry back to
+/// the caller, so it can be reported to a removal listener once the borrows are released.
+///
+/// # Returns
+///
+/// * `Some(CacheEntry<R>)` - The entry that was removed from the map
+/// * `None` - If the key was not present in the map (the order queue is still cleaned up)
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::{HashMap, VecDeque};
+/// use cachelito_core::{CacheEntry, utils::take_key_from_cache_local};
+///
+/// let mut map = HashMap::new();
+/// let mut order = VecDeque::new();
+/// map.insert("key1".to_string(), CacheEntry::new(42));
+/// order.push_back("key1".to_string());
+///
+/// let entry = take_key_from_cache_local(&mut map, &mut order, "key1");
+/// assert_eq!(entry.map(|e| e.value), Some(42));
+/// assert!(map.is_empty());
+/// ```
+pub fn take_key_from_cache_local<R>(
+    map: &mut HashMap<String, CacheEntry<R>>,
+    order: &mut VecDeque<String>,
+    key: &str,
+) -> Option<CacheEntry<R>> {
+    remove_from_maps(map, order, key).0
 }
 
 /// Internal helper function to remove a key from both the cache map and order queue.
@@ -291,8 +377,8 @@ pub fn remove_key_from_cache_local<R>(
 ///
 /// # Returns
 ///
-/// A tuple `(bool, bool)` where:
-/// - First element: `true` if the key was removed from the map, `false` otherwise
+/// A tuple `(Option<CacheEntry<R>>, bool)` where:
+/// - First element: the entry removed from the map, or `None` if the key was not in the map
 /// - Second element: `true` if the key was removed from the order queue, `false` otherwise
 ///
 /// # Performance
@@ -303,8 +389,8 @@ fn remove_from_maps<R>(
     map: &mut HashMap<String, CacheEntry<R>>,
     order: &mut VecDeque<String>,
     key: &str,
-) -> (bool, bool) {
-    let removed_from_map = map.remove(key).is_some();
+) -> (Option<CacheEntry<R>>, bool) {
+    let removed_from_map = map.remove(key);
     let removed_from_order = if let Some(pos) = order.iter().position(|k| k == key) {
         order.remove(pos);
         true
@@ -338,7 +424,7 @@ fn remove_from_maps<R>(
 ///
 /// ```
 /// use std::collections::HashMap;
-/// use std::time::Instant;
+/// use std::time::{Duration, Instant};
 /// use cachelito_core::{CacheEntry, utils::find_arc_eviction_key};
 ///
 /// let mut map = HashMap::new();
@@ -346,11 +432,17 @@ fn remove_from_maps<R>(
 ///     value: 2,
 ///     inserted_at: Instant::now(),
 ///     frequency: 10, // High frequency
+///     ttl: None,
+///     size: 0,
+///     cost: Duration::ZERO,
 /// });
 /// map.insert("old_rare".to_string(), CacheEntry {
 ///     value: 1,
 ///     inserted_at: Instant::now(),
 ///     frequency: 1, // Low frequency
+///     ttl: None,
+///     size: 0,
+///     cost: Duration::ZERO,
 /// });
 ///
 /// // Order: most recent first (recent_freq), oldest last (old_rare)
@@ -435,6 +527,9 @@ where
 ///     value: 1,
 ///     inserted_at: Instant::now(),
 ///     frequency: 5,
+///     ttl: None,
+///     size: 0,
+///     cost: Duration::ZERO,
 /// };
 /// map.insert("old_key".to_string(), old_entry);
 ///
@@ -445,6 +540,9 @@ where
 ///     value: 2,
 ///     inserted_at: Instant::now(),
 ///     frequency: 3,
+///     ttl: None,
+///     size: 0,
+///     cost: Duration::ZERO,
 /// });
 ///
 /// // Order: fresh_key (recent), old_key (older)
@@ -478,31 +576,9 @@ where
 
     for (idx, evict_key) in keys_vec {
         if let Some(entry) = map.get(evict_key) {
-            let frequency = entry.frequency as f64;
             let position_weight = (total_len - idx) as f64;
-
-            // Calculate age factor based on TTL
-            let age_factor = if let Some(ttl_secs) = ttl {
-                let elapsed = entry.inserted_at.elapsed().as_secs_f64();
-                let ttl_f64 = ttl_secs as f64;
-                // Entries close to expiration get lower scores (prioritized for eviction)
-                // age_factor ranges from 1.0 (just inserted) to 0.0 (about to expire)
-                (1.0 - (elapsed / ttl_f64).min(1.0)).max(0.0)
-            } else {
-                1.0 // No TTL, age doesn't matter
-            };
-
-            // Apply frequency weight if provided
-            // frequency_weight allows balancing between frequency and other factors.
-            // We use a linear scaling to keep the relationship intuitive and monotonic.
-            let frequency_component = match frequency_weight {
-                Some(weight) => frequency * weight,
-                None => frequency,
-            };
-
-            // Score combines frequency, recency, and age
             // Lower scores = more likely to be evicted
-            let score = frequency_component * position_weight * age_factor;
+            let score = tlru_score(entry, position_weight, ttl, frequency_weight);
 
             if score < best_score {
                 best_score = score;
@@ -514,16 +590,50 @@ where
     best_evict_key
 }
 
+/// FNV-1a hash of `data`.
+///
+/// Stable across processes and Rust versions, unlike `DefaultHasher`, so it can be
+/// used for checksums and file names written to disk.
+pub(crate) fn fnv1a_64(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Number of entries to evict to shrink `len` entries by `fraction` (clamped to 0.0..=1.0).
+///
+/// Rounds up, so that shrinking a non-empty cache by any positive fraction evicts something.
+pub(crate) fn shrink_count(len: usize, fraction: f64) -> usize {
+    if fraction.is_nan() {
+        return 0;
+    }
+    ((len as f64) * fraction.clamp(0.0, 1.0)).ceil() as usize
+}
+
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::*;
-    use std::time::Instant;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_shrink_count_rounds_up() {
+        assert_eq!(shrink_count(10, 0.25), 3);
+        assert_eq!(shrink_count(1, 0.01), 1);
+        assert_eq!(shrink_count(10, 2.0), 10);
+        assert_eq!(shrink_count(10, -1.0), 0);
+        assert_eq!(shrink_count(10, f64::NAN), 0);
+        assert_eq!(shrink_count(0, 0.5), 0);
+    }
 
     fn create_cache_entry<R>(value: R, frequency: u64) -> CacheEntry<R> {
         CacheEntry {
             value,
             inserted_at: Instant::now(),
             frequency,
+            ttl: None,
+            size: 0,
+            cost: Duration::ZERO,
         }
     }
 
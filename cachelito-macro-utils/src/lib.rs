@@ -8,7 +8,9 @@ use quote::quote;
 use syn::{punctuated::Punctuated, Expr, MetaNameValue, Token};
 
 /// List of supported eviction policies
-static POLICIES: &[&str] = &["fifo", "lru", "lfu", "arc", "random", "tlru"];
+static POLICIES: &[&str] = &[
+    "fifo", "lru", "lfu", "arc", "random", "tlru", "gdsf", "sieve", "s3fifo",
+];
 
 pub fn policies_str_with_separator(separator: &str) -> String {
     POLICIES
@@ -22,6 +24,7 @@ pub fn policies_str_with_separator(separator: &str) -> String {
 pub struct AsyncCacheAttributes {
     pub limit: TokenStream2,
     pub policy: TokenStream2,
+    pub strategy: Option<syn::Path>,
     pub ttl: TokenStream2,
     pub custom_name: Option<String>,
     pub max_memory: TokenStream2,
@@ -30,7 +33,11 @@ pub struct AsyncCacheAttributes {
     pub dependencies: Vec<String>,
     pub invalidate_on: Option<syn::Path>,
     pub cache_if: Option<syn::Path>,
+    pub on_evict: Option<syn::Path>,
     pub frequency_weight: TokenStream2,
+    pub result: Option<bool>,
+    pub buffered_access: bool,
+    pub arc: bool,
 }
 
 impl Default for AsyncCacheAttributes {
@@ -38,6 +45,7 @@ impl Default for AsyncCacheAttributes {
         Self {
             limit: quote! { Option::<usize>::None },
             policy: quote! { "fifo" },
+            strategy: None,
             ttl: quote! { Option::<u64>::None },
             custom_name: None,
             max_memory: quote! { Option::<usize>::None },
@@ -46,7 +54,11 @@ impl Default for AsyncCacheAttributes {
             dependencies: Vec::new(),
             invalidate_on: None,
             cache_if: None,
+            on_evict: None,
             frequency_weight: quote! { Option::<f64>::None },
+            result: None,
+            buffered_access: false,
+            arc: false,
         }
     }
 }
@@ -55,6 +67,7 @@ impl Default for AsyncCacheAttributes {
 pub struct SyncCacheAttributes {
     pub limit: TokenStream2,
     pub policy: TokenStream2,
+    pub strategy: Option<syn::Path>,
     pub ttl: TokenStream2,
     pub scope: TokenStream2,
     pub custom_name: Option<String>,
@@ -64,7 +77,21 @@ pub struct SyncCacheAttributes {
     pub dependencies: Vec<String>,
     pub invalidate_on: Option<syn::Path>,
     pub cache_if: Option<syn::Path>,
+    pub on_evict: Option<syn::Path>,
     pub frequency_weight: TokenStream2,
+    pub handle: bool,
+    pub cache_field: Option<syn::Ident>,
+    pub cache_err: bool,
+    pub err_ttl: TokenStream2,
+    pub cache_none: bool,
+    pub result: Option<bool>,
+    pub persist: bool,
+    pub l2: Option<syn::Path>,
+    pub shards: Option<usize>,
+    pub buffered_access: bool,
+    pub compress: Option<TokenStream2>,
+    pub compress_min_size: usize,
+    pub arc: bool,
 }
 
 impl Default for SyncCacheAttributes {
@@ -72,6 +99,7 @@ impl Default for SyncCacheAttributes {
         Self {
             limit: quote! { None },
             policy: quote! { cachelito_core::EvictionPolicy::FIFO },
+            strategy: None,
             ttl: quote! { None },
             scope: quote! { cachelito_core::CacheScope::Global },
             custom_name: None,
@@ -81,7 +109,21 @@ impl Default for SyncCacheAttributes {
             dependencies: Vec::new(),
             invalidate_on: None,
             cache_if: None,
+            on_evict: None,
             frequency_weight: quote! { None },
+            handle: true,
+            cache_field: None,
+            cache_err: false,
+            err_ttl: quote! { None },
+            cache_none: true,
+            result: None,
+            persist: false,
+            l2: None,
+            shards: None,
+            buffered_access: false,
+            compress: None,
+            compress_min_size: 1024,
+            arc: false,
         }
     }
 }
@@ -120,7 +162,7 @@ pub fn parse_policy_attribute(nv: &MetaNameValue) -> Result<String, TokenStream2
         _ => {
             let policies = policies_str_with_separator("|");
             let err_msg = format!(
-                "Invalid syntax for `policy`: expected `policy = \"{}\"`",
+                "Invalid syntax for `policy`: expected `policy = \"{}\"` or `policy = path::to::Strategy`",
                 policies
             );
             Err(quote! {
@@ -130,6 +172,16 @@ pub fn parse_policy_attribute(nv: &MetaNameValue) -> Result<String, TokenStream2
     }
 }
 
+/// Parse `policy = path::to::Strategy`, returning the path of the custom eviction strategy
+///
+/// Returns `None` for the other forms of `policy`, parsed by [`parse_policy_attribute`].
+pub fn parse_strategy_attribute(nv: &MetaNameValue) -> Option<syn::Path> {
+    match &nv.value {
+        Expr::Path(expr_path) => Some(expr_path.path.clone()),
+        _ => None,
+    }
+}
+
 /// Parse the `ttl` attribute
 pub fn parse_ttl_attribute(nv: &MetaNameValue) -> TokenStream2 {
     match &nv.value {
@@ -201,52 +253,32 @@ pub fn parse_name_attribute(nv: &MetaNameValue) -> Option<String> {
     }
 }
 
+/// Parse a size in bytes such as "100MB", "1GB", "500KB" or "2048"
+fn parse_byte_size(value: &str) -> Option<usize> {
+    let value = value.to_uppercase();
+    let (num_str, unit) = if let Some(num_str) = value.strip_suffix("GB") {
+        (num_str, 1024 * 1024 * 1024)
+    } else if let Some(num_str) = value.strip_suffix("MB") {
+        (num_str, 1024 * 1024)
+    } else if let Some(num_str) = value.strip_suffix("KB") {
+        (num_str, 1024)
+    } else {
+        (value.as_str(), 1)
+    };
+    num_str.parse::<usize>().ok().map(|n| n * unit)
+}
+
 /// Parse the `max_memory` attribute
 /// Supports formats like: "100MB", "1GB", "500KB", or raw numbers
 pub fn parse_max_memory_attribute(nv: &MetaNameValue) -> TokenStream2 {
     match &nv.value {
         Expr::Lit(expr_lit) => match &expr_lit.lit {
-            syn::Lit::Str(s) => {
-                let val_str = s.value();
-                let val_str = val_str.to_uppercase();
-
-                // Parse memory size with units
-                let bytes = if val_str.ends_with("GB") {
-                    let num_str = val_str.trim_end_matches("GB");
-                    match num_str.parse::<usize>() {
-                        Ok(n) => n * 1024 * 1024 * 1024,
-                        Err(_) => {
-                            return quote! { compile_error!("Invalid number format for max_memory") }
-                        }
-                    }
-                } else if val_str.ends_with("MB") {
-                    let num_str = val_str.trim_end_matches("MB");
-                    match num_str.parse::<usize>() {
-                        Ok(n) => n * 1024 * 1024,
-                        Err(_) => {
-                            return quote! { compile_error!("Invalid number format for max_memory") }
-                        }
-                    }
-                } else if val_str.ends_with("KB") {
-                    let num_str = val_str.trim_end_matches("KB");
-                    match num_str.parse::<usize>() {
-                        Ok(n) => n * 1024,
-                        Err(_) => {
-                            return quote! { compile_error!("Invalid number format for max_memory") }
-                        }
-                    }
-                } else {
-                    // Try to parse as raw number (bytes)
-                    match val_str.parse::<usize>() {
-                        Ok(n) => n,
-                        Err(_) => {
-                            return quote! { compile_error!("Invalid format for max_memory: expected \"100MB\", \"1GB\", \"500KB\", or number") }
-                        }
-                    }
-                };
-
-                quote! { Some(#bytes) }
-            }
+            syn::Lit::Str(s) => match parse_byte_size(&s.value()) {
+                Some(bytes) => quote! { Some(#bytes) },
+                None => {
+                    quote! { compile_error!("Invalid format for max_memory: expected \"100MB\", \"1GB\", \"500KB\", or number") }
+                }
+            },
             syn::Lit::Int(lit_int) => {
                 let val = lit_int
                     .base10_parse::<usize>()
@@ -263,6 +295,81 @@ pub fn parse_max_memory_attribute(nv: &MetaNameValue) -> TokenStream2 {
     }
 }
 
+/// Parse a boolean attribute such as `handle = false` or `cache_err = true`
+pub fn parse_bool_attribute(nv: &MetaNameValue, name: &str) -> Result<bool, TokenStream2> {
+    match &nv.value {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            syn::Lit::Bool(b) => Ok(b.value),
+            _ => {
+                let err_msg = format!("Invalid literal for `{}`: expected boolean", name);
+                Err(quote! { compile_error!(#err_msg) })
+            }
+        },
+        _ => {
+            let err_msg = format!(
+                "Invalid syntax for `{}`: expected `{} = true|false`",
+                name, name
+            );
+            Err(quote! { compile_error!(#err_msg) })
+        }
+    }
+}
+
+/// Returns `true` if the last segment of the type path is `ident`
+///
+/// Matches `Result<T, E>`, `std::result::Result<T, E>`, `io::Result<T>` and
+/// `anyhow::Result<T>` alike for `ident = "Result"`. Parentheses and invisible
+/// groups (from `macro_rules!` expansions) are looked through.
+fn last_path_segment_is(ty: &syn::Type, ident: &str) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == ident),
+        syn::Type::Group(group) => last_path_segment_is(&group.elem, ident),
+        syn::Type::Paren(paren) => last_path_segment_is(&paren.elem, ident),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the return type is a `Result`, including aliases named `Result`
+pub fn is_result_type(output: &syn::ReturnType) -> bool {
+    matches!(output, syn::ReturnType::Type(_, ty) if last_path_segment_is(ty, "Result"))
+}
+
+/// Returns `true` if the return type is an `Option`
+pub fn is_option_type(output: &syn::ReturnType) -> bool {
+    matches!(output, syn::ReturnType::Type(_, ty) if last_path_segment_is(ty, "Option"))
+}
+
+/// Returns `true` if the return type is an `Arc` (`Arc<T>`, `std::sync::Arc<T>`, ...)
+pub fn is_arc_type(output: &syn::ReturnType) -> bool {
+    matches!(output, syn::ReturnType::Type(_, ty) if last_path_segment_is(ty, "Arc"))
+}
+
+/// Parse the `handle` attribute (whether to generate the `<fn>_cache()` companion)
+pub fn parse_handle_attribute(nv: &MetaNameValue) -> Result<bool, TokenStream2> {
+    parse_bool_attribute(nv, "handle")
+}
+
+/// Parse the `cache_field` attribute (the `CacheSlot` field used by `scope = "instance"`)
+pub fn parse_cache_field_attribute(nv: &MetaNameValue) -> Result<syn::Ident, TokenStream2> {
+    match &nv.value {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            syn::Lit::Str(s) => s.parse::<syn::Ident>().map_err(|_| {
+                quote! { compile_error!("Invalid value for `cache_field`: expected a field name") }
+            }),
+            _ => {
+                Err(quote! { compile_error!("Invalid literal for `cache_field`: expected string") })
+            }
+        },
+        _ => Err(
+            quote! { compile_error!("Invalid syntax for `cache_field`: expected `cache_field = \"field_name\"`") },
+        ),
+    }
+}
+
 /// Parse the `scope` attribute and return the string value
 pub fn parse_scope_attribute(nv: &MetaNameValue) -> Result<String, TokenStream2> {
     match &nv.value {
@@ -270,11 +377,11 @@ pub fn parse_scope_attribute(nv: &MetaNameValue) -> Result<String, TokenStream2>
             syn::Lit::Str(s) => {
                 let val = s.value();
                 // Validate the scope value
-                if val == "global" || val == "thread" {
+                if val == "global" || val == "thread" || val == "instance" {
                     Ok(val)
                 } else {
                     Err(
-                        quote! { compile_error!("Invalid scope: expected \"global\" or \"thread\"") },
+                        quote! { compile_error!("Invalid scope: expected \"global\", \"thread\" or \"instance\"") },
                     )
                 }
             }
@@ -409,8 +516,80 @@ pub fn parse_cache_if_attribute(nv: &MetaNameValue) -> Result<syn::Path, TokenSt
     }
 }
 
+/// Parse the `on_evict` attribute
+/// Expects a function path like `on_evict = log_removal` or `on_evict = my_module::log_removal`
+pub fn parse_on_evict_attribute(nv: &MetaNameValue) -> Result<syn::Path, TokenStream2> {
+    match &nv.value {
+        Expr::Path(expr_path) => Ok(expr_path.path.clone()),
+        _ => Err(
+            quote! { compile_error!("Invalid syntax for `on_evict`: expected `on_evict = function_name`") },
+        ),
+    }
+}
+
+/// Parse the `l2` attribute
+/// Expects the path of a static `SecondaryCache`, like `l2 = REPORTS_L2`
+pub fn parse_l2_attribute(nv: &MetaNameValue) -> Result<syn::Path, TokenStream2> {
+    match &nv.value {
+        Expr::Path(expr_path) => Ok(expr_path.path.clone()),
+        _ => Err(quote! { compile_error!("Invalid syntax for `l2`: expected `l2 = STATIC_NAME`") }),
+    }
+}
+
+/// Parse the `shards` attribute
+/// Expects a positive integer, like `shards = 16`
+pub fn parse_shards_attribute(nv: &MetaNameValue) -> Result<usize, TokenStream2> {
+    match &nv.value {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit_int),
+            ..
+        }) => match lit_int.base10_parse::<usize>() {
+            Ok(shards) if shards > 0 => Ok(shards),
+            _ => Err(quote! { compile_error!("shards must be a positive integer") }),
+        },
+        _ => Err(quote! { compile_error!("Invalid syntax for `shards`: expected `shards = N`") }),
+    }
+}
+
+/// Parse the `compress` attribute
+/// Expects the codec name, like `compress = "lz4"` or `compress = "zstd"`
+pub fn parse_compress_attribute(nv: &MetaNameValue) -> Result<TokenStream2, TokenStream2> {
+    match &nv.value {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            syn::Lit::Str(s) => match s.value().as_str() {
+                "lz4" => Ok(quote! { cachelito_core::Compression::Lz4 }),
+                "zstd" => Ok(quote! { cachelito_core::Compression::Zstd }),
+                _ => Err(
+                    quote! { compile_error!("Invalid codec for `compress`: expected \"lz4\" or \"zstd\"") },
+                ),
+            },
+            _ => Err(quote! { compile_error!("Invalid literal for `compress`: expected string") }),
+        },
+        _ => Err(
+            quote! { compile_error!("Invalid syntax for `compress`: expected `compress = \"lz4\"`") },
+        ),
+    }
+}
+
+/// Parse the `compress_min_size` attribute
+/// Supports the formats of `max_memory`: "4KB", "1MB" or raw numbers
+pub fn parse_compress_min_size_attribute(nv: &MetaNameValue) -> Result<usize, TokenStream2> {
+    let size = match &nv.value {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            syn::Lit::Str(s) => parse_byte_size(&s.value()),
+            syn::Lit::Int(lit_int) => lit_int.base10_parse::<usize>().ok(),
+            _ => None,
+        },
+        _ => None,
+    };
+    size.ok_or_else(|| {
+        quote! { compile_error!("Invalid format for compress_min_size: expected \"4KB\", \"1MB\", or number") }
+    })
+}
+
 /// Parse common attributes shared between async and sync caches
 /// Returns true if the attribute was recognized and processed
+#[allow(clippy::too_many_arguments)]
 fn parse_common_attribute(
     nv: &MetaNameValue,
     custom_name: &mut Option<String>,
@@ -420,6 +599,7 @@ fn parse_common_attribute(
     dependencies: &mut Vec<String>,
     invalidate_on: &mut Option<syn::Path>,
     cache_if: &mut Option<syn::Path>,
+    on_evict: &mut Option<syn::Path>,
     frequency_weight: &mut TokenStream2,
 ) -> Result<bool, TokenStream2> {
     if nv.path.is_ident("name") {
@@ -443,6 +623,9 @@ fn parse_common_attribute(
     } else if nv.path.is_ident("cache_if") {
         *cache_if = Some(parse_cache_if_attribute(nv)?);
         Ok(true)
+    } else if nv.path.is_ident("on_evict") {
+        *on_evict = Some(parse_on_evict_attribute(nv)?);
+        Ok(true)
     } else if nv.path.is_ident("frequency_weight") {
         *frequency_weight = parse_frequency_weight_attribute(nv);
         Ok(true)
@@ -467,12 +650,22 @@ pub fn parse_async_attributes(attr: TokenStream2) -> Result<AsyncCacheAttributes
         if nv.path.is_ident("limit") {
             attrs.limit = parse_limit_attribute(&nv);
         } else if nv.path.is_ident("policy") {
+            if let Some(strategy) = parse_strategy_attribute(&nv) {
+                attrs.strategy = Some(strategy);
+                continue;
+            }
             match parse_policy_attribute(&nv) {
                 Ok(policy_str) => attrs.policy = quote! { #policy_str },
                 Err(err) => return Err(err),
             }
         } else if nv.path.is_ident("ttl") {
             attrs.ttl = parse_ttl_attribute(&nv);
+        } else if nv.path.is_ident("result") {
+            attrs.result = Some(parse_bool_attribute(&nv, "result")?);
+        } else if nv.path.is_ident("buffered_access") {
+            attrs.buffered_access = parse_bool_attribute(&nv, "buffered_access")?;
+        } else if nv.path.is_ident("arc") {
+            attrs.arc = parse_bool_attribute(&nv, "arc")?;
         } else {
             // Try to parse as common attribute
             if !parse_common_attribute(
@@ -484,6 +677,7 @@ pub fn parse_async_attributes(attr: TokenStream2) -> Result<AsyncCacheAttributes
                 &mut attrs.dependencies,
                 &mut attrs.invalidate_on,
                 &mut attrs.cache_if,
+                &mut attrs.on_evict,
                 &mut attrs.frequency_weight,
             )? {
                 // Unknown attribute - generate compile error
@@ -493,7 +687,7 @@ pub fn parse_async_attributes(attr: TokenStream2) -> Result<AsyncCacheAttributes
                     .map(|i| i.to_string())
                     .unwrap_or_else(|| "unknown".to_string());
                 let err_msg = format!(
-                    "Unknown attribute: `{}`. Valid attributes are: limit, policy, ttl, name, max_memory, tags, events, dependencies, invalidate_on, cache_if, frequency_weight",
+                    "Unknown attribute: `{}`. Valid attributes are: limit, policy, ttl, name, max_memory, tags, events, dependencies, invalidate_on, cache_if, on_evict, frequency_weight, result, buffered_access, arc",
                     attr_name
                 );
                 return Err(quote! { compile_error!(#err_msg) });
@@ -520,6 +714,10 @@ pub fn parse_sync_attributes(attr: TokenStream2) -> Result<SyncCacheAttributes,
         if nv.path.is_ident("limit") {
             attrs.limit = parse_limit_attribute(&nv);
         } else if nv.path.is_ident("policy") {
+            if let Some(strategy) = parse_strategy_attribute(&nv) {
+                attrs.strategy = Some(strategy);
+                continue;
+            }
             match parse_policy_attribute(&nv) {
                 Ok(policy_str) => {
                     attrs.policy = if policy_str == "fifo" {
@@ -534,6 +732,12 @@ pub fn parse_sync_attributes(attr: TokenStream2) -> Result<SyncCacheAttributes,
                         quote! { cachelito_core::EvictionPolicy::Random }
                     } else if policy_str == "tlru" {
                         quote! { cachelito_core::EvictionPolicy::TLRU }
+                    } else if policy_str == "gdsf" {
+                        quote! { cachelito_core::EvictionPolicy::GDSF }
+                    } else if policy_str == "sieve" {
+                        quote! { cachelito_core::EvictionPolicy::SIEVE }
+                    } else if policy_str == "s3fifo" {
+                        quote! { cachelito_core::EvictionPolicy::S3FIFO }
                     } else {
                         let policies = policies_str_with_separator(", ");
                         let err_msg = format!("Invalid policy: expected one of {}", policies);
@@ -551,14 +755,42 @@ pub fn parse_sync_attributes(attr: TokenStream2) -> Result<SyncCacheAttributes,
                         quote! { cachelito_core::CacheScope::ThreadLocal }
                     } else if scope_str == "global" {
                         quote! { cachelito_core::CacheScope::Global }
+                    } else if scope_str == "instance" {
+                        quote! { cachelito_core::CacheScope::Instance }
                     } else {
                         return Err(
-                            quote! { compile_error!("Invalid scope: expected \"global\" or \"thread\"") },
+                            quote! { compile_error!("Invalid scope: expected \"global\", \"thread\" or \"instance\"") },
                         );
                     };
                 }
                 Err(err) => return Err(err),
             }
+        } else if nv.path.is_ident("handle") {
+            attrs.handle = parse_handle_attribute(&nv)?;
+        } else if nv.path.is_ident("cache_field") {
+            attrs.cache_field = Some(parse_cache_field_attribute(&nv)?);
+        } else if nv.path.is_ident("cache_err") {
+            attrs.cache_err = parse_bool_attribute(&nv, "cache_err")?;
+        } else if nv.path.is_ident("err_ttl") {
+            attrs.err_ttl = parse_ttl_attribute(&nv);
+        } else if nv.path.is_ident("cache_none") {
+            attrs.cache_none = parse_bool_attribute(&nv, "cache_none")?;
+        } else if nv.path.is_ident("result") {
+            attrs.result = Some(parse_bool_attribute(&nv, "result")?);
+        } else if nv.path.is_ident("persist") {
+            attrs.persist = parse_bool_attribute(&nv, "persist")?;
+        } else if nv.path.is_ident("l2") {
+            attrs.l2 = Some(parse_l2_attribute(&nv)?);
+        } else if nv.path.is_ident("shards") {
+            attrs.shards = Some(parse_shards_attribute(&nv)?);
+        } else if nv.path.is_ident("buffered_access") {
+            attrs.buffered_access = parse_bool_attribute(&nv, "buffered_access")?;
+        } else if nv.path.is_ident("compress") {
+            attrs.compress = Some(parse_compress_attribute(&nv)?);
+        } else if nv.path.is_ident("compress_min_size") {
+            attrs.compress_min_size = parse_compress_min_size_attribute(&nv)?;
+        } else if nv.path.is_ident("arc") {
+            attrs.arc = parse_bool_attribute(&nv, "arc")?;
         } else {
             // Try to parse as common attribute
             if !parse_common_attribute(
@@ -570,6 +802,7 @@ pub fn parse_sync_attributes(attr: TokenStream2) -> Result<SyncCacheAttributes,
                 &mut attrs.dependencies,
                 &mut attrs.invalidate_on,
                 &mut attrs.cache_if,
+                &mut attrs.on_evict,
                 &mut attrs.frequency_weight,
             )? {
                 // Unknown attribute - generate compile error
@@ -579,7 +812,7 @@ pub fn parse_sync_attributes(attr: TokenStream2) -> Result<SyncCacheAttributes,
                     .map(|i| i.to_string())
                     .unwrap_or_else(|| "unknown".to_string());
                 let err_msg = format!(
-                    "Unknown attribute: `{}`. Valid attributes are: limit, policy, ttl, scope, name, max_memory, tags, events, dependencies, invalidate_on, cache_if, frequency_weight",
+                    "Unknown attribute: `{}`. Valid attributes are: limit, policy, ttl, scope, name, max_memory, tags, events, dependencies, invalidate_on, cache_if, on_evict, frequency_weight, handle, cache_field, cache_err, err_ttl, cache_none, result, persist, l2, shards, buffered_access, compress, compress_min_size, arc",
                     attr_name
                 );
                 return Err(quote! { compile_error!(#err_msg) });
@@ -601,13 +834,13 @@ mod tests {
         let result = policies_str_with_separator(", ");
         assert_eq!(
             result,
-            "\"fifo\", \"lru\", \"lfu\", \"arc\", \"random\", \"tlru\""
+            "\"fifo\", \"lru\", \"lfu\", \"arc\", \"random\", \"tlru\", \"gdsf\", \"sieve\", \"s3fifo\""
         );
 
         let result = policies_str_with_separator("|");
         assert_eq!(
             result,
-            "\"fifo\"|\"lru\"|\"lfu\"|\"arc\"|\"random\"|\"tlru\""
+            "\"fifo\"|\"lru\"|\"lfu\"|\"arc\"|\"random\"|\"tlru\"|\"gdsf\"|\"sieve\"|\"s3fifo\""
         );
     }
 
@@ -669,6 +902,20 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_strategy_attribute() {
+        let attrs = parse_sync_attributes(quote! { policy = eviction::ByPriority }).unwrap();
+        let strategy = attrs.strategy.unwrap();
+        assert_eq!(quote! { #strategy }.to_string(), "eviction :: ByPriority");
+
+        let attrs = parse_async_attributes(quote! { policy = ByPriority }).unwrap();
+        assert!(attrs.strategy.unwrap().is_ident("ByPriority"));
+        assert!(parse_async_attributes(quote! { policy = "lru" })
+            .unwrap()
+            .strategy
+            .is_none());
+    }
+
     #[test]
     fn test_parse_ttl_attribute_valid() {
         let nv: MetaNameValue = parse_quote! { ttl = 60 };
@@ -694,6 +941,10 @@ mod tests {
         let result = parse_scope_attribute(&nv);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "thread");
+
+        let nv: MetaNameValue = parse_quote! { scope = "instance" };
+        let result = parse_scope_attribute(&nv);
+        assert_eq!(result.unwrap(), "instance");
     }
 
     #[test]
@@ -703,6 +954,180 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_cache_field_attribute() {
+        let attrs = parse_sync_attributes(quote! {
+            scope = "instance",
+            cache_field = "lookup_cache"
+        })
+        .unwrap();
+        assert_eq!(
+            attrs.scope.to_string(),
+            "cachelito_core :: CacheScope :: Instance"
+        );
+        assert_eq!(attrs.cache_field.unwrap().to_string(), "lookup_cache");
+
+        let nv: MetaNameValue = parse_quote! { cache_field = "not a field" };
+        assert!(parse_cache_field_attribute(&nv).is_err());
+
+        let nv: MetaNameValue = parse_quote! { cache_field = 1 };
+        assert!(parse_cache_field_attribute(&nv).is_err());
+    }
+
+    #[test]
+    fn test_parse_negative_caching_attributes() {
+        let attrs = parse_sync_attributes(quote! {}).unwrap();
+        assert!(!attrs.cache_err);
+        assert_eq!(attrs.err_ttl.to_string(), "None");
+        assert!(attrs.cache_none);
+
+        let attrs = parse_sync_attributes(quote! {
+            cache_err = true,
+            err_ttl = 5,
+            cache_none = false
+        })
+        .unwrap();
+        assert!(attrs.cache_err);
+        assert_eq!(attrs.err_ttl.to_string(), "Some (5u64)");
+        assert!(!attrs.cache_none);
+
+        let result = parse_sync_attributes(quote! { cache_err = "yes" });
+        assert!(result.is_err());
+        assert!(result.err().unwrap().to_string().contains("cache_err"));
+    }
+
+    #[test]
+    fn test_parse_persist_attribute() {
+        assert!(!parse_sync_attributes(quote! {}).unwrap().persist);
+        assert!(
+            parse_sync_attributes(quote! { persist = true })
+                .unwrap()
+                .persist
+        );
+        assert!(parse_sync_attributes(quote! { persist = 1 }).is_err());
+    }
+
+    #[test]
+    fn test_parse_l2_attribute() {
+        assert!(parse_sync_attributes(quote! {}).unwrap().l2.is_none());
+
+        let attrs = parse_sync_attributes(quote! { l2 = storage::REPORTS_L2 }).unwrap();
+        let path = attrs.l2.unwrap();
+        assert_eq!(quote!(#path).to_string(), "storage :: REPORTS_L2");
+
+        let nv: MetaNameValue = parse_quote! { l2 = "REPORTS_L2" };
+        assert!(parse_l2_attribute(&nv).is_err());
+    }
+
+    #[test]
+    fn test_parse_shards_attribute() {
+        assert!(parse_sync_attributes(quote! {}).unwrap().shards.is_none());
+
+        let attrs = parse_sync_attributes(quote! { shards = 16 }).unwrap();
+        assert_eq!(attrs.shards, Some(16));
+
+        let nv: MetaNameValue = parse_quote! { shards = 0 };
+        assert!(parse_shards_attribute(&nv).is_err());
+        let nv: MetaNameValue = parse_quote! { shards = "16" };
+        assert!(parse_shards_attribute(&nv).is_err());
+    }
+
+    #[test]
+    fn test_parse_arc_attribute() {
+        assert!(!parse_sync_attributes(quote! {}).unwrap().arc);
+        assert!(parse_sync_attributes(quote! { arc = true }).unwrap().arc);
+        assert!(parse_async_attributes(quote! { arc = true }).unwrap().arc);
+        assert!(parse_sync_attributes(quote! { arc = "yes" }).is_err());
+    }
+
+    #[test]
+    fn test_parse_compress_attributes() {
+        let attrs = parse_sync_attributes(quote! {}).unwrap();
+        assert!(attrs.compress.is_none());
+        assert_eq!(attrs.compress_min_size, 1024);
+
+        let attrs =
+            parse_sync_attributes(quote! { compress = "zstd", compress_min_size = "4KB" }).unwrap();
+        assert_eq!(
+            attrs.compress.unwrap().to_string(),
+            "cachelito_core :: Compression :: Zstd"
+        );
+        assert_eq!(attrs.compress_min_size, 4096);
+
+        let nv: MetaNameValue = parse_quote! { compress = "gzip" };
+        assert!(parse_compress_attribute(&nv).is_err());
+        let nv: MetaNameValue = parse_quote! { compress_min_size = 512 };
+        assert_eq!(parse_compress_min_size_attribute(&nv).unwrap(), 512);
+        let nv: MetaNameValue = parse_quote! { compress_min_size = "lots" };
+        assert!(parse_compress_min_size_attribute(&nv).is_err());
+    }
+
+    #[test]
+    fn test_parse_buffered_access_attribute() {
+        assert!(!parse_sync_attributes(quote! {}).unwrap().buffered_access);
+        assert!(
+            parse_sync_attributes(quote! { buffered_access = true })
+                .unwrap()
+                .buffered_access
+        );
+        assert!(
+            parse_async_attributes(quote! { buffered_access = true })
+                .unwrap()
+                .buffered_access
+        );
+        assert!(parse_sync_attributes(quote! { buffered_access = "yes" }).is_err());
+    }
+
+    #[test]
+    fn test_result_type_detection() {
+        let output: syn::ReturnType = parse_quote! { -> Result<u32, String> };
+        assert!(is_result_type(&output));
+        let output: syn::ReturnType = parse_quote! { -> std::io::Result<u32> };
+        assert!(is_result_type(&output));
+        let output: syn::ReturnType = parse_quote! { -> anyhow::Result<u32> };
+        assert!(is_result_type(&output));
+        let output: syn::ReturnType = parse_quote! { -> MyResult<u32> };
+        assert!(!is_result_type(&output));
+        let output: syn::ReturnType = parse_quote! { -> Option<u32> };
+        assert!(!is_result_type(&output));
+        assert!(is_option_type(&output));
+        let output: syn::ReturnType = parse_quote! {};
+        assert!(!is_result_type(&output));
+        let output: syn::ReturnType = parse_quote! { -> std::sync::Arc<Vec<u8>> };
+        assert!(is_arc_type(&output));
+        assert!(!is_option_type(&output));
+
+        assert_eq!(parse_sync_attributes(quote! {}).unwrap().result, None);
+        assert_eq!(
+            parse_sync_attributes(quote! { result = true })
+                .unwrap()
+                .result,
+            Some(true)
+        );
+        assert_eq!(
+            parse_async_attributes(quote! { result = false })
+                .unwrap()
+                .result,
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_parse_handle_attribute() {
+        let nv: MetaNameValue = parse_quote! { handle = false };
+        assert!(!parse_handle_attribute(&nv).unwrap());
+
+        let nv: MetaNameValue = parse_quote! { handle = "no" };
+        assert!(parse_handle_attribute(&nv).is_err());
+
+        assert!(parse_sync_attributes(quote! {}).unwrap().handle);
+        assert!(
+            !parse_sync_attributes(quote! { handle = false })
+                .unwrap()
+                .handle
+        );
+    }
+
     #[test]
     fn test_generate_key_expr_no_self_no_args() {
         let result = generate_key_expr(false, &[]);
@@ -795,6 +1220,7 @@ mod tests {
         let mut dependencies = Vec::new();
         let mut invalidate_on = None;
         let mut cache_if = None;
+        let mut on_evict = None;
         let mut frequency_weight = quote! { None };
 
         let result = parse_common_attribute(
@@ -806,11 +1232,12 @@ mod tests {
             &mut dependencies,
             &mut invalidate_on,
             &mut cache_if,
+            &mut on_evict,
             &mut frequency_weight,
         );
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
         assert_eq!(custom_name, Some("test_cache".to_string()));
     }
 
@@ -824,6 +1251,7 @@ mod tests {
         let mut dependencies = Vec::new();
         let mut invalidate_on = None;
         let mut cache_if = None;
+        let mut on_evict = None;
         let mut frequency_weight = quote! { None };
 
         let result = parse_common_attribute(
@@ -835,11 +1263,12 @@ mod tests {
             &mut dependencies,
             &mut invalidate_on,
             &mut cache_if,
+            &mut on_evict,
             &mut frequency_weight,
         );
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
         let expected = 100 * 1024 * 1024;
         assert_eq!(max_memory.to_string(), format!("Some ({}usize)", expected));
     }
@@ -854,6 +1283,7 @@ mod tests {
         let mut dependencies = Vec::new();
         let mut invalidate_on = None;
         let mut cache_if = None;
+        let mut on_evict = None;
         let mut frequency_weight = quote! { None };
 
         let result = parse_common_attribute(
@@ -865,11 +1295,12 @@ mod tests {
             &mut dependencies,
             &mut invalidate_on,
             &mut cache_if,
+            &mut on_evict,
             &mut frequency_weight,
         );
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
         assert_eq!(tags, vec!["tag1".to_string(), "tag2".to_string()]);
     }
 
@@ -883,6 +1314,7 @@ mod tests {
         let mut dependencies = Vec::new();
         let mut invalidate_on = None;
         let mut cache_if = None;
+        let mut on_evict = None;
         let mut frequency_weight = quote! { None };
 
         let result = parse_common_attribute(
@@ -894,11 +1326,12 @@ mod tests {
             &mut dependencies,
             &mut invalidate_on,
             &mut cache_if,
+            &mut on_evict,
             &mut frequency_weight,
         );
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
         assert_eq!(events, vec!["event1".to_string()]);
     }
 
@@ -912,6 +1345,7 @@ mod tests {
         let mut dependencies = Vec::new();
         let mut invalidate_on = None;
         let mut cache_if = None;
+        let mut on_evict = None;
         let mut frequency_weight = quote! { None };
 
         let result = parse_common_attribute(
@@ -923,11 +1357,12 @@ mod tests {
             &mut dependencies,
             &mut invalidate_on,
             &mut cache_if,
+            &mut on_evict,
             &mut frequency_weight,
         );
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
         assert_eq!(dependencies, vec!["dep1".to_string(), "dep2".to_string()]);
     }
 
@@ -941,6 +1376,7 @@ mod tests {
         let mut dependencies = Vec::new();
         let mut invalidate_on = None;
         let mut cache_if = None;
+        let mut on_evict = None;
         let mut frequency_weight = quote! { None };
 
         let result = parse_common_attribute(
@@ -952,11 +1388,12 @@ mod tests {
             &mut dependencies,
             &mut invalidate_on,
             &mut cache_if,
+            &mut on_evict,
             &mut frequency_weight,
         );
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), false); // Not recognized
+        assert!(!result.unwrap()); // Not recognized
     }
 
     #[test]
@@ -969,6 +1406,7 @@ mod tests {
         let mut dependencies = Vec::new();
         let mut invalidate_on = None;
         let mut cache_if = None;
+        let mut on_evict = None;
         let mut frequency_weight = quote! { None };
 
         let result = parse_common_attribute(
@@ -980,11 +1418,12 @@ mod tests {
             &mut dependencies,
             &mut invalidate_on,
             &mut cache_if,
+            &mut on_evict,
             &mut frequency_weight,
         );
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
         assert!(invalidate_on.is_some());
         assert_eq!(
             invalidate_on
@@ -998,6 +1437,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_common_attribute_on_evict() {
+        let nv: MetaNameValue = parse_quote! { on_evict = listeners::log_removal };
+        let mut custom_name = None;
+        let mut max_memory = quote! { None };
+        let mut tags = Vec::new();
+        let mut events = Vec::new();
+        let mut dependencies = Vec::new();
+        let mut invalidate_on = None;
+        let mut cache_if = None;
+        let mut on_evict = None;
+        let mut frequency_weight = quote! { None };
+
+        let result = parse_common_attribute(
+            &nv,
+            &mut custom_name,
+            &mut max_memory,
+            &mut tags,
+            &mut events,
+            &mut dependencies,
+            &mut invalidate_on,
+            &mut cache_if,
+            &mut on_evict,
+            &mut frequency_weight,
+        );
+
+        assert!(result.is_ok());
+        assert!(result.unwrap());
+        let path = on_evict.unwrap();
+        assert_eq!(quote!(#path).to_string(), "listeners :: log_removal");
+    }
+
+    #[test]
+    fn test_parse_on_evict_attribute_invalid() {
+        let nv: MetaNameValue = parse_quote! { on_evict = "log_removal" };
+        let result = parse_on_evict_attribute(&nv);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_parse_string_array_attribute() {
         let nv: MetaNameValue = parse_quote! { tags = ["tag1", "tag2", "tag3"] };
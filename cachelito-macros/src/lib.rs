@@ -1,11 +1,13 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
+use syn::visit_mut::{self, VisitMut};
 use syn::{parse_macro_input, FnArg, ItemFn, ReturnType};
 
 // Import shared utilities
 use cachelito_macro_utils::{
-    generate_key_expr_with_cacheable_key, parse_sync_attributes, SyncCacheAttributes,
+    generate_key_expr_with_cacheable_key, is_arc_type, is_option_type, is_result_type,
+    parse_sync_attributes, SyncCacheAttributes,
 };
 
 /// Parse macro attributes from the attribute token stream
@@ -21,27 +23,175 @@ fn parse_attributes(attr: TokenStream) -> SyncCacheAttributes {
     }
 }
 
-/// Generate the appropriate insert call based on memory configuration and result type
-fn generate_insert_call(has_max_memory: bool, is_result: bool) -> TokenStream2 {
+/// Shape of the return type, which decides how results are inserted into the cache
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReturnKind {
+    /// `Result<T, E>` or an alias named `Result` (`io::Result<T>`, `anyhow::Result<T>`, ...)
+    Result,
+    /// `Option<T>`
+    Option,
+    /// `result = true`: cacheability decided by `CacheableOutcome`
+    Outcome,
+    /// Any other type, cached as is
+    Plain,
+}
+
+/// Detect the return kind, honoring an explicit `result = true|false`
+fn detect_return_kind(output: &ReturnType, result: Option<bool>) -> ReturnKind {
+    match result {
+        Some(true) => ReturnKind::Outcome,
+        Some(false) => ReturnKind::Plain,
+        None if is_result_type(output) => ReturnKind::Result,
+        None if is_option_type(output) => ReturnKind::Option,
+        None => ReturnKind::Plain,
+    }
+}
+
+/// Check that the negative caching attributes match the return type
+fn validate_return_attributes(
+    sig: &syn::Signature,
+    attrs: &SyncCacheAttributes,
+    return_kind: ReturnKind,
+) -> syn::Result<()> {
+    if attrs.cache_err && !matches!(return_kind, ReturnKind::Result | ReturnKind::Outcome) {
+        return Err(syn::Error::new_spanned(
+            &sig.output,
+            "`cache_err` requires a function returning `Result` (or `result = true`)",
+        ));
+    }
+    if !attrs.cache_err && attrs.err_ttl.to_string() != "None" {
+        return Err(syn::Error::new_spanned(
+            &sig.ident,
+            "`err_ttl` requires `cache_err = true`",
+        ));
+    }
+    if !attrs.cache_none && return_kind != ReturnKind::Option {
+        return Err(syn::Error::new_spanned(
+            &sig.output,
+            "`cache_none` requires a function returning `Option`",
+        ));
+    }
+    Ok(())
+}
+
+/// Generate the appropriate insert call based on memory configuration and return type
+fn generate_insert_call(
+    has_max_memory: bool,
+    return_kind: ReturnKind,
+    attrs: &SyncCacheAttributes,
+) -> TokenStream2 {
+    let err_ttl = &attrs.err_ttl;
+
+    if attrs.compress.is_some() || attrs.arc {
+        return generate_wrapped_insert_call(has_max_memory, return_kind, attrs);
+    }
+
     if has_max_memory {
         // Use memory-aware insert methods when max_memory is configured
-        if is_result {
-            quote! { __cache.insert_result_with_memory(&__key, &__result); }
-        } else {
-            quote! { __cache.insert_with_memory(&__key, __result.clone()); }
+        match return_kind {
+            ReturnKind::Result if attrs.cache_err => {
+                quote! { __cache.insert_result_caching_err_with_memory(&__key, &__result, #err_ttl); }
+            }
+            ReturnKind::Result => quote! { __cache.insert_result_with_memory(&__key, &__result); },
+            ReturnKind::Option if !attrs.cache_none => {
+                quote! { __cache.insert_option_with_memory(&__key, &__result); }
+            }
+            ReturnKind::Outcome if attrs.cache_err => quote! {
+                let __ttl = if ::cachelito_core::CacheableOutcome::is_cacheable(&__result) { None } else { #err_ttl };
+                __cache.insert_with_memory_and_ttl(&__key, __result.clone(), __ttl);
+            },
+            ReturnKind::Outcome => quote! {
+                if ::cachelito_core::CacheableOutcome::is_cacheable(&__result) {
+                    __cache.insert_with_memory(&__key, __result.clone());
+                }
+            },
+            _ => quote! { __cache.insert_with_memory(&__key, __result.clone()); },
         }
     } else {
         // Use regular insert methods when max_memory is None
-        if is_result {
-            quote! { __cache.insert_result(&__key, &__result); }
-        } else {
-            quote! { __cache.insert(&__key, __result.clone()); }
+        match return_kind {
+            ReturnKind::Result if attrs.cache_err => {
+                quote! { __cache.insert_result_caching_err(&__key, &__result, #err_ttl); }
+            }
+            ReturnKind::Result => quote! { __cache.insert_result(&__key, &__result); },
+            ReturnKind::Option if !attrs.cache_none => {
+                quote! { __cache.insert_option(&__key, &__result); }
+            }
+            ReturnKind::Outcome if attrs.cache_err => quote! {
+                let __ttl = if ::cachelito_core::CacheableOutcome::is_cacheable(&__result) { None } else { #err_ttl };
+                __cache.insert_with_ttl(&__key, __result.clone(), __ttl);
+            },
+            ReturnKind::Outcome => quote! {
+                if ::cachelito_core::CacheableOutcome::is_cacheable(&__result) {
+                    __cache.insert(&__key, __result.clone());
+                }
+            },
+            _ => quote! { __cache.insert(&__key, __result.clone()); },
+        }
+    }
+}
+
+/// Generate the insert call of a cache storing wrapped values: `Compressed<R>` with
+/// `compress`, `Arc<R>` with `arc = true`
+///
+/// The typed insert methods of `Result` and `Option` caches do not apply to the wrapper,
+/// so the return kind only decides whether (and with which TTL) the value is cached.
+fn generate_wrapped_insert_call(
+    has_max_memory: bool,
+    return_kind: ReturnKind,
+    attrs: &SyncCacheAttributes,
+) -> TokenStream2 {
+    let err_ttl = &attrs.err_ttl;
+
+    // `None` skips the value, `Some(ttl)` caches it with its own TTL
+    let ttl = match return_kind {
+        ReturnKind::Result if attrs.cache_err => {
+            quote! { Some(if __result.is_ok() { None } else { #err_ttl }) }
+        }
+        ReturnKind::Result => quote! { __result.is_ok().then_some(None) },
+        ReturnKind::Option if !attrs.cache_none => quote! { __result.is_some().then_some(None) },
+        ReturnKind::Outcome if attrs.cache_err => quote! {
+            Some(if ::cachelito_core::CacheableOutcome::is_cacheable(&__result) { None } else { #err_ttl })
+        },
+        ReturnKind::Outcome => quote! {
+            ::cachelito_core::CacheableOutcome::is_cacheable(&__result).then_some(None)
+        },
+        _ => quote! { Some(None) },
+    };
+    let insert_method = if has_max_memory {
+        quote! { insert_with_memory_and_ttl }
+    } else {
+        quote! { insert_with_ttl }
+    };
+
+    let wrap = if let Some(codec) = &attrs.compress {
+        let min_size = attrs.compress_min_size;
+        quote! {
+            let __value = ::cachelito_core::Compressed::new(__result.clone(), #codec, #min_size);
+            #[cfg(feature = "stats")]
+            {
+                if let (Some(__stats), Some((__original, __compressed))) =
+                    (__handle.stats(), __value.compressed_sizes())
+                {
+                    __stats.record_compression(__original, __compressed);
+                }
+            }
+        }
+    } else {
+        quote! { let __value = ::std::sync::Arc::new(__result.clone()); }
+    };
+
+    quote! {
+        if let Some(__ttl) = #ttl {
+            #wrap
+            __cache.#insert_method(&__key, __value, __ttl);
         }
     }
 }
 
-/// Generate the thread-local cache branch
-fn generate_thread_local_branch(
+/// Generate the thread-local cache backend
+#[allow(clippy::too_many_arguments)]
+fn generate_thread_local_backend(
     cache_ident: &syn::Ident,
     order_ident: &syn::Ident,
     ret_type: &TokenStream2,
@@ -50,25 +200,25 @@ fn generate_thread_local_branch(
     policy_expr: &TokenStream2,
     ttl_expr: &TokenStream2,
     frequency_weight_expr: &TokenStream2,
-    key_expr: &TokenStream2,
-    block: &syn::Block,
-    is_result: bool,
-    invalidate_on: &Option<syn::Path>,
-    cache_if: &Option<syn::Path>,
+    on_evict: &Option<syn::Path>,
+    strategy: &Option<syn::Path>,
 ) -> TokenStream2 {
-    // Check if max_memory is None by comparing the token stream
-    let has_max_memory = has_max_memory(max_memory_expr);
-
-    let invalidation_check = generate_invalidation_check(invalidate_on);
-    let cache_condition = generate_cache_condition(cache_if, has_max_memory, is_result);
+    let removal_listener = generate_removal_listener(on_evict);
+    let eviction_strategy = generate_eviction_strategy(strategy);
+    let timers_ident = format_ident!("{}_TIMERS", cache_ident);
+    let memory_ident = format_ident!("{}_MEMORY", cache_ident);
+    let state_ident = format_ident!("{}_EVICTION_STATE", cache_ident);
 
     quote! {
         thread_local! {
             static #cache_ident: RefCell<std::collections::HashMap<String, CacheEntry<#ret_type>>> = RefCell::new(std::collections::HashMap::new());
             static #order_ident: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+            static #timers_ident: cachelito_core::TimerWheel = cachelito_core::TimerWheel::new();
+            static #memory_ident: cachelito_core::MemoryUsage = cachelito_core::MemoryUsage::new();
+            static #state_ident: cachelito_core::EvictionState = cachelito_core::EvictionState::new();
         }
 
-        let __cache = ThreadLocalCache::<#ret_type>::new(
+        CacheBackend::ThreadLocal(ThreadLocalCache::<#ret_type>::new(
             &#cache_ident,
             &#order_ident,
             #limit_expr,
@@ -76,24 +226,72 @@ fn generate_thread_local_branch(
             #policy_expr,
             #ttl_expr,
             #frequency_weight_expr
-        );
+        )#removal_listener #eviction_strategy
+        .with_timer_wheel(&#timers_ident)
+        .with_memory_usage(&#memory_ident)
+        .with_eviction_state(&#state_ident))
+    }
+}
 
+/// Generate the cache lookup, execution and insertion logic shared by both backends
+fn generate_cached_call(
+    key_expr: &TokenStream2,
+    block: &syn::Block,
+    return_kind: ReturnKind,
+    has_max_memory: bool,
+    attrs: &SyncCacheAttributes,
+) -> TokenStream2 {
+    let invalidation_check = generate_invalidation_check(&attrs.invalidate_on);
+    let cache_condition = generate_cache_condition(has_max_memory, return_kind, attrs);
+
+    // On a miss, entries demoted to the secondary tier are promoted before recomputing
+    let secondary_lookup = match (&attrs.l2, has_max_memory) {
+        (None, _) => quote! {},
+        (Some(_), false) => quote! {
+            if let Some(cached) = __cache.promote_secondary(&__key) {
+                #invalidation_check
+            }
+        },
+        (Some(_), true) => quote! {
+            if let Some(cached) = __cache.promote_secondary_with_memory(&__key) {
+                #invalidation_check
+            }
+        },
+    };
+
+    // Compressed values are decompressed on every hit (undecodable ones are recomputed),
+    // and shared values are cloned out of their `Arc` once the cache locks are released
+    let lookup = if attrs.compress.is_some() {
+        quote! { __cache.get(&__key).and_then(::cachelito_core::Compressed::decompress) }
+    } else if attrs.arc {
+        quote! { __cache.get(&__key).map(|__shared| ::std::clone::Clone::clone(&*__shared)) }
+    } else {
+        quote! { __cache.get(&__key) }
+    };
+
+    quote! {
         let __key = #key_expr;
 
-        if let Some(cached) = __cache.get(&__key) {
+        if let Some(cached) = #lookup {
             #invalidation_check
         }
+        #secondary_lookup
 
+        // The time the body takes is the cost of the entry, for cost-aware policies
+        let __started = ::std::time::Instant::now();
         let __result = (|| #block)();
-        #cache_condition
+        ::cachelito_core::with_compute_cost(__started.elapsed(), || {
+            #cache_condition
+        });
         __result
     }
 }
+
 /// Check if max_memory is None by comparing the token stream
 fn has_max_memory(max_memory_expr: &TokenStream2) -> bool {
     let max_memory_str = max_memory_expr.to_string();
-    let has_max_memory = !max_memory_str.contains("None");
-    has_max_memory
+
+    !max_memory_str.contains("None")
 }
 
 /// Generate invalidation check code if an invalidate_on function is specified
@@ -115,15 +313,109 @@ fn generate_invalidation_check(invalidate_on: &Option<syn::Path>) -> TokenStream
     }
 }
 
+/// Generate the builder call that registers the `on_evict` removal listener, if specified
+fn generate_removal_listener(on_evict: &Option<syn::Path>) -> TokenStream2 {
+    if let Some(listener_fn) = on_evict {
+        quote! { .with_removal_listener(&#listener_fn) }
+    } else {
+        quote! {}
+    }
+}
+
+/// Generate the builder call that registers the custom eviction strategy, if specified
+fn generate_eviction_strategy(strategy: &Option<syn::Path>) -> TokenStream2 {
+    if let Some(strategy) = strategy {
+        quote! { .with_eviction_strategy(&#strategy) }
+    } else {
+        quote! {}
+    }
+}
+
+/// Generate the `GlobalCache` constructor expression for the configured attributes
+#[allow(clippy::too_many_arguments)]
+fn generate_global_cache_constructor(
+    map_expr: &TokenStream2,
+    order_expr: &TokenStream2,
+    stats_ident: &syn::Ident,
+    ret_type: &TokenStream2,
+    limit_expr: &TokenStream2,
+    max_memory_expr: &TokenStream2,
+    policy_expr: &TokenStream2,
+    ttl_expr: &TokenStream2,
+    frequency_weight_expr: &TokenStream2,
+    on_evict: &Option<syn::Path>,
+    strategy: &Option<syn::Path>,
+    l2: &Option<syn::Path>,
+    shards: Option<&syn::Ident>,
+    accesses: Option<&TokenStream2>,
+    timers: Option<&TokenStream2>,
+    memory: Option<&TokenStream2>,
+    eviction_state: Option<&TokenStream2>,
+) -> TokenStream2 {
+    let removal_listener = generate_removal_listener(on_evict);
+    let eviction_strategy = generate_eviction_strategy(strategy);
+    let secondary_cache = match l2 {
+        Some(l2) => quote! { .with_secondary_cache(&#l2) },
+        None => quote! {},
+    };
+    let sharding = match shards {
+        Some(shards_ident) => quote! { .with_shards(&#shards_ident) },
+        None => quote! {},
+    };
+    let access_buffer = match accesses {
+        Some(accesses_expr) => quote! { .with_access_buffer(#accesses_expr) },
+        None => quote! {},
+    };
+    let timer_wheel = match timers {
+        Some(timers_expr) => quote! { .with_timer_wheel(#timers_expr) },
+        None => quote! {},
+    };
+    let memory_usage = match memory {
+        Some(memory_expr) => quote! { .with_memory_usage(#memory_expr) },
+        None => quote! {},
+    };
+    let eviction_state = match eviction_state {
+        Some(state_expr) => quote! { .with_eviction_state(#state_expr) },
+        None => quote! {},
+    };
+
+    quote! {
+        {
+            #[cfg(feature = "stats")]
+            let __cache = GlobalCache::<#ret_type>::new(
+                #map_expr,
+                #order_expr,
+                #limit_expr,
+                #max_memory_expr,
+                #policy_expr,
+                #ttl_expr,
+                #frequency_weight_expr,
+                &#stats_ident,
+            )#removal_listener #eviction_strategy #secondary_cache #sharding #access_buffer #timer_wheel #memory_usage #eviction_state;
+            #[cfg(not(feature = "stats"))]
+            let __cache = GlobalCache::<#ret_type>::new(
+                #map_expr,
+                #order_expr,
+                #limit_expr,
+                #max_memory_expr,
+                #policy_expr,
+                #ttl_expr,
+                #frequency_weight_expr,
+            )#removal_listener #eviction_strategy #secondary_cache #sharding #access_buffer #timer_wheel #memory_usage #eviction_state;
+            __cache
+        }
+    }
+}
+
 /// Generate cache condition check code if a cache_if function is specified
 fn generate_cache_condition(
-    cache_if: &Option<syn::Path>,
     has_max_memory: bool,
-    is_result: bool,
+    return_kind: ReturnKind,
+    attrs: &SyncCacheAttributes,
 ) -> TokenStream2 {
-    let insert_call = generate_insert_call(has_max_memory, is_result);
+    let insert_call = generate_insert_call(has_max_memory, return_kind, attrs);
 
-    if let Some(pred_fn) = cache_if {
+    if let Some(pred_fn) = &attrs.cache_if {
         quote! {
             // Check if result should be cached using cache_if function
             // Only cache if function returns true
@@ -137,8 +429,9 @@ fn generate_cache_condition(
     }
 }
 
-/// Generate the global cache branch
-fn generate_global_branch(
+/// Generate the global cache backend, registering its statistics and invalidation callbacks
+#[allow(clippy::too_many_arguments)]
+fn generate_global_backend(
     cache_ident: &syn::Ident,
     order_ident: &syn::Ident,
     stats_ident: &syn::Ident,
@@ -148,17 +441,153 @@ fn generate_global_branch(
     policy_expr: &TokenStream2,
     ttl_expr: &TokenStream2,
     frequency_weight_expr: &TokenStream2,
-    key_expr: &TokenStream2,
-    block: &syn::Block,
     fn_name_str: &str,
-    is_result: bool,
+    type_params: &[TokenStream2],
     attrs: &SyncCacheAttributes,
 ) -> TokenStream2 {
-    // ...existing code...
-    let has_max_memory = has_max_memory(max_memory_expr);
+    // Statics cannot use generic parameters, so generic functions keep one cache
+    // per monomorphization in a type-keyed registry instead
+    let is_generic = !type_params.is_empty();
+    let registry_ident = format_ident!("{}_REGISTRY", cache_ident);
+    let shards_ident = format_ident!("{}_SHARDS", cache_ident);
+    let sharded = attrs.shards.is_some() && !is_generic;
+    // Sharded caches replay the buffers of their shards
+    let accesses_ident = format_ident!("{}_ACCESSES", cache_ident);
+    let accesses_expr = if sharded {
+        quote! { #shards_ident.access_buffer(0) }
+    } else {
+        quote! { &#accesses_ident }
+    };
+    let buffered = attrs.buffered_access && !is_generic;
+    // Expirations are tracked in a timer wheel, per shard for sharded caches
+    let timers_ident = format_ident!("{}_TIMERS", cache_ident);
+    let timers_expr = if sharded {
+        quote! { #shards_ident.timer_wheel(0) }
+    } else {
+        quote! { &#timers_ident }
+    };
+    // Entry sizes are summed in a running total, per shard for sharded caches
+    let memory_ident = format_ident!("{}_MEMORY", cache_ident);
+    let memory_expr = if sharded {
+        quote! { #shards_ident.memory_usage(0) }
+    } else {
+        quote! { &#memory_ident }
+    };
+    // Built-in strategies keep their state per shard too; the registry of generic
+    // functions gives each monomorphization its own
+    let state_ident = format_ident!("{}_EVICTION_STATE", cache_ident);
+    let state_expr = if sharded {
+        quote! { #shards_ident.eviction_state(0) }
+    } else {
+        quote! { &#state_ident }
+    };
+    let (map_expr, order_expr) = if is_generic {
+        (quote! { __map }, quote! { __order })
+    } else if sharded {
+        (
+            quote! { #shards_ident.map(0) },
+            quote! { #shards_ident.order(0) },
+        )
+    } else {
+        (quote! { &#cache_ident }, quote! { &#order_ident })
+    };
 
-    let invalidation_check = generate_invalidation_check(&attrs.invalidate_on);
-    let cache_condition = generate_cache_condition(&attrs.cache_if, has_max_memory, is_result);
+    let cache_constructor = generate_global_cache_constructor(
+        &map_expr,
+        &order_expr,
+        stats_ident,
+        ret_type,
+        limit_expr,
+        max_memory_expr,
+        policy_expr,
+        ttl_expr,
+        frequency_weight_expr,
+        &attrs.on_evict,
+        &attrs.strategy,
+        &attrs.l2,
+        sharded.then_some(&shards_ident),
+        buffered.then_some(&accesses_expr),
+        (!is_generic).then_some(&timers_expr),
+        (!is_generic).then_some(&memory_expr),
+        (!is_generic).then_some(&state_expr),
+    );
+    let cache_constructor = if is_generic {
+        quote! {
+            #registry_ident.get_or_init::<(#(#type_params,)*), #ret_type>(
+                |__map, __order| #cache_constructor
+            )
+        }
+    } else {
+        cache_constructor
+    };
+
+    let (invalidate_all, invalidate_where, purge_expired, shrink) = if is_generic {
+        (
+            quote! { #registry_ident.invalidate_all(); },
+            quote! { #registry_ident.invalidate_where(check_fn); },
+            quote! { #registry_ident.purge_expired() },
+            quote! { #registry_ident.shrink(fraction) },
+        )
+    } else {
+        (
+            quote! {
+                let __cache = #cache_constructor;
+                __cache.invalidate_all();
+            },
+            quote! {
+                let __cache = #cache_constructor;
+                __cache.invalidate_where(check_fn);
+            },
+            quote! {
+                let __cache = #cache_constructor;
+                __cache.purge_expired()
+            },
+            quote! {
+                let __cache = #cache_constructor;
+                __cache.shrink(fraction)
+            },
+        )
+    };
+
+    let storage = if is_generic {
+        quote! {
+            static #registry_ident: once_cell::sync::Lazy<cachelito_core::TypeCacheRegistry> =
+                once_cell::sync::Lazy::new(cachelito_core::TypeCacheRegistry::new);
+        }
+    } else if let Some(shards) = attrs.shards {
+        quote! {
+            static #shards_ident: once_cell::sync::Lazy<cachelito_core::CacheShards<#ret_type>> =
+                once_cell::sync::Lazy::new(|| cachelito_core::CacheShards::new(#shards));
+        }
+    } else {
+        quote! {
+            static #cache_ident: once_cell::sync::Lazy<parking_lot::RwLock<std::collections::HashMap<String, CacheEntry<#ret_type>>>> =
+                once_cell::sync::Lazy::new(|| parking_lot::RwLock::new(std::collections::HashMap::new()));
+            static #order_ident: once_cell::sync::Lazy<parking_lot::Mutex<VecDeque<String>>> =
+                once_cell::sync::Lazy::new(|| parking_lot::Mutex::new(VecDeque::new()));
+        }
+    };
+    let storage = if buffered && !sharded {
+        quote! {
+            #storage
+            static #accesses_ident: once_cell::sync::Lazy<cachelito_core::AccessBuffer> =
+                once_cell::sync::Lazy::new(cachelito_core::AccessBuffer::new);
+        }
+    } else {
+        storage
+    };
+    let storage = if !is_generic && !sharded {
+        quote! {
+            #storage
+            static #timers_ident: once_cell::sync::Lazy<cachelito_core::TimerWheel> =
+                once_cell::sync::Lazy::new(cachelito_core::TimerWheel::new);
+            static #memory_ident: cachelito_core::MemoryUsage = cachelito_core::MemoryUsage::new();
+            static #state_ident: once_cell::sync::Lazy<cachelito_core::EvictionState> =
+                once_cell::sync::Lazy::new(cachelito_core::EvictionState::new);
+        }
+    } else {
+        storage
+    };
 
     // ...existing code...
 
@@ -188,8 +617,7 @@ fn generate_global_branch(
                     cachelito_core::InvalidationRegistry::global().register_callback(
                         #fn_name_str,
                         move || {
-                            #cache_ident.write().clear();
-                            #order_ident.lock().clear();
+                            #invalidate_all
                         }
                     );
                 });
@@ -209,35 +637,87 @@ fn generate_global_branch(
                 cachelito_core::InvalidationRegistry::global().register_invalidation_callback(
                     #fn_name_str,
                     move |check_fn: &dyn Fn(&str) -> bool| {
-                        let mut map_write = #cache_ident.write();
-                        let mut order_write = #order_ident.lock();
-
-                        // Collect keys to remove based on check function
-                        let keys_to_remove: Vec<String> = map_write
-                            .keys()
-                            .filter(|k| check_fn(k.as_str()))
-                            .cloned()
-                            .collect();
-
-                        // Remove matched keys
-                        for key in &keys_to_remove {
-                            map_write.remove(key);
-                            if let Some(pos) = order_write.iter().position(|k| k == key) {
-                                order_write.remove(pos);
-                            }
-                        }
+                        // Remove the keys matched by the check function
+                        #invalidate_where
                     }
                 );
             });
         }
     };
 
+    let purge_registration = quote! {
+        // Register the cache for `purge_expired` and the expiration sweeper
+        {
+            use std::sync::Once;
+            static PURGE_REGISTER_ONCE: Once = Once::new();
+            PURGE_REGISTER_ONCE.call_once(|| {
+                cachelito_core::InvalidationRegistry::global().register_purge_callback(
+                    #fn_name_str,
+                    move || { #purge_expired }
+                );
+            });
+        }
+        // Register the cache for `shrink_all_caches` and the memory pressure monitor
+        {
+            use std::sync::Once;
+            static SHRINK_REGISTER_ONCE: Once = Once::new();
+            SHRINK_REGISTER_ONCE.call_once(|| {
+                cachelito_core::InvalidationRegistry::global().register_shrink_callback(
+                    #fn_name_str,
+                    move |fraction: f64| { #shrink }
+                );
+            });
+        }
+    };
+
+    // Memory-aware caches count against the global memory budget
+    let budget_registration = if has_max_memory(max_memory_expr) && !is_generic {
+        quote! {
+            {
+                use std::sync::Once;
+                static BUDGET_REGISTER_ONCE: Once = Once::new();
+                BUDGET_REGISTER_ONCE.call_once(|| {
+                    cachelito_core::MemoryBudget::global().register(
+                        #fn_name_str,
+                        move || {
+                            let __cache = #cache_constructor;
+                            __cache.memory_usage()
+                        },
+                        move || {
+                            let __cache = #cache_constructor;
+                            __cache.evict()
+                        },
+                    );
+                });
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let persist_register = if has_max_memory(max_memory_expr) {
+        quote! { register_with_memory }
+    } else {
+        quote! { register }
+    };
+    let persist_registration = if attrs.persist {
+        quote! {
+            // Register the cache for disk snapshots, restoring any snapshot already loaded
+            {
+                use std::sync::Once;
+                static PERSIST_REGISTER_ONCE: Once = Once::new();
+                PERSIST_REGISTER_ONCE.call_once(|| {
+                    cachelito_core::persist::#persist_register(#fn_name_str, #cache_constructor);
+                });
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     quote! {
         // ...existing code...
-        static #cache_ident: once_cell::sync::Lazy<parking_lot::RwLock<std::collections::HashMap<String, CacheEntry<#ret_type>>>> =
-            once_cell::sync::Lazy::new(|| parking_lot::RwLock::new(std::collections::HashMap::new()));
-        static #order_ident: once_cell::sync::Lazy<parking_lot::Mutex<VecDeque<String>>> =
-            once_cell::sync::Lazy::new(|| parking_lot::Mutex::new(VecDeque::new()));
+        #storage
 
         #[cfg(feature = "stats")]
         static #stats_ident: once_cell::sync::Lazy<cachelito_core::CacheStats> =
@@ -254,38 +734,218 @@ fn generate_global_branch(
 
         #invalidation_registration
         #invalidation_callback_registration
+        #purge_registration
+        #budget_registration
+        #persist_registration
 
-        #[cfg(feature = "stats")]
-        let __cache = GlobalCache::<#ret_type>::new(
-            &#cache_ident,
-            &#order_ident,
-            #limit_expr,
-            #max_memory_expr,
-            #policy_expr,
-            #ttl_expr,
-            #frequency_weight_expr,
-            &#stats_ident,
-        );
-        #[cfg(not(feature = "stats"))]
-        let __cache = GlobalCache::<#ret_type>::new(
-            &#cache_ident,
-            &#order_ident,
-            #limit_expr,
-            #max_memory_expr,
-            #policy_expr,
-            #ttl_expr,
-            #frequency_weight_expr,
-        );
+        CacheBackend::Global(#cache_constructor)
+    }
+}
 
-        let __key = #key_expr;
-        if let Some(cached) = __cache.get(&__key) {
-            #invalidation_check
+/// Generate the insert function used by the cache handle, honoring `max_memory`
+fn generate_handle_insert_fn(
+    ret_type: &TokenStream2,
+    has_max_memory: bool,
+    backend_variant: &TokenStream2,
+) -> TokenStream2 {
+    let insert_method = if has_max_memory {
+        quote! { insert_with_memory }
+    } else {
+        quote! { insert }
+    };
+
+    quote! {
+        |__backend: &CacheBackend<#ret_type>, __key: &str, __value: #ret_type| {
+            if let CacheBackend::#backend_variant(__cache) = __backend {
+                __cache.#insert_method(__key, __value);
+            }
         }
+    }
+}
 
-        let __result = (|| #block)();
-        #cache_condition
-        __result
+/// Add a `'static` bound to every type parameter that lacks one
+///
+/// The bound goes next to the existing bounds of the parameter (inline or in the
+/// `where` clause) so that it is not split across two places.
+fn add_static_bounds(generics: &mut syn::Generics) {
+    let is_static = |bound: &syn::TypeParamBound| matches!(bound, syn::TypeParamBound::Lifetime(lt) if lt.ident == "static");
+
+    let param_idents: Vec<syn::Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+    for param_ident in param_idents {
+        let where_predicate = generics.where_clause.as_mut().and_then(|where_clause| {
+            where_clause.predicates.iter_mut().find_map(|predicate| match predicate {
+                syn::WherePredicate::Type(predicate)
+                    if matches!(&predicate.bounded_ty, syn::Type::Path(ty) if ty.path.is_ident(&param_ident)) =>
+                {
+                    Some(predicate)
+                }
+                _ => None,
+            })
+        });
+
+        if let Some(predicate) = where_predicate {
+            if !predicate.bounds.iter().any(is_static) {
+                predicate.bounds.push(syn::parse_quote!('static));
+            }
+        } else if let Some(param) = generics
+            .type_params_mut()
+            .find(|param| param.ident == param_ident)
+        {
+            if !param.bounds.iter().any(is_static) {
+                param.bounds.push(syn::parse_quote!('static));
+            }
+        }
+    }
+}
+
+/// Lifetime given to elided references in the arguments type of the cache handle
+const HANDLE_LIFETIME: &str = "'__cachelito";
+
+/// Replaces elided and anonymous (`'_`) lifetimes with a named one
+struct NameElidedLifetimes {
+    lifetime: syn::Lifetime,
+    replaced: bool,
+}
+
+impl VisitMut for NameElidedLifetimes {
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.lifetime.clone());
+            self.replaced = true;
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.lifetime.clone();
+            self.replaced = true;
+        }
+    }
+}
+
+/// Arguments type, key function and generic parameters of the cache handle
+struct HandleArgs {
+    args_ty: TokenStream2,
+    key_fn_expr: TokenStream2,
+    generics: TokenStream2,
+}
+
+/// Build the arguments type of the cache handle from the function signature
+///
+/// Single-argument functions use the argument type directly, others use a tuple.
+/// The receiver of a method comes first, as `&Self`, unless `include_receiver` is
+/// `false` (instance caches, whose keys do not contain `self`). The key function
+/// produces exactly the same key as the cached function.
+///
+/// Elided lifetimes are named only when the handle gets its own companion function;
+/// handles built inline keep them anonymous so that they are inferred.
+fn generate_handle_args(
+    sig: &syn::Signature,
+    include_receiver: bool,
+    name_lifetimes: bool,
+) -> HandleArgs {
+    let lifetime = if name_lifetimes {
+        HANDLE_LIFETIME
+    } else {
+        "'_"
+    };
+    let mut namer = NameElidedLifetimes {
+        lifetime: syn::Lifetime::new(lifetime, proc_macro2::Span::call_site()),
+        replaced: false,
+    };
+
+    let mut types: Vec<syn::Type> = Vec::new();
+    for arg in sig.inputs.iter() {
+        let mut ty = match arg {
+            FnArg::Receiver(_) if include_receiver => syn::parse_quote! { &Self },
+            FnArg::Receiver(_) => continue,
+            FnArg::Typed(pat_type) => (*pat_type.ty).clone(),
+        };
+        // Only shared access is needed to build the key
+        if let syn::Type::Reference(reference) = &mut ty {
+            reference.mutability = None;
+        }
+        namer.visit_type_mut(&mut ty);
+        types.push(ty);
+    }
+
+    let (args_ty, key_parts) = match types.len() {
+        0 => (quote! { () }, Vec::new()),
+        1 => {
+            let ty = &types[0];
+            (quote! { #ty }, vec![quote! { __args }])
+        }
+        _ => {
+            let parts = (0..types.len())
+                .map(|i| {
+                    let index = syn::Index::from(i);
+                    quote! { &__args.#index }
+                })
+                .collect();
+            (quote! { (#(#types),*) }, parts)
+        }
+    };
+
+    // Lifetimes must come before type parameters
+    let mut params: Vec<TokenStream2> = sig.generics.lifetimes().map(|lt| quote! { #lt }).collect();
+    if namer.replaced && name_lifetimes {
+        let lifetime = &namer.lifetime;
+        params.push(quote! { #lifetime });
+    }
+    params.extend(sig.generics.type_params().map(|param| quote! { #param }));
+    let generics = if params.is_empty() {
+        quote! {}
+    } else {
+        quote! { <#(#params),*> }
+    };
+
+    HandleArgs {
+        args_ty,
+        key_fn_expr: generate_key_expr_with_cacheable_key(false, &key_parts),
+        generics,
+    }
+}
+
+/// Check that `scope = "instance"` is used on a `&self` method together with `cache_field`
+fn validate_instance_scope(
+    sig: &syn::Signature,
+    attrs: &SyncCacheAttributes,
+    is_instance: bool,
+) -> syn::Result<()> {
+    if !is_instance {
+        return match &attrs.cache_field {
+            Some(field) => Err(syn::Error::new_spanned(
+                field,
+                "`cache_field` requires `scope = \"instance\"`",
+            )),
+            None => Ok(()),
+        };
     }
+
+    let has_ref_self = matches!(
+        sig.inputs.first(),
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_none()
+    );
+    if !has_ref_self {
+        return Err(syn::Error::new_spanned(
+            &sig.ident,
+            "`scope = \"instance\"` requires a method taking `&self`",
+        ));
+    }
+    if attrs.cache_field.is_none() {
+        return Err(syn::Error::new_spanned(
+            &sig.ident,
+            "`scope = \"instance\"` requires `cache_field = \"...\"` naming a `CacheSlot` field of `self`",
+        ));
+    }
+    if !attrs.tags.is_empty() || !attrs.events.is_empty() || !attrs.dependencies.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.ident,
+            "`tags`, `events` and `dependencies` are not supported with `scope = \"instance\"`: instance caches are not registered globally",
+        ));
+    }
+    Ok(())
 }
 
 /// A procedural macro that adds automatic memoization to functions and methods.
@@ -316,6 +976,12 @@ fn generate_global_branch(
 ///   - `"arc"` - Adaptive Replacement Cache (hybrid LRU/LFU)
 ///   - `"random"` - Random Replacement
 ///   - `"tlru"` - Time-aware Least Recently Used (combines recency, frequency, and age)
+///   - `"gdsf"` - Greedy-Dual-Size-Frequency (keeps small, popular entries that were slow
+///     to compute)
+///   - `"sieve"` - SIEVE (hits only set a visited bit, a hand evicts unvisited entries)
+///   - `"s3fifo"` - S3-FIFO (a small queue filters out entries accessed at most once)
+///   - `path::to::Strategy` - A custom `EvictionStrategy` of the cached type: a unit struct or
+///     a `static`, e.g. `policy = ByPriority`
 /// - `ttl` (optional): Time-to-live in seconds. Entries older than this will be
 ///   automatically removed when accessed. Default: None (no expiration).
 /// - `frequency_weight` (optional): Weight factor for frequency in TLRU policy.
@@ -328,7 +994,10 @@ fn generate_global_branch(
 ///   - Example: `frequency_weight = 1.5` makes frequently accessed entries more resistant to eviction
 /// - `scope` (optional): Cache scope - where the cache is stored. Options:
 ///   - `"global"` - Global storage shared across all threads (default, uses RwLock)
-///   - `"thread"` - Thread-local storage (no synchronization overhead)
+///   - `"thread"` - Thread-local storage (no synchronization overhead, return types need not be `Send`)
+///   - `"instance"` - A [`CacheSlot`](cachelito_core::CacheSlot) field of `self` (see below)
+/// - `cache_field` (required with `scope = "instance"`): Name of the `CacheSlot<R>` field
+///   holding the cache of the method. Example: `cache_field = "users"`.
 /// - `name` (optional): Custom identifier for the cache in the statistics registry.
 ///   Default: the function name. Useful when you want a more descriptive name or
 ///   when caching multiple versions of a function. Only relevant with `stats` feature.
@@ -345,11 +1014,196 @@ fn generate_global_branch(
 ///   Signature: `fn(key: &String, value: &T) -> bool`. Return `true` to cache the result.
 ///   The check runs after computing the result but before caching it. Example: `cache_if = should_cache`.
 ///   When not specified, all results are cached (default behavior).
+/// - `on_evict` (optional): Function called whenever an entry leaves the cache.
+///   Signature: `fn(key: &str, value: &T, cause: RemovalCause)`. The cause tells whether the
+///   entry was evicted by capacity or memory limits, expired, invalidated, replaced or removed
+///   explicitly. It runs after the cache locks are released. Example: `on_evict = log_removal`.
+/// - `handle` (optional): Whether to generate the `<fn_name>_cache()` companion function
+///   (see below). Default: `true`. Set `handle = false` for methods in trait implementations,
+///   where extra associated functions are not allowed.
+/// - `cache_err` (optional): Also cache `Err` values of `Result`-returning functions
+///   (negative caching), protecting a failing resource from retry storms. Default: `false`.
+/// - `err_ttl` (optional): Time-to-live in seconds of cached `Err` values. Requires
+///   `cache_err = true`. Default: the `ttl` of the cache.
+/// - `cache_none` (optional): Set `cache_none = false` to skip caching `None` values of
+///   `Option`-returning functions. Default: `true`.
+/// - `result` (optional): Overrides `Result` detection. `result = true` caches only values
+///   for which [`CacheableOutcome::is_cacheable`](cachelito_core::CacheableOutcome) returns
+///   `true`, which covers `Result` aliases with other names and custom enums. `result = false`
+///   caches every value as is. Default: detected from the return type.
+/// - `persist` (optional): Register the cache for disk snapshots with
+///   [`persist::save_all`](cachelito_core::persist) and `persist::load_all`. Requires the
+///   `persist` feature, `scope = "global"`, a non-generic function and a return type
+///   implementing `serde::Serialize` and `serde::de::DeserializeOwned`. Default: `false`.
+/// - `l2` (optional): Path of a `static` [`SecondaryCache`](cachelito_core::SecondaryCache)
+///   receiving the entries evicted by `limit` or `max_memory` (see below). Requires
+///   `scope = "global"` and a non-generic function. Default: none.
+/// - `shards` (optional): Number of independently locked partitions of a global cache
+///   (see below). Requires `scope = "global"` and a non-generic function. Default: one.
+/// - `buffered_access` (optional): Record hits in a lossy buffer and apply them to the
+///   eviction policy in batches (see below). Requires `scope = "global"` and a
+///   non-generic function. Default: `false`.
+/// - `compress` (optional): Store values compressed with `"lz4"` or `"zstd"` (see below).
+///   Requires the feature of the same name and a return type implementing
+///   [`Compressible`](cachelito_core::Compressible). Not supported with `persist`, `l2` or
+///   `scope = "instance"`. Default: none.
+/// - `compress_min_size` (optional): Smallest value, in bytes, that gets compressed
+///   (e.g., `"4KB"`). Default: `"1KB"`.
+/// - `arc` (optional): Store values in an `Arc` (see below). Not supported with `compress`,
+///   `persist`, `l2` or `scope = "instance"`. Default: `false`.
+///
+/// # Cache Handle
+///
+/// The macro generates a companion function `<fn_name>_cache()` returning a
+/// [`CacheHandle`](cachelito_core::CacheHandle) with typed `get`, `insert`, `invalidate`,
+/// `len`, `clear` and `stats` methods. Keys are built exactly like the cached function
+/// builds them. The handle takes the arguments as a reference to the argument type
+/// (single argument) or to a tuple of them; methods are keyed by `&Self` first and
+/// their companion is an associated function (`Type::<fn_name>_cache()`).
+///
+/// ```ignore
+/// #[cache(scope = "global", limit = 100)]
+/// fn fetch_user(id: u64) -> User { load_user(id) }
+///
+/// fetch_user_cache().invalidate(&42);
+/// ```
+///
+/// # Generic Functions
+///
+/// Functions with type parameters are supported with `scope = "global"`. Each
+/// monomorphization gets its own cache, stored in a
+/// [`TypeCacheRegistry`](cachelito_core::TypeCacheRegistry) keyed by the `TypeId` of the
+/// type parameters, so type parameters implicitly get a `'static` bound. Invalidation
+/// applies to every monomorphization. Const generic parameters are not supported.
+///
+/// ```ignore
+/// #[cache(limit = 100)]
+/// fn parse_value<T: FromStr + Clone + Send + Sync>(input: String) -> Option<T> {
+///     input.parse().ok()
+/// }
+///
+/// parse_value::<u8>("42".to_string());   // cached in the `u8` cache
+/// parse_value::<f64>("42".to_string());  // cached separately
+/// parse_value_cache::<u8>().len();
+/// ```
+///
+/// # Instance Scope
+///
+/// With `scope = "instance"`, a `&self` method stores its results in the `CacheSlot`
+/// field named by `cache_field`. Each instance has its own cache, dropped with it, and
+/// `self` is not part of the key. The companion handle is a method
+/// (`self.<fn_name>_cache()`) keyed by the remaining arguments. Instance caches are not
+/// registered globally, so `tags`, `events` and `dependencies` are not supported.
+///
+/// ```ignore
+/// struct Repository {
+///     users: CacheSlot<Option<User>>,
+/// }
+///
+/// impl Repository {
+///     #[cache(scope = "instance", cache_field = "users", limit = 100)]
+///     fn find_user(&self, id: u64) -> Option<User> { query_user(id) }
+/// }
+/// ```
+///
+/// # Persistence
+///
+/// With `persist = true`, the cache registers itself under its name (`name` or the
+/// function name) on first use. Snapshots loaded before that are applied at registration,
+/// so loading at startup warms up the cache before its first call.
+///
+/// ```ignore
+/// #[cache(limit = 1000, ttl = 3600, persist = true)]
+/// fn fetch_rates(currency: String) -> Rates { query_rates(&currency) }
+///
+/// cachelito::persist::load_all("snapshots")?;   // at startup
+/// cachelito::persist::save_all("snapshots")?;   // before exiting
+/// ```
+///
+/// # Two-Tier Caching
+///
+/// With `l2 = PATH`, entries evicted from memory are serialized into the secondary tier
+/// instead of being dropped. A miss in memory checks the secondary tier before running the
+/// function, and a hit there moves the entry back into memory. Expired, invalidated and
+/// explicitly removed entries are removed from both tiers. The cache handle only reads the
+/// in-memory tier.
+///
+/// ```ignore
+/// static REPORTS_L2: Lazy<SecondaryCache<Report>> = Lazy::new(|| {
+///     SecondaryCache::new(FileStore::new("/var/cache/reports").unwrap(), JsonSerializer)
+/// });
+///
+/// #[cache(max_memory = "64MB", l2 = REPORTS_L2)]
+/// fn build_report(id: u64) -> Report { render_report(id) }
+/// ```
+///
+/// # Sharding
+///
+/// A global cache is protected by a single lock, which every write and every LRU hit
+/// takes. With `shards = N`, keys are spread by hash over `N` partitions with their own
+/// locks, and each partition enforces its share of `limit` and `max_memory`. Eviction
+/// policies then apply per partition, so they are approximate for the cache as a whole.
+///
+/// ```ignore
+/// #[cache(limit = 10_000, policy = "lru", shards = 16)]
+/// fn resolve(host: String) -> IpAddr { lookup(&host) }
+/// ```
+///
+/// # Buffered Access
+///
+/// With the LRU, LFU, ARC, TLRU, GDSF, SIEVE and S3-FIFO policies, every hit updates the eviction policy under
+/// the cache locks. With `buffered_access = true`, hits are recorded in a lossy buffer
+/// without waiting and applied in batches, so concurrent reads rarely block each other.
+/// Hits dropped while the buffer is busy are not counted: recency and frequency become
+/// sampled, which is enough to keep popular entries cached.
+///
+/// ```ignore
+/// #[cache(scope = "global", limit = 10_000, policy = "lru", buffered_access = true)]
+/// fn resolve(host: String) -> IpAddr { lookup(&host) }
+/// ```
+///
+/// # Compression
+///
+/// With `compress = "lz4"` or `compress = "zstd"`, values of at least `compress_min_size`
+/// bytes are compressed on insert and decompressed on every hit; values that do not
+/// shrink are kept as they are. The cache stores
+/// [`Compressed<R>`](cachelito_core::Compressed), which is also the value type of the
+/// cache handle and of `on_evict` listeners. `max_memory` counts the compressed size, and
+/// the statistics report the compression ratio.
+///
+/// ```ignore
+/// #[cache(max_memory = "256MB", compress = "zstd", compress_min_size = "4KB")]
+/// fn render_report(id: u64) -> String { build_json(id) }
+///
+/// let stats = render_report_cache().stats().unwrap();
+/// println!("Compression ratio: {:.1}", stats.compression_ratio());
+/// ```
+///
+/// # Shared Values
+///
+/// Every hit clones the cached value, under the cache locks. With `arc = true`, the cache
+/// stores `Arc<R>`: lookups and evictions only bump a reference count, and the function
+/// still returns `R`, cloned once the locks are released. The cache handle and `on_evict`
+/// listeners see `Arc<R>`.
+///
+/// Functions returning `Arc<R>` already share their values: hits return the cached `Arc`
+/// without copying `R`, which then need not implement `Clone`. `arc = true` leaves them
+/// unchanged.
+///
+/// ```ignore
+/// #[cache(limit = 100, arc = true)]
+/// fn load_matrix(id: u64) -> Vec<f64> { read_matrix(id) }
+///
+/// #[cache(limit = 100)]
+/// fn load_model(id: u64) -> Arc<Model> { Arc::new(train(id)) } // `Model` is not `Clone`
+/// ```
 ///
 /// # Cache Behavior
 ///
 /// - **Regular functions**: All results are cached
 /// - **Result-returning functions**: Only `Ok` values are cached, `Err` values are not
+///   (unless `cache_err = true`). Return types whose last path segment is `Result`
+///   (`io::Result<T>`, `anyhow::Result<T>`, ...) are detected too
 /// - **Thread-local storage** (default): Each thread maintains its own independent cache
 /// - **Global storage**: With `scope = "global"`, cache is shared across all threads
 /// - **Methods**: Works with `self`, `&self`, and `&mut self` parameters
@@ -577,15 +1431,119 @@ pub fn cache(attr: TokenStream, item: TokenStream) -> TokenStream {
     // Parse function
     let input = parse_macro_input!(item as ItemFn);
     let vis = &input.vis;
-    let sig = &input.sig;
-    let ident = &sig.ident;
+    let ident = &input.sig.ident;
     let block = &input.block;
 
-    // Extract return type
+    // Generic functions keep one cache per monomorphization in a `TypeCacheRegistry`,
+    // which is keyed by `TypeId` and therefore needs `'static` type parameters
+    if let Some(param) = input.sig.generics.const_params().next() {
+        return syn::Error::new_spanned(
+            param,
+            "#[cache] does not support const generic parameters",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let mut sig = input.sig.clone();
+    add_static_bounds(&mut sig.generics);
+    let sig = &sig;
+    let type_params: Vec<TokenStream2> = sig
+        .generics
+        .type_params()
+        .map(|param| {
+            let param_ident = &param.ident;
+            quote! { #param_ident }
+        })
+        .collect();
+    let is_generic = !type_params.is_empty();
+    let is_thread_local = attrs.scope.to_string().contains("ThreadLocal");
+    if is_generic && is_thread_local {
+        return syn::Error::new_spanned(
+            &sig.generics,
+            "generic functions only support `scope = \"global\"`: thread-local storage cannot depend on type parameters",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let is_instance = attrs.scope.to_string().contains("Instance");
+    if let Err(err) = validate_instance_scope(sig, &attrs, is_instance) {
+        return err.to_compile_error().into();
+    }
+
+    // Snapshots are stored by cache name, so only the single static cache of a global function can be persisted
+    if attrs.persist && (is_thread_local || is_instance || is_generic) {
+        return syn::Error::new_spanned(
+            &sig.ident,
+            "`persist = true` requires `scope = \"global\"` and a non-generic function",
+        )
+        .to_compile_error()
+        .into();
+    }
+    // Likewise, a static secondary tier can only back a single static cache
+    if attrs.l2.is_some() && (is_thread_local || is_instance || is_generic) {
+        return syn::Error::new_spanned(
+            &sig.ident,
+            "`l2` requires `scope = \"global\"` and a non-generic function",
+        )
+        .to_compile_error()
+        .into();
+    }
+    // Thread-local caches are never contended, and instance and generic caches own their storage
+    if attrs.shards.is_some() && (is_thread_local || is_instance || is_generic) {
+        return syn::Error::new_spanned(
+            &sig.ident,
+            "`shards` requires `scope = \"global\"` and a non-generic function",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // Thread-local caches never wait for their locks, and the buffer is a single static
+    if attrs.buffered_access && (is_thread_local || is_instance || is_generic) {
+        return syn::Error::new_spanned(
+            &sig.ident,
+            "`buffered_access` requires `scope = \"global\"` and a non-generic function",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // Functions returning `Arc` already share their cached values
+    let mut attrs = attrs;
+    attrs.arc &= !is_arc_type(&sig.output);
+    if attrs.arc && attrs.compress.is_some() {
+        return syn::Error::new_spanned(
+            &sig.ident,
+            "`arc = true` cannot be combined with `compress`: compressed bytes are already shared",
+        )
+        .to_compile_error()
+        .into();
+    }
+    // Snapshots, the secondary tier and `CacheSlot` fields hold plain values
+    if (attrs.compress.is_some() || attrs.arc)
+        && (attrs.persist || attrs.l2.is_some() || is_instance)
+    {
+        return syn::Error::new_spanned(
+            &sig.ident,
+            "`compress` and `arc` are not supported with `persist`, `l2` or `scope = \"instance\"`",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // Extract return type; compressed and shared caches store it wrapped
     let ret_type = match &sig.output {
         ReturnType::Type(_, ty) => quote! { #ty },
         ReturnType::Default => quote! { () },
     };
+    let ret_type = if attrs.compress.is_some() {
+        quote! { ::cachelito_core::Compressed<#ret_type> }
+    } else if attrs.arc {
+        quote! { ::std::sync::Arc<#ret_type> }
+    } else {
+        ret_type
+    };
 
     // Parse arguments and detect self
     let mut arg_pats = Vec::new();
@@ -614,14 +1572,14 @@ pub fn cache(attr: TokenStream, item: TokenStream) -> TokenStream {
         ident.to_string().to_uppercase()
     );
 
-    // Generate cache key expression
-    let key_expr = generate_key_expr_with_cacheable_key(has_self, &arg_pats);
+    // Generate cache key expression; instance caches belong to `self`, so it is not part of the key
+    let key_expr = generate_key_expr_with_cacheable_key(has_self && !is_instance, &arg_pats);
 
-    // Detect Result type
-    let is_result = {
-        let s = quote!(#ret_type).to_string().replace(' ', "");
-        s.starts_with("Result<") || s.starts_with("std::result::Result<")
-    };
+    // Detect Result and Option return types
+    let return_kind = detect_return_kind(&sig.output, attrs.result);
+    if let Err(err) = validate_return_attributes(sig, &attrs, return_kind) {
+        return err.to_compile_error().into();
+    }
 
     // Use custom name if provided, otherwise use function name
     let fn_name_str = attrs
@@ -629,56 +1587,403 @@ pub fn cache(attr: TokenStream, item: TokenStream) -> TokenStream {
         .clone()
         .unwrap_or_else(|| ident.to_string());
 
-    // Generate thread-local and global cache branches
-    let thread_local_branch = generate_thread_local_branch(
-        &cache_ident,
-        &order_ident,
-        &ret_type,
-        &attrs.limit,
-        &attrs.max_memory,
-        &attrs.policy,
-        &attrs.ttl,
-        &attrs.frequency_weight,
-        &key_expr,
-        block,
-        is_result,
-        &attrs.invalidate_on,
-        &attrs.cache_if,
-    );
+    let has_max_memory = has_max_memory(&attrs.max_memory);
+    let cached_call = generate_cached_call(&key_expr, block, return_kind, has_max_memory, &attrs);
+    let handle_args = generate_handle_args(sig, !is_instance, attrs.handle);
+    let args_ty = &handle_args.args_ty;
+    let key_fn_expr = &handle_args.key_fn_expr;
+    let handle_generics = &handle_args.generics;
+    let handle_where = &sig.generics.where_clause;
+    let handle_turbofish = if is_generic {
+        quote! { ::<#(#type_params),*> }
+    } else {
+        quote! {}
+    };
 
-    let global_branch = generate_global_branch(
-        &cache_ident,
-        &order_ident,
-        &stats_ident,
-        &ret_type,
-        &attrs.limit,
-        &attrs.max_memory,
-        &attrs.policy,
-        &attrs.ttl,
-        &attrs.frequency_weight,
-        &key_expr,
-        block,
-        &fn_name_str,
-        is_result,
-        &attrs,
-    );
+    // The scope is known at expansion time, so only the storage of the selected backend is
+    // generated: thread-local caches get no global statics (and no `Send + Sync` requirement)
+    let backend_variant = if is_thread_local {
+        quote! { ThreadLocal }
+    } else {
+        quote! { Global }
+    };
+    let insert_fn = generate_handle_insert_fn(&ret_type, has_max_memory, &backend_variant);
+
+    // The handle function owns the cache storage; the cached function looks it up on every call
+    let (handle_body, handle_ret) = if let (true, Some(field)) = (is_instance, &attrs.cache_field) {
+        // Instance caches borrow the storage of the `CacheSlot` field instead of a static
+        let limit = &attrs.limit;
+        let max_memory = &attrs.max_memory;
+        let policy = &attrs.policy;
+        let ttl = &attrs.ttl;
+        let frequency_weight = &attrs.frequency_weight;
+        let removal_listener = generate_removal_listener(&attrs.on_evict);
+        let eviction_strategy = generate_eviction_strategy(&attrs.strategy);
+        (
+            quote! {
+                use ::cachelito_core::{CacheBackend, CacheHandle};
+
+                let __backend = CacheBackend::Global(
+                    self.#field.cache(#limit, #max_memory, #policy, #ttl, #frequency_weight)#removal_listener #eviction_strategy
+                );
+
+                CacheHandle::new(__backend, |__args: &#args_ty| #key_fn_expr, #insert_fn)
+            },
+            quote! { ::cachelito_core::CacheHandle<'_, #args_ty, #ret_type> },
+        )
+    } else if is_thread_local {
+        let thread_local_backend = generate_thread_local_backend(
+            &cache_ident,
+            &order_ident,
+            &ret_type,
+            &attrs.limit,
+            &attrs.max_memory,
+            &attrs.policy,
+            &attrs.ttl,
+            &attrs.frequency_weight,
+            &attrs.on_evict,
+            &attrs.strategy,
+        );
+        (
+            quote! {
+                use ::std::collections::VecDeque;
+                use ::std::cell::RefCell;
+                use ::cachelito_core::{CacheBackend, CacheEntry, CacheHandle, ThreadLocalCache};
+
+                let __backend = { #thread_local_backend };
+
+                CacheHandle::new(__backend, |__args: &#args_ty| #key_fn_expr, #insert_fn)
+            },
+            quote! { ::cachelito_core::CacheHandle<'static, #args_ty, #ret_type> },
+        )
+    } else {
+        let global_backend = generate_global_backend(
+            &cache_ident,
+            &order_ident,
+            &stats_ident,
+            &ret_type,
+            &attrs.limit,
+            &attrs.max_memory,
+            &attrs.policy,
+            &attrs.ttl,
+            &attrs.frequency_weight,
+            &fn_name_str,
+            &type_params,
+            &attrs,
+        );
+        (
+            quote! {
+                use ::std::collections::VecDeque;
+                use ::cachelito_core::{CacheBackend, CacheEntry, CacheHandle, GlobalCache};
+
+                let __backend = { #global_backend };
+
+                CacheHandle::new(__backend, |__args: &#args_ty| #key_fn_expr, #insert_fn)
+            },
+            quote! { ::cachelito_core::CacheHandle<'static, #args_ty, #ret_type> },
+        )
+    };
+
+    let (handle_item, handle_call) = if attrs.handle {
+        let handle_ident = format_ident!("{}_cache", ident);
+        if is_instance {
+            let handle_doc = format!(
+                "Returns a typed handle to the cache of `{}` for this instance, keyed by its arguments.",
+                ident
+            );
+            (
+                quote! {
+                    #[doc = #handle_doc]
+                    #[allow(dead_code)]
+                    #vis fn #handle_ident #handle_generics (&self) -> #handle_ret #handle_where {
+                        #handle_body
+                    }
+                },
+                quote! { self.#handle_ident #handle_turbofish() },
+            )
+        } else {
+            let handle_doc = format!(
+                "Returns a typed handle to the cache of `{}`, keyed by its arguments.",
+                ident
+            );
+            // Methods and associated functions need `Self::` to reach the companion function
+            let handle_path = if has_self || quote!(#sig).to_string().contains("Self") {
+                quote! { Self::#handle_ident }
+            } else {
+                quote! { #handle_ident }
+            };
+            (
+                quote! {
+                    #[doc = #handle_doc]
+                    #[allow(dead_code)]
+                    #vis fn #handle_ident #handle_generics () -> #handle_ret #handle_where {
+                        #handle_body
+                    }
+                },
+                quote! { #handle_path #handle_turbofish() },
+            )
+        }
+    } else {
+        // Without a companion the handle is built inline, so `Self` and generics stay in scope
+        (quote! {}, quote! {{ #handle_body }})
+    };
 
     // Generate final expanded code
-    let scope_expr = &attrs.scope;
     let expanded = quote! {
+        #handle_item
+
         #vis #sig {
-            use ::std::collections::VecDeque;
-            use ::std::cell::RefCell;
-            use ::cachelito_core::{CacheEntry, CacheScope, ThreadLocalCache, GlobalCache, CacheableKey};
+            let __handle = #handle_call;
 
-            let __scope = #scope_expr;
+            let ::cachelito_core::CacheBackend::#backend_variant(__cache) = __handle.backend() else {
+                unreachable!("cache backend does not match the configured scope")
+            };
 
-            if __scope == cachelito_core::CacheScope::ThreadLocal {
-                #thread_local_branch
-            } else {
-                #global_branch
-            }
+            #cached_call
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Type arguments of `ty` if the last segment of its path is `ident` (`Vec<K>`, `HashMap<K, V>`)
+fn path_type_args<'t>(ty: &'t syn::Type, ident: &str) -> Option<Vec<&'t syn::Type>> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != ident {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    Some(
+        args.args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+/// Check that a `#[cache_batch]` function has the `fn(Vec<K>) -> HashMap<K, V>` shape
+/// and only uses attributes that apply per key
+fn validate_batch_function(
+    sig: &syn::Signature,
+    attrs: &SyncCacheAttributes,
+    is_thread_local: bool,
+) -> syn::Result<(syn::PatType, syn::Type)> {
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "#[cache_batch] does not support async functions: use `AsyncGlobalCache::get_many`",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "#[cache_batch] does not support generic functions",
+        ));
+    }
+
+    let shape_error = || {
+        syn::Error::new_spanned(
+            sig,
+            "#[cache_batch] requires a function shaped `fn(keys: Vec<K>) -> HashMap<K, V>`",
+        )
+    };
+    let mut inputs = sig.inputs.iter();
+    let (Some(FnArg::Typed(keys_arg)), None) = (inputs.next(), inputs.next()) else {
+        return Err(shape_error());
+    };
+    if path_type_args(&keys_arg.ty, "Vec").map_or(true, |args| args.len() != 1) {
+        return Err(shape_error());
+    }
+    let ReturnType::Type(_, ret_type) = &sig.output else {
+        return Err(shape_error());
+    };
+    let value_type = match path_type_args(ret_type, "HashMap").as_deref() {
+        Some([_, value_type]) => (*value_type).clone(),
+        _ => return Err(shape_error()),
+    };
+
+    if attrs.scope.to_string().contains("Instance") || attrs.cache_field.is_some() {
+        return Err(syn::Error::new_spanned(
+            &sig.ident,
+            "#[cache_batch] only supports `scope = \"global\"` and `scope = \"thread\"`",
+        ));
+    }
+    if attrs.invalidate_on.is_some()
+        || attrs.cache_if.is_some()
+        || attrs.cache_err
+        || !attrs.cache_none
+        || attrs.result.is_some()
+        || !attrs.handle
+        || attrs.compress.is_some()
+        || attrs.arc
+    {
+        return Err(syn::Error::new_spanned(
+            &sig.ident,
+            "`invalidate_on`, `cache_if`, `cache_err`, `cache_none`, `result`, `handle`, `compress` and `arc` are not supported by #[cache_batch]",
+        ));
+    }
+    if is_thread_local
+        && (attrs.persist || attrs.l2.is_some() || attrs.shards.is_some() || attrs.buffered_access)
+    {
+        return Err(syn::Error::new_spanned(
+            &sig.ident,
+            "`persist`, `l2`, `shards` and `buffered_access` require `scope = \"global\"`",
+        ));
+    }
+
+    Ok((keys_arg.clone(), value_type))
+}
+
+/// A procedural macro that caches a batch function key by key.
+///
+/// The function takes the keys to look up and returns the values it found, shaped
+/// `fn(keys: Vec<K>) -> HashMap<K, V>`. Each value is cached under its own key, so
+/// overlapping batches share entries: a call serves the cached keys and runs the
+/// function body once, with only the missing keys. The result contains every requested
+/// key the cache or the function body had a value for.
+///
+/// # Requirements
+///
+/// - **Keys**: `K` must implement `CacheableKey` (or `DefaultCacheableKey`), `Clone`, `Eq` and `Hash`
+/// - **Values**: `V` must implement `Clone` (and `Send + Sync` with `scope = "global"`)
+///
+/// # Macro Parameters
+///
+/// Accepts the `#[cache]` parameters that apply to single entries: `limit`, `max_memory`,
+/// `policy`, `ttl`, `frequency_weight`, `scope` (`"global"` or `"thread"`), `name`, `tags`,
+/// `events`, `dependencies`, `on_evict`, `persist`, `l2`, `shards` and `buffered_access`. Keys whose values the body
+/// does not return are not cached.
+///
+/// # Examples
+///
+/// ```ignore
+/// use cachelito::cache_batch;
+/// use std::collections::HashMap;
+///
+/// #[cache_batch(limit = 1000, ttl = 60)]
+/// fn get_users(ids: Vec<u64>) -> HashMap<u64, User> {
+///     // Only the ids not cached yet reach the database
+///     db.fetch_users(&ids)
+/// }
+///
+/// let users = get_users(vec![1, 2, 3]);
+/// let more = get_users(vec![2, 3, 4]); // Loads only user 4
+/// ```
+#[proc_macro_attribute]
+pub fn cache_batch(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attrs = parse_attributes(attr);
+    let input = parse_macro_input!(item as ItemFn);
+    let vis = &input.vis;
+    let ident = &input.sig.ident;
+    let block = &input.block;
+
+    let is_thread_local = attrs.scope.to_string().contains("ThreadLocal");
+    let (keys_arg, value_type) = match validate_batch_function(&input.sig, &attrs, is_thread_local)
+    {
+        Ok(parts) => parts,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let keys_pat = &keys_arg.pat;
+    let keys_type = &keys_arg.ty;
+    let ret_type = &input.sig.output;
+
+    // The body runs as the loader, on the missing keys only; the function itself
+    // receives the requested keys under a plain name
+    let keys_ident = match keys_pat.as_ref() {
+        syn::Pat::Ident(pat_ident) if pat_ident.subpat.is_none() => pat_ident.ident.clone(),
+        _ => format_ident!("__keys"),
+    };
+    let mut sig = input.sig.clone();
+    if let Some(FnArg::Typed(arg)) = sig.inputs.first_mut() {
+        *arg.pat = syn::parse_quote! { #keys_ident };
+    }
+
+    let cache_ident = format_ident!("BATCH_CACHE_{}", ident.to_string().to_uppercase());
+    let order_ident = format_ident!("BATCH_ORDER_{}", ident.to_string().to_uppercase());
+    let stats_ident = format_ident!("BATCH_STATS_{}", ident.to_string().to_uppercase());
+    let fn_name_str = attrs
+        .custom_name
+        .clone()
+        .unwrap_or_else(|| ident.to_string());
+    let value_type = quote! { #value_type };
+
+    let (backend, backend_variant) = if is_thread_local {
+        let thread_local_backend = generate_thread_local_backend(
+            &cache_ident,
+            &order_ident,
+            &value_type,
+            &attrs.limit,
+            &attrs.max_memory,
+            &attrs.policy,
+            &attrs.ttl,
+            &attrs.frequency_weight,
+            &attrs.on_evict,
+            &attrs.strategy,
+        );
+        (
+            quote! {
+                use ::std::collections::VecDeque;
+                use ::std::cell::RefCell;
+                use ::cachelito_core::{CacheBackend, CacheEntry, ThreadLocalCache};
+
+                #thread_local_backend
+            },
+            quote! { ThreadLocal },
+        )
+    } else {
+        let global_backend = generate_global_backend(
+            &cache_ident,
+            &order_ident,
+            &stats_ident,
+            &value_type,
+            &attrs.limit,
+            &attrs.max_memory,
+            &attrs.policy,
+            &attrs.ttl,
+            &attrs.frequency_weight,
+            &fn_name_str,
+            &[],
+            &attrs,
+        );
+        (
+            quote! {
+                use ::std::collections::VecDeque;
+                use ::cachelito_core::{CacheBackend, CacheEntry, GlobalCache};
+
+                #global_backend
+            },
+            quote! { Global },
+        )
+    };
+
+    let get_many = if has_max_memory(&attrs.max_memory) {
+        quote! { get_many_with_memory }
+    } else {
+        quote! { get_many }
+    };
+
+    let expanded = quote! {
+        #vis #sig {
+            let __backend = { #backend };
+
+            let ::cachelito_core::CacheBackend::#backend_variant(__cache) = &__backend else {
+                unreachable!("cache backend does not match the configured scope")
+            };
 
+            let __values = __cache.#get_many(&#keys_ident, |#keys_pat: #keys_type| #ret_type #block);
+            #keys_ident
+                .into_iter()
+                .zip(__values)
+                .filter_map(|(__key, __value)| __value.map(|__value| (__key, __value)))
+                .collect()
         }
     };
 
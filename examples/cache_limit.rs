@@ -7,7 +7,7 @@ use cachelito::cache;
 use std::cell::RefCell;
 // Counter to verify how many times the function executes
 thread_local! {
-    static EXEC_COUNT: RefCell<usize> = RefCell::new(0);
+    static EXEC_COUNT: RefCell<usize> = const { RefCell::new(0) };
 }
 /// Simulates a slow addition operation with a cache limit of 2 entries.
 ///
@@ -16,6 +16,7 @@
 use cachelito::cache;
 
 // Example 1: Only cache non-empty vectors
+#[allow(clippy::ptr_arg)]
 fn should_cache_non_empty(_key: &String, result: &Vec<String>) -> bool {
     !result.is_empty()
 }
@@ -34,6 +35,7 @@ fn fetch_items(category: String) -> Vec<String> {
 }
 
 // Example 2: Only cache successful HTTP-like responses
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct Response {
     status: u16,
@@ -70,6 +72,7 @@ fn cache_some(_key: &String, result: &Option<User>) -> bool {
     result.is_some()
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct User {
     id: u32,
@@ -92,6 +95,7 @@ fn find_user(id: u32) -> Option<User> {
 }
 
 // Example 4: Only cache large results
+#[allow(clippy::ptr_arg)]
 fn cache_if_large(_key: &String, data: &Vec<u8>) -> bool {
     data.len() > 1024 // Only cache results larger than 1KB
 }
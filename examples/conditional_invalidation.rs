@@ -94,7 +94,7 @@ fn main() {
     println!("\nInvalidating all products with 3-digit IDs (100-999)...");
     invalidate_with("get_product", |key: &str| {
         if let Ok(id) = key.parse::<u64>() {
-            id >= 100 && id < 1000
+            (100..1000).contains(&id)
         } else {
             false
         }
@@ -8,6 +8,7 @@ use cachelito::DefaultCacheableKey;
 ///
 /// This type demonstrates using the default cache key implementation,
 /// which generates keys based on the `Debug` trait representation.
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct Product {
     /// Unique product identifier
@@ -3,7 +3,7 @@ use std::cell::RefCell;
 
 // Counter to verify how many times the function executes
 thread_local! {
-    static EXEC_COUNT: RefCell<usize> = RefCell::new(0);
+    static EXEC_COUNT: RefCell<usize> = const { RefCell::new(0) };
 }
 
 #[cache(limit = 2, policy = "fifo")]
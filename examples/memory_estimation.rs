@@ -1,5 +1,6 @@
 use cachelito::cache;
 use cachelito::MemoryEstimator;
+#[allow(dead_code)]
 #[derive(Clone, Debug)]
 struct LargeData {
     id: u64,
@@ -8,7 +9,9 @@ struct LargeData {
 }
 impl MemoryEstimator for LargeData {
     fn estimate_memory(&self) -> usize {
-        std::mem::size_of::<Self>() + self.payload.capacity() * std::mem::size_of::<u8>() + self.metadata.capacity()
+        std::mem::size_of::<Self>()
+            + self.payload.capacity() * std::mem::size_of::<u8>()
+            + self.metadata.capacity()
     }
 }
 #[cache(limit = 10, policy = "lru")]
@@ -8,6 +8,7 @@ use cachelito::cache;
 use std::thread;
 use std::time::{Duration, Instant};
 
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct User {
     id: u64,
@@ -56,6 +57,7 @@ fn get_product(product_id: u64) -> String {
     format!("Product {}", product_id)
 }
 
+#[allow(clippy::ptr_arg)]
 fn is_admin_key(key: &String, _value: &String) -> bool {
     // Invalidate if key contains admin (for security)
     // Note: keys are stored with quotes, so we use contains
@@ -1,12 +1,12 @@
 use cachelito::cache;
 use std::sync::atomic::{AtomicU32, Ordering};
 
-/// Example comparing FIFO, LRU, and LFU eviction policies
-///
-/// This example demonstrates how different eviction policies behave
-/// with the same access pattern, helping you choose the right one.
-///
-/// Run with: cargo run --example policy_comparison
+// Example comparing FIFO, LRU, and LFU eviction policies
+//
+// This example demonstrates how different eviction policies behave
+// with the same access pattern, helping you choose the right one.
+//
+// Run with: cargo run --example policy_comparison
 
 static FIFO_CALLS: AtomicU32 = AtomicU32::new(0);
 static LRU_CALLS: AtomicU32 = AtomicU32::new(0);
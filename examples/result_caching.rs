@@ -23,7 +23,7 @@ use cachelito::cache;
 #[cache]
 fn risky_operation(x: u32) -> Result<u32, String> {
     println!("Running risky operation for {}", x);
-    if x.is_multiple_of(2) {
+    if x % 2 == 0 {
         Ok(x * 2)
     } else {
         Err(format!("Odd number: {}", x))
@@ -0,0 +1,72 @@
+// Example comparing a single-lock global cache with a sharded one
+// LRU hits reorder the eviction queue, so every hit takes the cache's order lock
+
+use cachelito::cache;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const THREADS: u64 = 8;
+const READS_PER_THREAD: u64 = 200_000;
+const KEYS: u64 = 500;
+
+#[cache(scope = "global", limit = 1000, policy = "lru")]
+fn single_lock(id: u64) -> u64 {
+    id * 2
+}
+
+// Same cache split into 16 partitions, each holding 1000 / 16 entries
+#[cache(scope = "global", limit = 1000, policy = "lru", shards = 16)]
+fn sharded(id: u64) -> u64 {
+    id * 2
+}
+
+fn run(name: &str, lookup: fn(u64) -> u64) -> Duration {
+    // Warm up the cache so that the measured reads are hits
+    for id in 0..KEYS {
+        lookup(id);
+    }
+
+    let start = Instant::now();
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            thread::spawn(move || {
+                for i in 0..READS_PER_THREAD {
+                    let id = (i * 31 + t * 7) % KEYS;
+                    assert_eq!(lookup(id), id * 2);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let elapsed = start.elapsed();
+    println!(
+        "  {:<12} {:>10.0} reads/s",
+        name,
+        (THREADS * READS_PER_THREAD) as f64 / elapsed.as_secs_f64()
+    );
+    elapsed
+}
+
+fn main() {
+    println!("=== Sharded Global Cache Demo ===\n");
+    println!(
+        "{} threads, {} LRU hits each over {} keys\n",
+        THREADS, READS_PER_THREAD, KEYS
+    );
+
+    let single = run("single lock", single_lock);
+    let sharded_time = run("16 shards", sharded);
+
+    println!(
+        "\n  Speedup: {:.1}x",
+        single.as_secs_f64() / sharded_time.as_secs_f64()
+    );
+    println!(
+        "  Entries: {} / {}",
+        single_lock_cache().len(),
+        sharded_cache().len()
+    );
+}
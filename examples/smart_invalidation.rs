@@ -2,6 +2,7 @@ use cachelito::cache;
 use cachelito::{invalidate_by_dependency, invalidate_by_event, invalidate_by_tag};
 
 /// User profile data
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct UserProfile {
     id: u64,
@@ -1,11 +1,11 @@
-/// Example demonstrating that thread-local caches DO track statistics internally.
-///
-/// This example shows that ThreadLocalCache has a `stats` field and tracks
-/// hit/miss rates, but these statistics are only accessible when you have
-/// direct access to the cache object (like in tests), not through stats_registry.
-///
-/// **Key Takeaway**: Thread-local statistics exist and work, but are not
-/// accessible via the public `stats_registry` API due to architectural limitations.
+//! Example demonstrating that thread-local caches DO track statistics internally.
+//!
+//! This example shows that ThreadLocalCache has a `stats` field and tracks
+//! hit/miss rates, but these statistics are only accessible when you have
+//! direct access to the cache object (like in tests), not through stats_registry.
+//!
+//! **Key Takeaway**: Thread-local statistics exist and work, but are not
+//! accessible via the public `stats_registry` API due to architectural limitations.
 
 #[cfg(feature = "stats")]
 fn main() {
@@ -18,11 +18,19 @@ fn main() {
     // Define thread-local storage
     thread_local! {
         static CACHE: RefCell<HashMap<String, CacheEntry<i32>>> = RefCell::new(HashMap::new());
-        static ORDER: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+        static ORDER: RefCell<VecDeque<String>> = const { RefCell::new(VecDeque::new()) };
     }
 
     // Create a thread-local cache
-    let cache = ThreadLocalCache::new(&CACHE, &ORDER, Some(5), None, EvictionPolicy::LRU, None, None);
+    let cache = ThreadLocalCache::new(
+        &CACHE,
+        &ORDER,
+        Some(5),
+        None,
+        EvictionPolicy::LRU,
+        None,
+        None,
+    );
 
     println!("Making cache calls...\n");
 
@@ -91,11 +99,18 @@ fn main() {
     let handle = std::thread::spawn(|| {
         thread_local! {
             static CACHE2: RefCell<HashMap<String, CacheEntry<i32>>> = RefCell::new(HashMap::new());
-            static ORDER2: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+            static ORDER2: RefCell<VecDeque<String>> = const { RefCell::new(VecDeque::new()) };
         }
 
-        let cache2 =
-            ThreadLocalCache::new(&CACHE2, &ORDER2, None, None, EvictionPolicy::FIFO, None, None);
+        let cache2 = ThreadLocalCache::new(
+            &CACHE2,
+            &ORDER2,
+            None,
+            None,
+            EvictionPolicy::FIFO,
+            None,
+            None,
+        );
 
         cache2.insert("thread2_key", 999);
         cache2.get("thread2_key");
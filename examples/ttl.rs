@@ -11,7 +11,7 @@ use std::time::Duration;
 
 // Counter to verify how many times the function executes
 thread_local! {
-    static EXEC_COUNT: RefCell<usize> = RefCell::new(0);
+    static EXEC_COUNT: RefCell<usize> = const { RefCell::new(0) };
 }
 
 /// Simulates fetching user data with a TTL of 2 seconds.
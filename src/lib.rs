@@ -9,7 +9,7 @@
 //! - **Global scope by default**: Cache shared across all threads (use `scope = "thread"` for thread-local)
 //! - **High-performance synchronization**: Uses `parking_lot::RwLock` for global caches
 //! - **Thread-local option**: Optional thread-local storage for maximum performance
-//! - **Multiple eviction policies**: FIFO, LRU, LFU, ARC, Random, and TLRU
+//! - **Multiple eviction policies**: FIFO, LRU, LFU, ARC, Random, TLRU, GDSF, SIEVE, and S3-FIFO
 //! - **TLRU with frequency_weight**: Fine-tune recency vs frequency balance (v0.15.0)
 //! - **Flexible key generation**: Supports custom cache key implementations
 //! - **Result-aware**: Intelligently caches only successful `Result::Ok` values
@@ -127,4 +127,4 @@
 //! ```
 
 pub use cachelito_core::*;
-pub use cachelito_macros::cache;
+pub use cachelito_macros::{cache, cache_batch};
@@ -0,0 +1,78 @@
+//! Integration tests for batch loading with `#[cache_batch]`
+
+use cachelito::cache_batch;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static LOADED: Mutex<Vec<Vec<u64>>> = Mutex::new(Vec::new());
+static THREAD_CALLS: AtomicUsize = AtomicUsize::new(0);
+static LIMITED_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cache_batch]
+fn get_users(ids: Vec<u64>) -> HashMap<u64, String> {
+    LOADED.lock().unwrap().push(ids.clone());
+    // User 0 does not exist
+    ids.into_iter()
+        .filter(|id| *id != 0)
+        .map(|id| (id, format!("user {}", id)))
+        .collect()
+}
+
+#[cache_batch(scope = "thread")]
+fn get_scores(names: Vec<String>) -> HashMap<String, usize> {
+    THREAD_CALLS.fetch_add(1, Ordering::SeqCst);
+    names
+        .into_iter()
+        .map(|name| (name.clone(), name.len()))
+        .collect()
+}
+
+#[cache_batch(limit = 2, policy = "lru")]
+fn get_squares(mut numbers: Vec<u32>) -> HashMap<u32, u32> {
+    LIMITED_CALLS.fetch_add(1, Ordering::SeqCst);
+    numbers.sort_unstable();
+    numbers.into_iter().map(|n| (n, n * n)).collect()
+}
+
+#[test]
+fn test_loader_receives_only_missing_keys() {
+    let users = get_users(vec![1, 2]);
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[&1], "user 1");
+
+    let users = get_users(vec![2, 3, 3, 0]);
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[&3], "user 3");
+    assert!(!users.contains_key(&0));
+
+    // All cached now, except the missing user which is retried
+    let users = get_users(vec![1, 2, 3]);
+    assert_eq!(users.len(), 3);
+    assert_eq!(
+        *LOADED.lock().unwrap(),
+        vec![vec![1, 2], vec![3, 0]],
+        "the loader must only see uncached keys, once each"
+    );
+}
+
+#[test]
+fn test_thread_scope() {
+    let scores = get_scores(vec!["ann".to_string(), "bob".to_string()]);
+    assert_eq!(scores["ann"], 3);
+    let scores = get_scores(vec!["bob".to_string()]);
+    assert_eq!(scores["bob"], 3);
+    assert_eq!(THREAD_CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_limit_applies_per_key() {
+    assert_eq!(get_squares(vec![1, 2, 3]).len(), 3);
+    assert_eq!(LIMITED_CALLS.load(Ordering::SeqCst), 1);
+
+    // Only the last two keys fit in the cache
+    assert_eq!(get_squares(vec![2, 3])[&3], 9);
+    assert_eq!(LIMITED_CALLS.load(Ordering::SeqCst), 1);
+    assert_eq!(get_squares(vec![1])[&1], 1);
+    assert_eq!(LIMITED_CALLS.load(Ordering::SeqCst), 2);
+}
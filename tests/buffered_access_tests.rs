@@ -0,0 +1,88 @@
+//! Integration tests for global caches recording hits in an access buffer (`buffered_access = true`)
+
+use cachelito::cache;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+static LRU_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cache(scope = "global", limit = 3, policy = "lru", buffered_access = true)]
+fn lru(x: u64) -> u64 {
+    LRU_CALLS.fetch_add(1, Ordering::SeqCst);
+    x * 10
+}
+
+#[cache(scope = "global", limit = 2, policy = "lfu", buffered_access = true)]
+fn lfu(x: u64) -> u64 {
+    x + 1
+}
+
+#[cache(
+    scope = "global",
+    limit = 4,
+    policy = "lru",
+    shards = 2,
+    buffered_access = true
+)]
+fn sharded(x: u64) -> u64 {
+    x * 2
+}
+
+#[cache(scope = "global", limit = 32, policy = "tlru", buffered_access = true)]
+fn contended(x: u64) -> u64 {
+    x * 3
+}
+
+#[test]
+fn test_buffered_hits_reach_lru_order() {
+    for x in 1..=3 {
+        assert_eq!(lru(x), x * 10);
+    }
+    // The hit is only buffered, and replayed before the next insertion evicts
+    assert_eq!(lru(1), 10);
+    assert_eq!(lru(4), 40);
+
+    assert_eq!(lru_cache().len(), 3);
+    assert_eq!(lru_cache().get(&1), Some(10));
+    assert_eq!(lru_cache().get(&2), None);
+    assert_eq!(LRU_CALLS.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn test_buffered_hits_reach_lfu_frequencies() {
+    lfu(1);
+    lfu(2);
+    for _ in 0..5 {
+        lfu(1);
+    }
+    lfu(3);
+
+    assert_eq!(lfu_cache().len(), 2);
+    assert_eq!(lfu_cache().get(&1), Some(2));
+    assert_eq!(lfu_cache().get(&2), None);
+}
+
+#[test]
+fn test_sharded_cache_with_buffered_access() {
+    for round in 0..3 {
+        for x in 0..20 {
+            assert_eq!(sharded(x), x * 2, "round {}", round);
+        }
+    }
+    assert_eq!(sharded_cache().len(), 4);
+}
+
+#[test]
+fn test_concurrent_buffered_hits() {
+    thread::scope(|scope| {
+        for t in 0..4 {
+            scope.spawn(move || {
+                for i in 0..500u64 {
+                    let x = (i * (t + 1)) % 48;
+                    assert_eq!(contended(x), x * 3);
+                }
+            });
+        }
+    });
+    assert!(contended_cache().len() <= 32);
+}
@@ -0,0 +1,127 @@
+//! Integration tests for the per-function cache handle (`<fn>_cache()`)
+
+use cachelito::cache;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ADD_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cache(scope = "global", limit = 10)]
+fn add(a: u32, b: u32) -> u32 {
+    ADD_CALLS.fetch_add(1, Ordering::SeqCst);
+    a + b
+}
+
+#[cache(scope = "global")]
+fn greet(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[cache(scope = "global")]
+fn cube(x: u64) -> u64 {
+    x * x * x
+}
+
+#[cache(scope = "thread")]
+fn thread_square(x: u64) -> u64 {
+    x * x
+}
+
+#[cache(scope = "global", handle = false)]
+fn without_handle(x: u64) -> u64 {
+    x + 1
+}
+
+#[cache(scope = "global", handle = false)]
+fn shout_without_handle(text: &str, times: usize) -> String {
+    text.to_uppercase().repeat(times)
+}
+
+#[derive(Debug, Clone)]
+struct Multiplier {
+    factor: u32,
+}
+
+impl cachelito_core::DefaultCacheableKey for Multiplier {}
+
+impl Multiplier {
+    #[cache(scope = "global")]
+    fn apply(&self, x: u32) -> u32 {
+        self.factor * x
+    }
+}
+
+#[test]
+fn test_handle_get_and_invalidate() {
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(add(2, 3), 5);
+    assert_eq!(ADD_CALLS.load(Ordering::SeqCst), 2);
+
+    let handle = add_cache();
+    assert_eq!(handle.get(&(1, 2)), Some(3));
+    assert_eq!(handle.len(), 2);
+
+    // Only the targeted entry is removed
+    assert!(handle.invalidate(&(1, 2)));
+    assert_eq!(handle.get(&(1, 2)), None);
+    assert_eq!(handle.get(&(2, 3)), Some(5));
+
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(ADD_CALLS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_handle_insert_is_used_by_function() {
+    greet_cache().insert(&"world", "Hi there".to_string());
+    assert_eq!(greet("world"), "Hi there");
+    assert_eq!(greet("rust"), "Hello, rust!");
+    assert_eq!(greet_cache().get(&"rust"), Some("Hello, rust!".to_string()));
+
+    greet_cache().clear();
+    assert!(greet_cache().is_empty());
+    assert_eq!(greet("world"), "Hello, world!");
+}
+
+#[test]
+fn test_handle_thread_scope() {
+    thread_square(3);
+    assert_eq!(thread_square_cache().get(&3), Some(9));
+
+    std::thread::spawn(|| {
+        // Other threads have their own cache
+        assert!(thread_square_cache().is_empty());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn test_handle_for_method() {
+    let double = Multiplier { factor: 2 };
+    let triple = Multiplier { factor: 3 };
+    assert_eq!(double.apply(5), 10);
+    assert_eq!(triple.apply(5), 15);
+
+    assert_eq!(Multiplier::apply_cache().get(&(&double, 5)), Some(10));
+    assert!(Multiplier::apply_cache().invalidate(&(&triple, 5)));
+    assert_eq!(Multiplier::apply_cache().len(), 1);
+}
+
+#[test]
+fn test_handle_disabled() {
+    assert_eq!(without_handle(1), 2);
+    assert_eq!(without_handle(1), 2);
+    assert_eq!(shout_without_handle("hi", 2), "HIHI");
+    assert_eq!(shout_without_handle("hi", 2), "HIHI");
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_handle_stats() {
+    cube(2);
+    cube(2);
+
+    let handle = cube_cache();
+    let stats = handle.stats().expect("global caches keep statistics");
+    assert!(stats.hits() >= 1);
+    assert!(thread_square_cache().stats().is_none());
+}
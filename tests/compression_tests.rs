@@ -0,0 +1,108 @@
+//! Integration tests for compressed values (`compress = "lz4" | "zstd"`)
+#![cfg(feature = "lz4")]
+
+use cachelito::cache;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DOCUMENT_CALLS: AtomicUsize = AtomicUsize::new(0);
+static FETCH_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn json(id: u64) -> String {
+    format!(
+        "{{\"id\": {}, \"payload\": \"{}\"}}",
+        id,
+        "abcdef".repeat(2000)
+    )
+}
+
+#[cache(scope = "global", compress = "lz4")]
+fn document(id: u64) -> String {
+    DOCUMENT_CALLS.fetch_add(1, Ordering::SeqCst);
+    if id == 0 {
+        "tiny".to_string()
+    } else {
+        json(id)
+    }
+}
+
+#[cache(
+    scope = "global",
+    max_memory = "64KB",
+    compress = "lz4",
+    compress_min_size = "2KB"
+)]
+fn sized_document(id: u64) -> String {
+    json(id)
+}
+
+#[cache(scope = "thread", compress = "lz4")]
+fn fetch(id: u64) -> Result<Vec<u8>, String> {
+    FETCH_CALLS.fetch_add(1, Ordering::SeqCst);
+    if id == 0 {
+        Err("not found".to_string())
+    } else {
+        Ok(json(id).into_bytes())
+    }
+}
+
+#[test]
+fn test_values_are_compressed_and_served_decompressed() {
+    assert_eq!(document(1), json(1));
+    assert_eq!(document(1), json(1));
+    assert_eq!(DOCUMENT_CALLS.load(Ordering::SeqCst), 1);
+
+    let handle = document_cache();
+    let stored = handle.get(&1).unwrap();
+    assert!(stored.is_compressed());
+    let (original, compressed) = stored.compressed_sizes().unwrap();
+    assert_eq!(original, json(1).len());
+    assert!(compressed < original / 4);
+
+    // Small values are stored as they are
+    assert_eq!(document(0), "tiny");
+    assert!(!handle.get(&0).unwrap().is_compressed());
+    assert_eq!(document(0), "tiny");
+    assert_eq!(DOCUMENT_CALLS.load(Ordering::SeqCst), 2);
+
+    let stats = handle.stats().unwrap();
+    assert!(stats.compression_ratio() > 4.0);
+    assert_eq!(stats.uncompressed_bytes(), original as u64);
+}
+
+#[test]
+fn test_max_memory_counts_compressed_size() {
+    // Uncompressed, 20 documents would take about 240KB
+    for id in 1..=20 {
+        assert_eq!(sized_document(id), json(id));
+    }
+    let handle = sized_document_cache();
+    assert_eq!(handle.len(), 20);
+    assert!(handle.memory_usage() <= 64 * 1024);
+}
+
+#[test]
+fn test_errors_are_not_cached_nor_compressed() {
+    assert_eq!(fetch(0), Err("not found".to_string()));
+    assert_eq!(fetch(0), Err("not found".to_string()));
+    assert_eq!(FETCH_CALLS.load(Ordering::SeqCst), 2);
+
+    assert_eq!(fetch(7), Ok(json(7).into_bytes()));
+    assert_eq!(fetch(7), Ok(json(7).into_bytes()));
+    assert_eq!(FETCH_CALLS.load(Ordering::SeqCst), 3);
+    assert!(fetch_cache().get(&7).unwrap().is_compressed());
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_codec() {
+    #[cache(compress = "zstd", compress_min_size = 0)]
+    fn label(id: u64) -> Option<String> {
+        (id > 0).then(|| json(id))
+    }
+
+    assert_eq!(label(3), Some(json(3)));
+    assert_eq!(label(3), Some(json(3)));
+    assert_eq!(label(0), None);
+    assert!(label_cache().get(&3).unwrap().is_compressed());
+    assert!(!label_cache().get(&0).unwrap().is_compressed());
+}
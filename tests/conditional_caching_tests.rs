@@ -7,6 +7,7 @@ use cachelito::cache;
 use std::sync::atomic::{AtomicU32, Ordering};
 
 // Test 1: Only cache non-empty vectors
+#[allow(clippy::ptr_arg)]
 fn should_cache_non_empty(_key: &String, result: &Vec<i32>) -> bool {
     !result.is_empty()
 }
@@ -29,11 +30,11 @@ fn test_cache_if_empty_not_cached() {
 
     // Empty results should not be cached
     let result1 = get_numbers(0);
-    assert_eq!(result1, vec![]);
+    assert_eq!(result1, Vec::<i32>::new());
     assert_eq!(CALL_COUNT_1.load(Ordering::SeqCst), 1);
 
     let result2 = get_numbers(0);
-    assert_eq!(result2, vec![]);
+    assert_eq!(result2, Vec::<i32>::new());
     // Should execute again (not cached)
     assert_eq!(CALL_COUNT_1.load(Ordering::SeqCst), 2);
 
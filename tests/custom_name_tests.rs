@@ -1,4 +1,4 @@
-/// Integration tests for custom cache names feature
+//! Integration tests for custom cache names feature
 
 #[cfg(feature = "stats")]
 #[cfg(test)]
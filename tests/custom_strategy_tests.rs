@@ -0,0 +1,119 @@
+//! Integration tests for custom eviction strategies (`policy = path::to::Strategy`)
+
+use cachelito::cache;
+use cachelito_core::{CacheEntry, EvictionCandidates, EvictionStrategy, RemovalCause};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Offer {
+    id: u64,
+    priority: u8,
+}
+
+/// Evicts the offer with the lowest business priority
+struct ByPriority;
+
+impl EvictionStrategy<Offer> for ByPriority {
+    fn choose_victim(&self, candidates: &mut EvictionCandidates<'_, Offer>) -> Option<usize> {
+        candidates.lowest_score(|entry, _| entry.value.priority as f64)
+    }
+
+    fn updates_on_access(&self) -> bool {
+        false
+    }
+}
+
+#[cache(limit = 3, policy = ByPriority)]
+fn offer(id: u64) -> Offer {
+    let priority = match id {
+        1 => 9,
+        2 => 1,
+        3 => 5,
+        _ => 7,
+    };
+    Offer { id, priority }
+}
+
+/// LRU recording the hooks it is called with
+struct Tracker {
+    inserts: AtomicUsize,
+    accesses: AtomicUsize,
+    removed: Mutex<Vec<(String, RemovalCause)>>,
+}
+
+impl<R> EvictionStrategy<R> for Tracker {
+    fn on_insert(&self, _key: &str, _entry: &mut CacheEntry<R>) {
+        self.inserts.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_access(&self, _key: &str, _entry: &mut CacheEntry<R>) {
+        self.accesses.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_remove(&self, key: &str, _value: &R, cause: RemovalCause) {
+        self.removed.lock().unwrap().push((key.to_string(), cause));
+    }
+
+    fn choose_victim(&self, _candidates: &mut EvictionCandidates<'_, R>) -> Option<usize> {
+        Some(0)
+    }
+
+    fn promotes_on_access(&self) -> bool {
+        true
+    }
+}
+
+static TRACKER: Tracker = Tracker {
+    inserts: AtomicUsize::new(0),
+    accesses: AtomicUsize::new(0),
+    removed: Mutex::new(Vec::new()),
+};
+
+#[cache(scope = "thread", limit = 2, policy = TRACKER)]
+fn square(x: u64) -> u64 {
+    x * x
+}
+
+#[test]
+fn test_strategy_chooses_the_victim() {
+    offer(1);
+    offer(2);
+    offer(3);
+    // Full: the offer with priority 1 makes room, although it is not the oldest
+    offer(4);
+
+    let handle = offer_cache();
+    assert_eq!(handle.len(), 3);
+    assert_eq!(handle.get(&2), None);
+    assert_eq!(handle.get(&1), Some(Offer { id: 1, priority: 9 }));
+
+    offer(5);
+    assert_eq!(handle.get(&3), None);
+    assert!(handle.get(&4).is_some() && handle.get(&5).is_some());
+}
+
+#[test]
+fn test_strategy_hooks() {
+    square(1);
+    square(2);
+    assert_eq!(square(1), 1); // Hit, promotes 1
+    square(3); // Evicts 2
+
+    assert_eq!(TRACKER.inserts.load(Ordering::SeqCst), 3);
+    assert_eq!(TRACKER.accesses.load(Ordering::SeqCst), 1);
+    assert_eq!(square_cache().get(&2), None);
+
+    // Replacing a value is an insertion, not a removal
+    square_cache().insert(&3, 10);
+    assert_eq!(TRACKER.inserts.load(Ordering::SeqCst), 4);
+    assert!(square_cache().invalidate(&1));
+
+    assert_eq!(
+        *TRACKER.removed.lock().unwrap(),
+        vec![
+            ("2".to_string(), RemovalCause::Capacity),
+            ("1".to_string(), RemovalCause::Explicit),
+        ]
+    );
+}
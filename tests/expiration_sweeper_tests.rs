@@ -0,0 +1,36 @@
+//! Integration tests for the background `ExpirationSweeper`
+
+use cachelito::cache;
+use cachelito_core::ExpirationSweeper;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cache(scope = "global", ttl = 1)]
+fn swept(x: u64) -> u64 {
+    x
+}
+
+#[test]
+fn test_sweeper_purges_keys_never_read_again() {
+    let sweeper = ExpirationSweeper::start(Duration::from_millis(100));
+    for x in 0..10 {
+        swept(x);
+    }
+    assert_eq!(swept_cache().len(), 10);
+
+    let started = Instant::now();
+    while !swept_cache().is_empty() {
+        assert!(
+            started.elapsed() < Duration::from_secs(10),
+            "expired entries were not swept"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+    assert!(started.elapsed() >= Duration::from_millis(900));
+
+    sweeper.stop();
+    swept(1);
+    thread::sleep(Duration::from_millis(1300));
+    // Stopped: the expired entry stays until read again
+    assert_eq!(swept_cache().len(), 1);
+}
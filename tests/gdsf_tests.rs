@@ -0,0 +1,76 @@
+use cachelito::cache;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Test GDSF eviction - a huge cold entry makes room before a small hot one
+#[test]
+fn test_gdsf_evicts_large_entries_first() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[cache(max_memory = "20KB", policy = "gdsf")]
+    fn payload(len: usize) -> Vec<u8> {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        // Same compute cost for every entry
+        thread::sleep(Duration::from_millis(5));
+        vec![0; len]
+    }
+
+    payload(100);
+    payload(15_000);
+    // Over 20KB: the 15KB entry has the lowest frequency × cost / size
+    payload(8_000);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+
+    payload(100); // Cache hit
+    payload(8_000); // Cache hit
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+
+    payload(15_000); // Recomputed
+    assert_eq!(CALLS.load(Ordering::SeqCst), 4);
+}
+
+/// Test GDSF eviction - entries that were slow to compute are kept longer
+#[test]
+fn test_gdsf_keeps_expensive_entries() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[cache(limit = 2, policy = "gdsf")]
+    fn compute(n: u64) -> u64 {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        if n == 0 {
+            thread::sleep(Duration::from_millis(20));
+        }
+        n * 2
+    }
+
+    assert_eq!(compute(0), 0); // Slow
+    assert_eq!(compute(1), 2); // Fast
+                               // Full: one of the fast entries is evicted, although the slow one is older
+    assert_eq!(compute(2), 4);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+
+    assert_eq!(compute(0), 0); // Cache hit
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+}
+
+/// Test that GDSF works with the thread-local scope
+#[test]
+fn test_gdsf_thread_local() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[cache(scope = "thread", limit = 2, policy = "gdsf")]
+    fn compute(n: u64) -> u64 {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        if n == 0 {
+            thread::sleep(Duration::from_millis(20));
+        }
+        n + 1
+    }
+
+    compute(0);
+    compute(1);
+    compute(2); // Evicts a fast entry
+    compute(0); // Cache hit
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,82 @@
+//! Integration tests for caching generic functions
+
+use cachelito::cache;
+use cachelito_core::{invalidate_cache, invalidate_with, CacheableKey};
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PARSE_CALLS: AtomicUsize = AtomicUsize::new(0);
+static DESCRIBE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cache(scope = "global", limit = 10)]
+fn parse_value<T: FromStr + Clone + Send + Sync>(input: String) -> Option<T> {
+    PARSE_CALLS.fetch_add(1, Ordering::SeqCst);
+    input.parse().ok()
+}
+
+#[cache(scope = "global", name = "generic_describe", tags = ["generic"])]
+fn describe<T>(value: T) -> String
+where
+    T: CacheableKey + Debug,
+{
+    DESCRIBE_CALLS.fetch_add(1, Ordering::SeqCst);
+    format!("{:?}", value)
+}
+
+#[derive(Debug, Clone)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[cache(scope = "global", handle = false)]
+fn wrap<T: CacheableKey + Clone + Send + Sync>(inner: T) -> Wrapper<T> {
+    Wrapper { inner }
+}
+
+#[test]
+fn test_one_cache_per_monomorphization() {
+    assert_eq!(parse_value::<u8>("42".to_string()), Some(42));
+    assert_eq!(parse_value::<u8>("42".to_string()), Some(42));
+    assert_eq!(PARSE_CALLS.load(Ordering::SeqCst), 1);
+
+    // Same key, different type parameter: a separate cache
+    assert_eq!(parse_value::<f64>("42".to_string()), Some(42.0));
+    assert_eq!(parse_value::<f64>("42".to_string()), Some(42.0));
+    assert_eq!(PARSE_CALLS.load(Ordering::SeqCst), 2);
+
+    // Out of range for u8, but cached as `None`
+    assert_eq!(parse_value::<u8>("300".to_string()), None);
+    assert_eq!(parse_value::<u16>("300".to_string()), Some(300));
+
+    assert_eq!(parse_value_cache::<u8>().len(), 2);
+    assert_eq!(parse_value_cache::<f64>().len(), 1);
+    assert_eq!(
+        parse_value_cache::<u8>().get(&"42".to_string()),
+        Some(Some(42))
+    );
+}
+
+#[test]
+fn test_generic_invalidation_covers_all_monomorphizations() {
+    assert_eq!(describe(1u32), "1");
+    assert_eq!(describe("one"), "\"one\"");
+    assert_eq!(describe(1u32), "1");
+    assert_eq!(DESCRIBE_CALLS.load(Ordering::SeqCst), 2);
+
+    assert!(invalidate_with("generic_describe", |key| key == "1"));
+    assert_eq!(describe_cache::<u32>().len(), 0);
+    assert_eq!(describe_cache::<&str>().len(), 1);
+
+    assert!(invalidate_cache("generic_describe"));
+    assert!(describe_cache::<&str>().is_empty());
+
+    assert_eq!(describe("one"), "\"one\"");
+    assert_eq!(DESCRIBE_CALLS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_generic_return_type() {
+    assert_eq!(wrap(5u8).inner, 5);
+    assert_eq!(wrap("five".to_string()).inner, "five");
+}
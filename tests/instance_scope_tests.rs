@@ -0,0 +1,124 @@
+//! Integration tests for instance-scoped method caches (`scope = "instance"`)
+
+use cachelito::{cache, CacheSlot, RemovalCause};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static EVICTED: Mutex<Vec<(String, u64, RemovalCause)>> = Mutex::new(Vec::new());
+
+fn on_evicted(key: &str, value: &u64, cause: RemovalCause) {
+    EVICTED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+/// Neither `Debug` nor `CacheableKey`: `self` is never part of the key
+struct PriceList {
+    markup: u64,
+    calls: AtomicUsize,
+    prices: CacheSlot<u64>,
+    labels: CacheSlot<String>,
+    bounded: CacheSlot<u64>,
+}
+
+impl PriceList {
+    fn new(markup: u64) -> Self {
+        Self {
+            markup,
+            calls: AtomicUsize::new(0),
+            prices: CacheSlot::new(),
+            labels: CacheSlot::new(),
+            bounded: CacheSlot::new(),
+        }
+    }
+
+    #[cache(scope = "instance", cache_field = "prices")]
+    fn price(&self, base: u64) -> u64 {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        base + self.markup
+    }
+
+    #[cache(scope = "instance", cache_field = "labels", handle = false)]
+    fn label(&self, name: &str, base: u64) -> String {
+        format!("{}: {}", name, base + self.markup)
+    }
+
+    #[cache(
+        scope = "instance",
+        cache_field = "bounded",
+        limit = 2,
+        policy = "fifo",
+        on_evict = on_evicted
+    )]
+    fn bounded_price(&self, base: u64) -> u64 {
+        base * self.markup
+    }
+}
+
+#[test]
+fn test_instances_have_separate_caches() {
+    let cheap = PriceList::new(1);
+    let expensive = PriceList::new(100);
+
+    assert_eq!(cheap.price(10), 11);
+    assert_eq!(cheap.price(10), 11);
+    assert_eq!(expensive.price(10), 110);
+    assert_eq!(cheap.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(expensive.calls.load(Ordering::SeqCst), 1);
+
+    assert_eq!(cheap.prices.len(), 1);
+    assert_eq!(expensive.prices.len(), 1);
+}
+
+#[test]
+fn test_cache_is_dropped_with_instance() {
+    let list = PriceList::new(1);
+    list.price(1);
+    list.price(2);
+    assert_eq!(list.prices.len(), 2);
+    drop(list);
+
+    // A new instance starts cold, and so does a clone of the slot
+    let list = PriceList::new(1);
+    assert!(list.prices.is_empty());
+    list.price(1);
+    assert!(list.prices.clone().is_empty());
+}
+
+#[test]
+fn test_instance_handle() {
+    let list = PriceList::new(5);
+    assert_eq!(list.price(1), 6);
+
+    let handle = list.price_cache();
+    assert_eq!(handle.get(&1), Some(6));
+    handle.insert(&2, 42);
+    assert_eq!(list.price(2), 42);
+
+    assert!(handle.invalidate(&1));
+    assert_eq!(list.price(1), 6);
+    assert_eq!(list.calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_instance_without_handle() {
+    let list = PriceList::new(2);
+    assert_eq!(list.label("apple", 3), "apple: 5");
+    assert_eq!(list.label("apple", 3), "apple: 5");
+    assert_eq!(list.labels.len(), 1);
+}
+
+#[test]
+fn test_instance_limit_and_listener() {
+    let list = PriceList::new(3);
+    list.bounded_price(1);
+    list.bounded_price(2);
+    list.bounded_price(3);
+
+    assert_eq!(list.bounded.len(), 2);
+    assert_eq!(
+        *EVICTED.lock().unwrap(),
+        vec![("1".to_string(), 3, RemovalCause::Capacity)]
+    );
+}
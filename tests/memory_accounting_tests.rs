@@ -0,0 +1,88 @@
+//! Integration tests for the running memory total of `max_memory` caches
+
+use cachelito::cache;
+use cachelito_core::MemoryEstimator;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ESTIMATES: AtomicUsize = AtomicUsize::new(0);
+
+/// A value of 100 bytes counting how many times it is measured
+#[derive(Clone)]
+struct Blob(u32);
+
+impl MemoryEstimator for Blob {
+    fn estimate_memory(&self) -> usize {
+        ESTIMATES.fetch_add(1, Ordering::SeqCst);
+        100
+    }
+}
+
+#[cache(scope = "global", max_memory = 1000, policy = "lru")]
+fn blob(id: u32) -> Blob {
+    Blob(id)
+}
+
+#[cache(scope = "global", max_memory = 1000, policy = "fifo", shards = 4)]
+fn sharded(id: u32) -> String {
+    "x".repeat(id as usize % 50)
+}
+
+#[cache(scope = "thread", max_memory = 500, policy = "fifo")]
+fn local(id: u32) -> Vec<u8> {
+    vec![0; id as usize]
+}
+
+#[cache(scope = "global", limit = 10)]
+fn unbounded(id: u32) -> u32 {
+    id
+}
+
+#[test]
+fn test_values_are_measured_once() {
+    for id in 0..200 {
+        assert_eq!(blob(id).0, id);
+    }
+
+    // Filling and evicting never measures the cached values again
+    assert_eq!(ESTIMATES.load(Ordering::SeqCst), 200);
+    assert_eq!(blob_cache().len(), 10);
+    assert_eq!(blob_cache().memory_usage(), 1000);
+
+    assert!(blob_cache().invalidate(&199));
+    assert_eq!(blob_cache().memory_usage(), 900);
+    blob_cache().clear();
+    assert_eq!(blob_cache().memory_usage(), 0);
+}
+
+#[test]
+fn test_sharded_cache_stays_within_max_memory() {
+    for round in 0..3 {
+        for id in 0..200 {
+            assert_eq!(sharded(id).len(), id as usize % 50, "round {}", round);
+        }
+    }
+    let usage = sharded_cache().memory_usage();
+    assert!(usage > 0 && usage <= 1000, "usage {}", usage);
+}
+
+#[test]
+fn test_thread_local_usage_follows_evictions() {
+    let entry_size = vec![0u8; 100].estimate_memory();
+    for id in [100, 100, 100] {
+        local(id);
+    }
+    assert_eq!(local_cache().memory_usage(), entry_size);
+
+    for id in 0..20 {
+        local(100 + id);
+    }
+    assert!(local_cache().memory_usage() <= 500);
+    local_cache().clear();
+    assert_eq!(local_cache().memory_usage(), 0);
+}
+
+#[test]
+fn test_caches_without_max_memory_report_zero() {
+    unbounded(1);
+    assert_eq!(unbounded_cache().memory_usage(), 0);
+}
@@ -0,0 +1,95 @@
+//! Integration tests for the global memory budget shared by `max_memory` caches
+
+use cachelito::{
+    cache, clear_global_memory_budget, global_memory_usage, set_global_memory_budget, MemoryBudget,
+};
+use cachelito_core::MemoryEstimator;
+use std::sync::Mutex;
+
+/// The budget is process-wide: tests changing it must not overlap
+static BUDGET_LOCK: Mutex<()> = Mutex::new(());
+
+/// A value of 100 bytes
+#[derive(Clone)]
+struct Blob(u32);
+
+impl MemoryEstimator for Blob {
+    fn estimate_memory(&self) -> usize {
+        100
+    }
+}
+
+#[cache(scope = "global", max_memory = 10000, policy = "lru")]
+fn hot(id: u32) -> Blob {
+    Blob(id)
+}
+
+#[cache(scope = "global", max_memory = 10000, policy = "fifo")]
+fn cold(id: u32) -> Blob {
+    Blob(id)
+}
+
+#[cache(scope = "global", max_memory = 10000, policy = "fifo", shards = 4)]
+fn sharded(id: u32) -> Blob {
+    Blob(id)
+}
+
+#[test]
+fn test_budget_evicts_from_lowest_hit_rate_cache() {
+    let _guard = BUDGET_LOCK.lock().unwrap();
+    set_global_memory_budget("1000");
+
+    for round in 0..2 {
+        for id in 0..5 {
+            assert_eq!(hot(id).0, id, "round {}", round);
+        }
+    }
+    for id in 0..10 {
+        assert_eq!(cold(id).0, id);
+    }
+
+    // Only the cache that is never hit gave up entries
+    assert_eq!(hot_cache().len(), 5);
+    assert_eq!(cold_cache().len(), 5);
+    assert!(global_memory_usage() <= 1000);
+
+    clear_global_memory_budget();
+    hot_cache().clear();
+    cold_cache().clear();
+}
+
+#[test]
+fn test_budget_applies_to_sharded_caches() {
+    let _guard = BUDGET_LOCK.lock().unwrap();
+    set_global_memory_budget("2KB");
+
+    for id in 0..100 {
+        assert_eq!(sharded(id).0, id);
+    }
+    assert!(sharded_cache().memory_usage() <= 2048);
+    assert!(MemoryBudget::global()
+        .list()
+        .contains(&"sharded".to_string()));
+
+    clear_global_memory_budget();
+    sharded_cache().clear();
+}
+
+#[test]
+fn test_no_eviction_without_budget() {
+    let _guard = BUDGET_LOCK.lock().unwrap();
+    assert_eq!(MemoryBudget::global().limit(), None);
+
+    for id in 100..130 {
+        cold(id);
+    }
+    assert!(cold_cache().len() >= 30);
+    assert!(global_memory_usage() >= 3000);
+    cold_cache().clear();
+}
+
+#[test]
+#[should_panic(expected = "invalid memory budget")]
+fn test_invalid_budget_panics() {
+    set_global_memory_budget("a lot");
+}
@@ -5,7 +5,7 @@ fn test_memory_limit_with_strings() {
     #[cache(max_memory = "1KB", policy = "fifo")]
     fn cached_string(id: u32) -> String {
         // Each string is approximately 500 bytes
-        format!("{}", "A".repeat(500))
+        "A".repeat(500).to_string()
     }
 
     // Fill cache with 2 strings (total ~1KB)
@@ -0,0 +1,160 @@
+//! Integration tests for shrinking the caches under memory pressure
+
+use cachelito::cache;
+use cachelito_core::{shrink_all_caches, MemoryPressure, MemoryPressureMonitor};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Shrinking applies to every cache of the process: tests must not overlap
+static PRESSURE_LOCK: Mutex<()> = Mutex::new(());
+
+#[cache(scope = "global", policy = "lru")]
+fn recent(x: u64) -> u64 {
+    x
+}
+
+#[cache(scope = "global", policy = "fifo", shards = 4)]
+fn sharded(x: u64) -> u64 {
+    x
+}
+
+fn fixture_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "cachelito-pressure-tests-{}-{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write_cgroup(dir: &Path, current: &str, max: &str) {
+    fs::write(dir.join("memory.current"), current).unwrap();
+    fs::write(dir.join("memory.max"), max).unwrap();
+}
+
+#[test]
+fn test_cgroup_above_watermark_shrinks_by_policy() {
+    let _guard = PRESSURE_LOCK.lock().unwrap();
+    recent_cache().clear();
+    for x in 0..20 {
+        recent(x);
+    }
+    // Entries 0 to 4 become the most recently used
+    for x in 0..5 {
+        recent(x);
+    }
+
+    let dir = fixture_dir("cgroup");
+    let pressure = MemoryPressure::cgroup(&dir).with_shrink_fraction(0.25);
+
+    write_cgroup(&dir, "500\n", "1000\n");
+    assert_eq!(pressure.read().unwrap(), 0.5);
+    assert_eq!(pressure.relieve().unwrap(), 0);
+    assert_eq!(recent_cache().len(), 20);
+
+    write_cgroup(&dir, "950\n", "1000\n");
+    assert!(pressure.is_high().unwrap());
+    assert!(pressure.relieve().unwrap() >= 5);
+    assert_eq!(recent_cache().len(), 15);
+    for x in 0..5 {
+        assert!(
+            recent_cache().get(&x).is_some(),
+            "recently used {} was evicted",
+            x
+        );
+    }
+
+    // Without a limit there is no pressure
+    write_cgroup(&dir, "950\n", "max\n");
+    assert_eq!(pressure.relieve().unwrap(), 0);
+    assert_eq!(recent_cache().len(), 15);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_psi_pressure_shrinks_every_shard() {
+    let _guard = PRESSURE_LOCK.lock().unwrap();
+    for x in 0..40 {
+        sharded(x);
+    }
+    let before = sharded_cache().len();
+
+    let path = fixture_dir("psi").join("memory.pressure");
+    fs::write(
+        &path,
+        "some avg10=25.00 avg60=10.00 avg300=2.00 total=123456\n\
+         full avg10=5.00 avg60=1.00 avg300=0.10 total=6543\n",
+    )
+    .unwrap();
+    let pressure = MemoryPressure::psi(&path).with_shrink_fraction(0.5);
+    assert_eq!(pressure.read().unwrap(), 0.25);
+
+    pressure.relieve().unwrap();
+    assert!(
+        sharded_cache().len() <= before / 2,
+        "{} entries left",
+        sharded_cache().len()
+    );
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_missing_files_are_errors() {
+    let pressure = MemoryPressure::cgroup(fixture_dir("missing").join("nope"));
+    assert!(pressure.read().is_err());
+    assert!(pressure.relieve().is_err());
+}
+
+#[test]
+fn test_monitor_shrinks_while_under_pressure() {
+    let _guard = PRESSURE_LOCK.lock().unwrap();
+    recent_cache().clear();
+    for x in 100..200 {
+        recent(x);
+    }
+
+    let dir = fixture_dir("monitor");
+    write_cgroup(&dir, "990", "1000");
+    let pressure = MemoryPressure::cgroup(&dir).with_shrink_fraction(0.5);
+    let monitor = MemoryPressureMonitor::start(pressure, Duration::from_millis(20));
+
+    let started = Instant::now();
+    while recent_cache().len() > 10 {
+        assert!(
+            started.elapsed() < Duration::from_secs(10),
+            "the caches were not shrunk"
+        );
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    // Once the pressure is gone, the caches are left alone
+    write_cgroup(&dir, "100", "1000");
+    thread::sleep(Duration::from_millis(100));
+    recent_cache().clear();
+    recent(1);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(recent_cache().len(), 1);
+
+    monitor.stop();
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_shrink_all_caches_clamps_fraction() {
+    let _guard = PRESSURE_LOCK.lock().unwrap();
+    recent_cache().clear();
+    for x in 0..4 {
+        recent(x);
+    }
+    assert_eq!(shrink_all_caches(0.0), 0);
+    assert_eq!(recent_cache().len(), 4);
+
+    shrink_all_caches(5.0);
+    assert!(recent_cache().is_empty());
+}
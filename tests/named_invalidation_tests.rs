@@ -5,6 +5,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::thread;
 use std::time::{Duration, Instant};
 
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct TimestampedValue {
     value: String,
@@ -43,6 +44,7 @@ fn test_time_based_invalidation_check() {
 }
 
 // Check function based on value content
+#[allow(clippy::ptr_arg)]
 fn is_invalid_value(_key: &String, val: &String) -> bool {
     val.contains("invalid")
 }
@@ -71,6 +73,7 @@ fn test_content_based_invalidation_check() {
 }
 
 // Check function based on key pattern
+#[allow(clippy::ptr_arg)]
 fn is_admin_key(key: &String, _val: &u64) -> bool {
     // Note: keys are stored with Debug format, so they have quotes
     key.contains("admin")
@@ -138,6 +141,7 @@ fn test_invalidation_check_with_thread_local() {
 }
 
 // Complex check function with multiple conditions
+#[allow(clippy::ptr_arg)]
 fn is_complex_stale(key: &String, val: &(String, u64)) -> bool {
     // Invalidate if key is "special" OR value's second element is > 100
     key.contains("special") || val.1 > 100
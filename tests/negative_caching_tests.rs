@@ -0,0 +1,98 @@
+//! Integration tests for negative caching (`cache_err`, `err_ttl`, `cache_none`)
+
+use cachelito::cache;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+static FLAKY_CALLS: AtomicUsize = AtomicUsize::new(0);
+static THREAD_FLAKY_CALLS: AtomicUsize = AtomicUsize::new(0);
+static DEFAULT_CALLS: AtomicUsize = AtomicUsize::new(0);
+static ALIAS_CALLS: AtomicUsize = AtomicUsize::new(0);
+static LOOKUP_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+mod app {
+    pub type Result<T> = std::result::Result<T, String>;
+}
+
+#[cache(scope = "global", cache_err = true, err_ttl = 1)]
+fn flaky_fetch(id: u32) -> Result<u32, String> {
+    FLAKY_CALLS.fetch_add(1, Ordering::SeqCst);
+    if id == 0 {
+        Err("service unavailable".to_string())
+    } else {
+        Ok(id * 10)
+    }
+}
+
+#[cache(scope = "thread", cache_err = true)]
+fn thread_flaky_fetch(id: u32) -> Result<u32, String> {
+    THREAD_FLAKY_CALLS.fetch_add(1, Ordering::SeqCst);
+    Err(format!("no item {}", id))
+}
+
+#[cache(scope = "global")]
+fn default_fetch(id: u32) -> Result<u32, String> {
+    DEFAULT_CALLS.fetch_add(1, Ordering::SeqCst);
+    Err(format!("no item {}", id))
+}
+
+#[cache(scope = "global")]
+fn alias_fetch(id: u32) -> app::Result<u32> {
+    ALIAS_CALLS.fetch_add(1, Ordering::SeqCst);
+    Err(format!("no item {}", id))
+}
+
+#[cache(scope = "global", cache_none = false)]
+fn lookup(id: u32) -> Option<String> {
+    LOOKUP_CALLS.fetch_add(1, Ordering::SeqCst);
+    (id % 2 == 0).then(|| format!("item {}", id))
+}
+
+#[test]
+fn test_errors_are_cached_until_err_ttl() {
+    assert!(flaky_fetch(0).is_err());
+    assert!(flaky_fetch(0).is_err());
+    assert_eq!(FLAKY_CALLS.load(Ordering::SeqCst), 1);
+
+    // Successful results keep the cache TTL (none here)
+    assert_eq!(flaky_fetch(1), Ok(10));
+
+    thread::sleep(Duration::from_millis(1100));
+    assert!(flaky_fetch(0).is_err());
+    assert_eq!(flaky_fetch(1), Ok(10));
+    assert_eq!(FLAKY_CALLS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_errors_cached_with_thread_scope() {
+    assert!(thread_flaky_fetch(1).is_err());
+    assert!(thread_flaky_fetch(1).is_err());
+    assert_eq!(THREAD_FLAKY_CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_errors_not_cached_by_default() {
+    assert!(default_fetch(1).is_err());
+    assert!(default_fetch(1).is_err());
+    assert_eq!(DEFAULT_CALLS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_result_alias_is_detected() {
+    assert!(alias_fetch(1).is_err());
+    assert!(alias_fetch(1).is_err());
+    assert_eq!(ALIAS_CALLS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_none_not_cached() {
+    assert_eq!(lookup(1), None);
+    assert_eq!(lookup(1), None);
+    assert_eq!(LOOKUP_CALLS.load(Ordering::SeqCst), 2);
+
+    assert_eq!(lookup(2), Some("item 2".to_string()));
+    assert_eq!(lookup(2), Some("item 2".to_string()));
+    assert_eq!(LOOKUP_CALLS.load(Ordering::SeqCst), 3);
+    assert_eq!(lookup_cache().len(), 1);
+}
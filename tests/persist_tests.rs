@@ -0,0 +1,119 @@
+//! Integration tests for disk snapshots (`persist = true`)
+#![cfg(feature = "persist")]
+
+use cachelito::{cache, persist};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SQUARE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cache(limit = 10, ttl = 60, persist = true)]
+fn square(n: u64) -> u64 {
+    SQUARE_CALLS.fetch_add(1, Ordering::SeqCst);
+    n * n
+}
+
+#[cache(name = "labels_v1", persist = true)]
+fn label(id: u32) -> Result<String, String> {
+    Ok(format!("label {}", id))
+}
+
+fn snapshot_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "cachelito-persist-tests-{}-{}",
+        test,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_snapshot_warms_up_cache() {
+    let dir = snapshot_dir("warm_up");
+    assert_eq!(square(3), 9);
+    assert_eq!(square(4), 16);
+    assert_eq!(SQUARE_CALLS.load(Ordering::SeqCst), 2);
+
+    let saved = persist::save_all(&dir).unwrap();
+    assert!(saved.contains(&"square".to_string()));
+    assert!(dir.join("square.snapshot").exists());
+
+    square_cache().clear();
+    let report = persist::load_all(&dir).unwrap();
+    assert!(report.loaded.contains(&"square".to_string()));
+    assert!(report.skipped.is_empty());
+
+    assert_eq!(square_cache().len(), 2);
+    assert_eq!(square(3), 9);
+    assert_eq!(SQUARE_CALLS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_custom_name_and_result_values() {
+    let dir = snapshot_dir("custom_name");
+    assert_eq!(label(1), Ok("label 1".to_string()));
+    assert!(persist::list().contains(&"labels_v1".to_string()));
+
+    persist::save_all(&dir).unwrap();
+    label_cache().clear();
+    persist::load_all(&dir).unwrap();
+    assert_eq!(label_cache().get(&1), Some(Ok("label 1".to_string())));
+}
+
+#[test]
+fn test_bad_snapshot_is_skipped() {
+    let dir = snapshot_dir("bad");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("square.snapshot"), "truncated").unwrap();
+
+    let report = persist::load_all(&dir).unwrap();
+    assert!(report.loaded.is_empty());
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].0, dir.join("square.snapshot"));
+}
+
+#[cache(limit = 16, persist = true, shards = 4)]
+fn sharded_double(n: u64) -> u64 {
+    n * 2
+}
+
+#[test]
+fn test_sharded_cache_snapshot() {
+    let dir = snapshot_dir("sharded");
+    for n in 0..10 {
+        sharded_double(n);
+    }
+
+    persist::save_all(&dir).unwrap();
+    sharded_double_cache().clear();
+    let report = persist::load_all(&dir).unwrap();
+    assert!(report.loaded.contains(&"sharded_double".to_string()));
+
+    assert_eq!(sharded_double_cache().len(), 10);
+    assert_eq!(sharded_double_cache().get(&7), Some(14));
+}
+
+#[cache(max_memory = 1000, persist = true)]
+fn padded(n: u64) -> String {
+    "x".repeat(100 + n as usize)
+}
+
+#[test]
+fn test_restored_entries_count_against_max_memory() {
+    let dir = snapshot_dir("max_memory");
+    for n in 0..3 {
+        padded(n);
+    }
+    let usage = padded_cache().memory_usage();
+    assert!(usage > 0);
+
+    persist::save_all(&dir).unwrap();
+    padded_cache().clear();
+    assert_eq!(padded_cache().memory_usage(), 0);
+    persist::load_all(&dir).unwrap();
+
+    assert_eq!(padded_cache().len(), 3);
+    assert_eq!(padded_cache().memory_usage(), usage);
+}
@@ -0,0 +1,78 @@
+//! Integration tests for `purge_expired` and `purge_all_expired`
+
+use cachelito::cache;
+use cachelito_core::{purge_all_expired, purge_expired, CacheableKey, RemovalCause};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+static EXPIRED: Mutex<Vec<(String, RemovalCause)>> = Mutex::new(Vec::new());
+
+fn on_expired(key: &str, _value: &u64, cause: RemovalCause) {
+    EXPIRED.lock().unwrap().push((key.to_string(), cause));
+}
+
+#[cache(scope = "global", ttl = 1, on_evict = on_expired)]
+fn short_lived(x: u64) -> u64 {
+    x
+}
+
+#[cache(scope = "global", ttl = 1, limit = 10, policy = "lru", shards = 4)]
+fn sharded(x: u64) -> u64 {
+    x
+}
+
+#[cache(scope = "global", ttl = 1)]
+fn generic<T: CacheableKey + Clone + Send + Sync>(value: T) -> T {
+    value
+}
+
+#[cache(scope = "global", limit = 10)]
+fn eternal(x: u64) -> u64 {
+    x
+}
+
+#[test]
+fn test_purge_expired_by_name() {
+    for x in 0..5 {
+        short_lived(x);
+    }
+    assert_eq!(purge_expired("short_lived"), Some(0));
+
+    thread::sleep(Duration::from_millis(1100));
+    short_lived(100);
+    assert_eq!(purge_expired("short_lived"), Some(5));
+
+    // Only the fresh entry is left, and the listener saw the expired ones
+    assert_eq!(short_lived_cache().len(), 1);
+    let expired = EXPIRED.lock().unwrap();
+    assert_eq!(expired.len(), 5);
+    assert!(expired
+        .iter()
+        .all(|(_, cause)| *cause == RemovalCause::Expired));
+}
+
+#[test]
+fn test_purge_sharded_and_generic_caches() {
+    for x in 0..8 {
+        sharded(x);
+    }
+    generic(1u32);
+    generic("one".to_string());
+
+    thread::sleep(Duration::from_millis(1100));
+    assert_eq!(purge_expired("sharded"), Some(8));
+    assert_eq!(sharded_cache().len(), 0);
+    assert_eq!(purge_expired("generic"), Some(2));
+}
+
+#[test]
+fn test_purge_without_ttl_or_registration() {
+    eternal(1);
+    assert_eq!(purge_expired("eternal"), Some(0));
+    assert_eq!(eternal_cache().len(), 1);
+
+    assert_eq!(purge_expired("never_called"), None);
+    purge_all_expired();
+    assert_eq!(eternal_cache().len(), 1);
+}
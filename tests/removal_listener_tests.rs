@@ -0,0 +1,184 @@
+//! Integration tests for removal listeners (`on_evict`)
+
+use cachelito::cache;
+use cachelito_core::{invalidate_by_tag, invalidate_cache, invalidate_with, RemovalCause};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+type Removed = Mutex<Vec<(String, u64, RemovalCause)>>;
+
+static CAPACITY_REMOVED: Removed = Mutex::new(Vec::new());
+static EXPIRED_REMOVED: Removed = Mutex::new(Vec::new());
+static REPLACED_REMOVED: Removed = Mutex::new(Vec::new());
+static INVALIDATED_REMOVED: Removed = Mutex::new(Vec::new());
+static TAG_REMOVED: Removed = Mutex::new(Vec::new());
+static THREAD_REMOVED: Removed = Mutex::new(Vec::new());
+
+fn on_capacity(key: &str, value: &u64, cause: RemovalCause) {
+    CAPACITY_REMOVED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+fn on_expired(key: &str, value: &u64, cause: RemovalCause) {
+    EXPIRED_REMOVED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+fn on_replaced(key: &str, value: &u64, cause: RemovalCause) {
+    REPLACED_REMOVED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+fn on_invalidated(key: &str, value: &u64, cause: RemovalCause) {
+    INVALIDATED_REMOVED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+fn on_tag(key: &str, value: &u64, cause: RemovalCause) {
+    TAG_REMOVED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+fn on_thread(key: &str, value: &u64, cause: RemovalCause) {
+    THREAD_REMOVED
+        .lock()
+        .unwrap()
+        .push((key.to_string(), *value, cause));
+}
+
+fn always_stale(_key: &String, _value: &u64) -> bool {
+    true
+}
+
+#[cache(scope = "global", limit = 2, policy = "fifo", on_evict = on_capacity)]
+fn capacity_square(x: u64) -> u64 {
+    x * x
+}
+
+#[cache(scope = "global", ttl = 1, on_evict = on_expired)]
+fn expiring_double(x: u64) -> u64 {
+    x * 2
+}
+
+#[cache(scope = "global", invalidate_on = always_stale, on_evict = on_replaced)]
+fn replaced_triple(x: u64) -> u64 {
+    x * 3
+}
+
+#[cache(
+    scope = "global",
+    name = "removal_invalidated",
+    limit = 10,
+    on_evict = on_invalidated
+)]
+fn invalidated_plus_one(x: u64) -> u64 {
+    x + 1
+}
+
+#[cache(scope = "global", limit = 10, tags = ["removal_tag"], on_evict = on_tag)]
+fn tagged_plus_two(x: u64) -> u64 {
+    x + 2
+}
+
+#[cache(scope = "thread", limit = 1, on_evict = on_thread)]
+fn thread_plus_three(x: u64) -> u64 {
+    x + 3
+}
+
+#[test]
+fn test_capacity_eviction_notifies_listener() {
+    capacity_square(1);
+    capacity_square(2);
+    assert!(CAPACITY_REMOVED.lock().unwrap().is_empty());
+
+    capacity_square(3);
+
+    let removed = CAPACITY_REMOVED.lock().unwrap();
+    assert_eq!(*removed, vec![("1".to_string(), 1, RemovalCause::Capacity)]);
+}
+
+#[test]
+fn test_expiration_notifies_listener() {
+    expiring_double(5);
+    thread::sleep(Duration::from_millis(1100));
+    expiring_double(5);
+
+    let removed = EXPIRED_REMOVED.lock().unwrap();
+    assert_eq!(*removed, vec![("5".to_string(), 10, RemovalCause::Expired)]);
+}
+
+#[test]
+fn test_replacement_notifies_listener() {
+    replaced_triple(4);
+    assert!(REPLACED_REMOVED.lock().unwrap().is_empty());
+
+    // The invalidation check rejects the cached value, so it is recomputed and replaced
+    replaced_triple(4);
+
+    let removed = REPLACED_REMOVED.lock().unwrap();
+    assert_eq!(
+        *removed,
+        vec![("4".to_string(), 12, RemovalCause::Replaced)]
+    );
+}
+
+#[test]
+fn test_invalidation_notifies_listener() {
+    invalidated_plus_one(1);
+    invalidated_plus_one(2);
+    invalidated_plus_one(3);
+
+    assert!(invalidate_with("removal_invalidated", |key: &str| key == "2"));
+    assert_eq!(
+        *INVALIDATED_REMOVED.lock().unwrap(),
+        vec![("2".to_string(), 3, RemovalCause::Invalidated)]
+    );
+
+    INVALIDATED_REMOVED.lock().unwrap().clear();
+    invalidate_with("removal_invalidated", |_key: &str| true);
+
+    let mut removed = INVALIDATED_REMOVED.lock().unwrap().clone();
+    removed.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        removed,
+        vec![
+            ("1".to_string(), 2, RemovalCause::Invalidated),
+            ("3".to_string(), 4, RemovalCause::Invalidated),
+        ]
+    );
+}
+
+#[test]
+fn test_tag_invalidation_notifies_listener() {
+    tagged_plus_two(1);
+    tagged_plus_two(2);
+
+    assert_eq!(invalidate_by_tag("removal_tag"), 1);
+    assert!(invalidate_cache("tagged_plus_two"));
+
+    let removed = TAG_REMOVED.lock().unwrap().clone();
+    assert_eq!(removed.len(), 2);
+    assert!(removed
+        .iter()
+        .all(|(_, _, cause)| *cause == RemovalCause::Invalidated));
+}
+
+#[test]
+fn test_thread_local_eviction_notifies_listener() {
+    thread_plus_three(1);
+    thread_plus_three(2);
+
+    let removed = THREAD_REMOVED.lock().unwrap();
+    assert_eq!(*removed, vec![("1".to_string(), 4, RemovalCause::Capacity)]);
+}
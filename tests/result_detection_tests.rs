@@ -0,0 +1,109 @@
+//! Integration tests for Result detection (`result = true|false`, `CacheableOutcome`)
+
+use cachelito::{cache, CacheableOutcome};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+static ALIAS_CALLS: AtomicUsize = AtomicUsize::new(0);
+static FORCED_CALLS: AtomicUsize = AtomicUsize::new(0);
+static LOOKUP_CALLS: AtomicUsize = AtomicUsize::new(0);
+static RETRY_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, PartialEq)]
+struct MyErr;
+
+/// An alias whose name gives no hint that it is a `Result`
+type Fallible<T> = Result<T, MyErr>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Lookup {
+    Found(u32),
+    NotFound,
+    Unavailable,
+}
+
+impl CacheableOutcome for Lookup {
+    fn is_cacheable(&self) -> bool {
+        !matches!(self, Lookup::Unavailable)
+    }
+}
+
+#[cache(scope = "global", result = true)]
+fn alias_fetch(id: u32) -> Fallible<u32> {
+    ALIAS_CALLS.fetch_add(1, Ordering::SeqCst);
+    if id == 0 {
+        Err(MyErr)
+    } else {
+        Ok(id)
+    }
+}
+
+#[cache(scope = "global", result = false)]
+fn forced_plain(id: u32) -> Result<u32, MyErr> {
+    FORCED_CALLS.fetch_add(1, Ordering::SeqCst);
+    Err(MyErr)
+}
+
+#[cache(scope = "global", result = true)]
+fn lookup(id: u32) -> Lookup {
+    LOOKUP_CALLS.fetch_add(1, Ordering::SeqCst);
+    match id {
+        0 => Lookup::Unavailable,
+        1 => Lookup::NotFound,
+        _ => Lookup::Found(id),
+    }
+}
+
+#[cache(scope = "thread", result = true, cache_err = true, err_ttl = 1)]
+fn lookup_with_retry(id: u32) -> Lookup {
+    RETRY_CALLS.fetch_add(1, Ordering::SeqCst);
+    if id == 0 {
+        Lookup::Unavailable
+    } else {
+        Lookup::Found(id)
+    }
+}
+
+#[test]
+fn test_result_true_on_unrecognized_alias() {
+    assert_eq!(alias_fetch(0), Err(MyErr));
+    assert_eq!(alias_fetch(0), Err(MyErr));
+    assert_eq!(ALIAS_CALLS.load(Ordering::SeqCst), 2);
+
+    assert_eq!(alias_fetch(1), Ok(1));
+    assert_eq!(alias_fetch(1), Ok(1));
+    assert_eq!(ALIAS_CALLS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_result_false_caches_errors() {
+    assert_eq!(forced_plain(1), Err(MyErr));
+    assert_eq!(forced_plain(1), Err(MyErr));
+    assert_eq!(FORCED_CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_custom_outcome() {
+    assert_eq!(lookup(0), Lookup::Unavailable);
+    assert_eq!(lookup(0), Lookup::Unavailable);
+    assert_eq!(lookup(1), Lookup::NotFound);
+    assert_eq!(lookup(1), Lookup::NotFound);
+    assert_eq!(lookup(2), Lookup::Found(2));
+    assert_eq!(lookup(2), Lookup::Found(2));
+
+    // Only the two cacheable outcomes were stored
+    assert_eq!(LOOKUP_CALLS.load(Ordering::SeqCst), 4);
+    assert_eq!(lookup_cache().len(), 2);
+}
+
+#[test]
+fn test_custom_outcome_with_err_ttl() {
+    assert_eq!(lookup_with_retry(0), Lookup::Unavailable);
+    assert_eq!(lookup_with_retry(0), Lookup::Unavailable);
+    assert_eq!(RETRY_CALLS.load(Ordering::SeqCst), 1);
+
+    thread::sleep(Duration::from_millis(1100));
+    assert_eq!(lookup_with_retry(0), Lookup::Unavailable);
+    assert_eq!(RETRY_CALLS.load(Ordering::SeqCst), 2);
+}
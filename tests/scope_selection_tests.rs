@@ -0,0 +1,37 @@
+//! Integration tests for compile-time scope selection
+
+use cachelito::cache;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// `Rc` is neither `Send` nor `Sync`, which only a thread-local cache can store
+#[cache(scope = "thread", limit = 10)]
+fn shared_numbers(n: usize) -> Rc<Vec<usize>> {
+    Rc::new((0..n).collect::<Vec<_>>())
+}
+
+#[cache(scope = "thread")]
+fn counter(start: u32) -> Rc<RefCell<u32>> {
+    Rc::new(RefCell::new(start))
+}
+
+#[test]
+fn test_thread_scope_supports_non_send_values() {
+    let first = shared_numbers(3);
+    let second = shared_numbers(3);
+
+    assert_eq!(*first, vec![0, 1, 2]);
+    // The cached `Rc` is cloned, so both point to the same allocation
+    assert!(Rc::ptr_eq(&first, &second));
+    assert_eq!(shared_numbers_cache().len(), 1);
+}
+
+#[test]
+fn test_thread_scope_values_are_shared_within_thread() {
+    *counter(1).borrow_mut() += 1;
+    assert_eq!(*counter(1).borrow(), 2);
+
+    std::thread::spawn(|| assert_eq!(*counter(1).borrow(), 1))
+        .join()
+        .unwrap();
+}
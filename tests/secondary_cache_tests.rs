@@ -0,0 +1,94 @@
+//! Integration tests for two-tier caching (`l2 = PATH`)
+
+use cachelito::{cache, FileStore, SecondaryCache, ValueSerializer};
+use once_cell::sync::Lazy;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Decimal;
+
+impl ValueSerializer<u64> for Decimal {
+    fn serialize(&self, value: &u64) -> Option<Vec<u8>> {
+        Some(value.to_string().into_bytes())
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> Option<u64> {
+        std::str::from_utf8(bytes).ok()?.parse().ok()
+    }
+}
+
+struct Utf8;
+
+impl ValueSerializer<String> for Utf8 {
+    fn serialize(&self, value: &String) -> Option<Vec<u8>> {
+        Some(value.as_bytes().to_vec())
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> Option<String> {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+fn store_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "cachelito-l2-tests-{}-{}",
+        name,
+        std::process::id()
+    ))
+}
+
+static SQUARE_CALLS: AtomicUsize = AtomicUsize::new(0);
+static BLOB_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+static SQUARES_L2: Lazy<SecondaryCache<u64>> =
+    Lazy::new(|| SecondaryCache::new(FileStore::new(store_dir("squares")).unwrap(), Decimal));
+
+static BLOBS_L2: Lazy<SecondaryCache<String>> =
+    Lazy::new(|| SecondaryCache::new(FileStore::new(store_dir("blobs")).unwrap(), Utf8));
+
+#[cache(limit = 2, policy = "lru", l2 = SQUARES_L2)]
+fn square(n: u64) -> u64 {
+    SQUARE_CALLS.fetch_add(1, Ordering::SeqCst);
+    n * n
+}
+
+#[cache(max_memory = "1KB", l2 = BLOBS_L2)]
+fn blob(len: usize) -> String {
+    BLOB_CALLS.fetch_add(1, Ordering::SeqCst);
+    "x".repeat(len)
+}
+
+#[test]
+fn test_evicted_entries_are_demoted_and_promoted() {
+    assert_eq!(square(1), 1);
+    assert_eq!(square(2), 4);
+    assert_eq!(square(3), 9);
+    assert_eq!(square_cache().len(), 2);
+    assert!(SQUARES_L2.contains_key("1"));
+
+    // Served from the secondary tier, which moves 2 out of memory in turn
+    assert_eq!(square(1), 1);
+    assert_eq!(SQUARE_CALLS.load(Ordering::SeqCst), 3);
+    assert!(!SQUARES_L2.contains_key("1"));
+    assert!(SQUARES_L2.contains_key("2"));
+
+    // Removing an entry also removes its demoted copy
+    assert!(square_cache().invalidate(&2));
+    assert!(SQUARES_L2.is_empty());
+    assert_eq!(square(2), 4);
+    assert_eq!(SQUARE_CALLS.load(Ordering::SeqCst), 4);
+
+    square_cache().clear();
+    assert!(SQUARES_L2.is_empty());
+}
+
+#[test]
+fn test_values_too_large_for_memory_live_in_secondary_tier() {
+    assert_eq!(blob(2000).len(), 2000);
+    assert_eq!(blob_cache().len(), 0);
+    assert!(BLOBS_L2.contains_key("2000"));
+
+    assert_eq!(blob(2000).len(), 2000);
+    assert_eq!(blob(2000).len(), 2000);
+    assert_eq!(BLOB_CALLS.load(Ordering::SeqCst), 1);
+}
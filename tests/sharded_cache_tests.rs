@@ -0,0 +1,96 @@
+//! Integration tests for sharded global caches (`shards = N`)
+
+use cachelito::{cache, invalidate_by_tag};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+static SQUARE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cache(scope = "global", shards = 4)]
+fn square(x: u64) -> u64 {
+    SQUARE_CALLS.fetch_add(1, Ordering::SeqCst);
+    x * x
+}
+
+#[cache(scope = "global", limit = 8, policy = "lru", shards = 4)]
+fn bounded(x: u64) -> u64 {
+    x + 1
+}
+
+#[cache(scope = "global", limit = 2, shards = 8)]
+fn tiny(x: u64) -> u64 {
+    x
+}
+
+#[cache(scope = "global", shards = 4, tags = ["sharded"])]
+fn tagged(x: u64) -> String {
+    x.to_string()
+}
+
+#[cache(scope = "global", limit = 64, policy = "lru", shards = 8)]
+fn contended(x: u64) -> u64 {
+    x * 3
+}
+
+#[test]
+fn test_sharded_cache_hits() {
+    for x in 0..20 {
+        assert_eq!(square(x), x * x);
+    }
+    for x in 0..20 {
+        assert_eq!(square(x), x * x);
+    }
+    assert_eq!(SQUARE_CALLS.load(Ordering::SeqCst), 20);
+    assert_eq!(square_cache().len(), 20);
+
+    assert!(square_cache().invalidate(&3));
+    assert_eq!(square_cache().get(&3), None);
+    assert_eq!(square_cache().len(), 19);
+}
+
+#[test]
+fn test_shard_limits_sum_to_limit() {
+    for x in 0..100 {
+        bounded(x);
+    }
+    assert_eq!(bounded_cache().len(), 8);
+    // The most recent entry always survives in its shard
+    assert_eq!(bounded_cache().get(&99), Some(100));
+}
+
+#[test]
+fn test_limit_below_shard_count() {
+    for x in 0..50 {
+        tiny(x);
+    }
+    assert_eq!(tiny_cache().len(), 2);
+}
+
+#[test]
+fn test_invalidation_clears_every_shard() {
+    for x in 0..16 {
+        tagged(x);
+    }
+    assert_eq!(tagged_cache().len(), 16);
+
+    invalidate_by_tag("sharded");
+    assert!(tagged_cache().is_empty());
+}
+
+#[test]
+fn test_concurrent_access() {
+    let handles: Vec<_> = (0..8)
+        .map(|t| {
+            thread::spawn(move || {
+                for i in 0..1000 {
+                    let x = (i * 7 + t) % 128;
+                    assert_eq!(contended(x), x * 3);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert!(contended_cache().len() <= 64);
+}
@@ -0,0 +1,87 @@
+//! Integration tests for values shared through an `Arc` (`arc = true`)
+
+use cachelito::cache;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+static MATRIX_CLONES: AtomicUsize = AtomicUsize::new(0);
+static MODEL_CALLS: AtomicUsize = AtomicUsize::new(0);
+static LOOKUP_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts its clones, standing for a large value
+#[derive(Debug, PartialEq)]
+struct Matrix(Vec<f64>);
+
+impl Clone for Matrix {
+    fn clone(&self) -> Self {
+        MATRIX_CLONES.fetch_add(1, Ordering::SeqCst);
+        Matrix(self.0.clone())
+    }
+}
+
+/// Not `Clone`: only cacheable behind an `Arc`
+#[derive(Debug)]
+struct Model {
+    id: u64,
+}
+
+#[cache(scope = "global", limit = 10, policy = "lru", arc = true)]
+fn load_matrix(id: u64) -> Matrix {
+    Matrix(vec![id as f64; 1000])
+}
+
+#[cache(scope = "global", limit = 10, arc = true)]
+fn load_model(id: u64) -> Arc<Model> {
+    MODEL_CALLS.fetch_add(1, Ordering::SeqCst);
+    Arc::new(Model { id })
+}
+
+#[cache(scope = "thread", arc = true)]
+fn lookup(id: u64) -> Result<Vec<u8>, String> {
+    LOOKUP_CALLS.fetch_add(1, Ordering::SeqCst);
+    if id == 0 {
+        Err("missing".to_string())
+    } else {
+        Ok(vec![id as u8; 16])
+    }
+}
+
+#[test]
+fn test_cache_stores_arc_and_returns_value() {
+    let before = MATRIX_CLONES.load(Ordering::SeqCst);
+    assert_eq!(load_matrix(1), Matrix(vec![1.0; 1000]));
+    // The result is cloned once into the cache
+    assert_eq!(MATRIX_CLONES.load(Ordering::SeqCst), before + 1);
+
+    // Each hit clones the value once, out of the shared entry
+    assert_eq!(load_matrix(1), Matrix(vec![1.0; 1000]));
+    assert_eq!(MATRIX_CLONES.load(Ordering::SeqCst), before + 2);
+
+    // The handle reads the shared entry without cloning the value
+    let first: Arc<Matrix> = load_matrix_cache().get(&1).unwrap();
+    let second = load_matrix_cache().get(&1).unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(MATRIX_CLONES.load(Ordering::SeqCst), before + 2);
+}
+
+#[test]
+fn test_arc_return_types_are_shared_without_clone() {
+    let first = load_model(7);
+    let second = load_model(7);
+    assert_eq!(first.id, 7);
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(MODEL_CALLS.load(Ordering::SeqCst), 1);
+    assert!(Arc::ptr_eq(&load_model_cache().get(&7).unwrap(), &first));
+}
+
+#[test]
+fn test_shared_results_only_cache_ok() {
+    assert_eq!(lookup(0), Err("missing".to_string()));
+    assert_eq!(lookup(0), Err("missing".to_string()));
+    assert_eq!(LOOKUP_CALLS.load(Ordering::SeqCst), 2);
+
+    assert_eq!(lookup(3), Ok(vec![3; 16]));
+    assert_eq!(lookup(3), Ok(vec![3; 16]));
+    assert_eq!(LOOKUP_CALLS.load(Ordering::SeqCst), 3);
+    assert_eq!(lookup_cache().len(), 1);
+}
@@ -0,0 +1,76 @@
+//! Integration tests for the SIEVE eviction policy
+
+use cachelito::cache;
+use cachelito_core::RemovalCause;
+use std::sync::Mutex;
+
+type Evicted = Mutex<Vec<u64>>;
+
+static RESUMED_EVICTED: Evicted = Mutex::new(Vec::new());
+static PASS_EVICTED: Evicted = Mutex::new(Vec::new());
+
+fn record(evicted: &Evicted, value: u64, cause: RemovalCause) {
+    assert_eq!(cause, RemovalCause::Capacity);
+    evicted.lock().unwrap().push(value);
+}
+
+fn on_resumed(_key: &str, value: &u64, cause: RemovalCause) {
+    record(&RESUMED_EVICTED, *value, cause);
+}
+
+fn on_pass(_key: &str, value: &u64, cause: RemovalCause) {
+    record(&PASS_EVICTED, *value, cause);
+}
+
+#[cache(scope = "global", limit = 4, policy = "sieve", on_evict = on_resumed)]
+fn resumed(n: u64) -> u64 {
+    n
+}
+
+#[cache(scope = "thread", limit = 3, policy = "sieve", on_evict = on_pass)]
+fn pass(n: u64) -> u64 {
+    n
+}
+
+/// The hand stays where it evicted: the next eviction resumes there instead of going
+/// back to the entries it already passed
+#[test]
+fn test_sieve_hand_resumes_where_it_stopped() {
+    for n in 1..=4 {
+        resumed(n);
+    }
+    resumed(1);
+    resumed(2);
+
+    // The hand passes 1 and 2, clearing their visited bits, and evicts 3. It stays
+    // between 2 and 4, so 1 and 2 outlive every new entry accessed only once
+    for n in 5..=8 {
+        resumed(n);
+    }
+    assert_eq!(*RESUMED_EVICTED.lock().unwrap(), [3, 4, 5, 6]);
+}
+
+/// Visited entries survive one pass of the hand, and are evicted by the next one unless
+/// they are visited again
+#[test]
+fn test_sieve_visited_entries_survive_one_pass() {
+    for n in 1..=3 {
+        pass(n);
+    }
+    pass(1);
+    pass(2);
+
+    // First pass: 1 and 2 survive, 3 is evicted
+    pass(4);
+    assert_eq!(*PASS_EVICTED.lock().unwrap(), [3]);
+
+    // The hand passes 4 (visited), then starts over: 1 was not visited since
+    pass(4);
+    pass(5);
+    assert_eq!(*PASS_EVICTED.lock().unwrap(), [3, 1]);
+
+    // 2 is visited again, and survives this pass too
+    pass(2);
+    pass(6);
+    assert_eq!(*PASS_EVICTED.lock().unwrap(), [3, 1, 4]);
+}
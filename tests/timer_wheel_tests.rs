@@ -0,0 +1,83 @@
+//! Integration tests for expirations tracked by the timer wheel of TTL caches
+
+use cachelito::cache;
+use cachelito_core::purge_expired;
+use std::thread;
+use std::time::Duration;
+
+#[cache(scope = "global", ttl = 1)]
+fn many(x: u64) -> u64 {
+    x
+}
+
+#[cache(scope = "global", cache_err = true, err_ttl = 1)]
+fn fetch(id: u32) -> Result<u32, String> {
+    if id % 2 == 0 {
+        Err(format!("no item {}", id))
+    } else {
+        Ok(id)
+    }
+}
+
+#[cache(scope = "global", ttl = 2)]
+fn recomputed(x: u64) -> u64 {
+    x
+}
+
+#[cache(scope = "thread", ttl = 1)]
+fn local(x: u64) -> u64 {
+    x
+}
+
+#[test]
+fn test_purge_many_expired_entries() {
+    for x in 0..500 {
+        many(x);
+    }
+    assert_eq!(purge_expired("many"), Some(0));
+
+    thread::sleep(Duration::from_millis(1100));
+    assert_eq!(purge_expired("many"), Some(500));
+    assert_eq!(purge_expired("many"), Some(0));
+    assert_eq!(many_cache().len(), 0);
+}
+
+#[test]
+fn test_per_entry_ttl_is_tracked() {
+    for id in 0..6 {
+        let _ = fetch(id);
+    }
+
+    // Only the errors have a TTL
+    thread::sleep(Duration::from_millis(1100));
+    assert_eq!(purge_expired("fetch"), Some(3));
+    assert_eq!(fetch_cache().len(), 3);
+}
+
+#[test]
+fn test_timers_of_replaced_entries_are_ignored() {
+    recomputed(1);
+    assert!(recomputed_cache().invalidate(&1));
+    thread::sleep(Duration::from_millis(1100));
+    recomputed(1);
+
+    // The timer of the first entry is due, but the entry was replaced since
+    thread::sleep(Duration::from_millis(1000));
+    assert_eq!(purge_expired("recomputed"), Some(0));
+    assert_eq!(recomputed_cache().len(), 1);
+
+    thread::sleep(Duration::from_millis(1100));
+    assert_eq!(purge_expired("recomputed"), Some(1));
+}
+
+#[test]
+fn test_thread_local_purge() {
+    for x in 0..10 {
+        local(x);
+    }
+    assert_eq!(local_cache().purge_expired(), 0);
+
+    thread::sleep(Duration::from_millis(1100));
+    assert_eq!(local_cache().purge_expired(), 10);
+    assert_eq!(local_cache().len(), 0);
+}